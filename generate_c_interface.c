@@ -35,22 +35,84 @@ void generate_function(json_t *idl, const char *program_name, FILE *output_file)
         const char *instr_name = json_string_value(json_object_get(instr, "name"));
         json_t *accounts = json_object_get(instr, "accounts");
 
+        size_t account_count = json_array_size(accounts);
+
         fprintf(output_file, "// Function to call '%s' dynamically\n", instr_name);
         fprintf(output_file,
                 "char *%s_%s_c(SolClient *client, const char *program_id, SolPublicKey *accounts, size_t account_count, SolKeyPair **signers, size_t signer_count) {\n",
                 program_name, instr_name);
 
-        // Start the body of the function
-        fprintf(output_file, "    return send_generic_transaction_c(\n");
-        fprintf(output_file, "        client,\n");
-        fprintf(output_file, "        program_id,\n");
-        fprintf(output_file, "        \"%s\",\n", instr_name);
-        fprintf(output_file, "        accounts,\n");
+        // Signer and writable flags come from the IDL; accounts beyond the IDL list are passed read-only
+        fprintf(output_file, "    // Account flags from the IDL: {is_signer, is_writable}\n");
+        fprintf(output_file, "    static const bool account_flags[][2] = {\n");
+        size_t account_index;
+        json_t *account;
+        json_array_foreach(accounts, account_index, account)
+        {
+            fprintf(output_file, "        {%s, %s}, // %s\n",
+                    json_is_true(json_object_get(account, "signer")) ? "true" : "false",
+                    json_is_true(json_object_get(account, "writable")) ? "true" : "false",
+                    json_string_value(json_object_get(account, "name")));
+        }
+        if (account_count == 0)
+        {
+            fprintf(output_file, "        {false, false},\n");
+        }
+        fprintf(output_file, "    };\n");
+        fprintf(output_file, "    size_t idl_account_count = %zu;\n\n", account_count);
+
+        fprintf(output_file, "    SolAccountMeta *metas = malloc(account_count * sizeof(SolAccountMeta));\n");
+        fprintf(output_file, "    for (size_t i = 0; i < account_count; i++) {\n");
+        fprintf(output_file, "        metas[i].pubkey = accounts[i];\n");
+        fprintf(output_file, "        metas[i].is_signer = i < idl_account_count && account_flags[i][0];\n");
+        fprintf(output_file, "        metas[i].is_writable = i < idl_account_count && account_flags[i][1];\n");
+        fprintf(output_file, "    }\n\n");
+
+        // Prefer the discriminator recorded in the IDL; older IDLs fall back to the "global:<name>" hash
+        json_t *discriminator = json_object_get(instr, "discriminator");
+        if (json_is_array(discriminator) && json_array_size(discriminator) > 0)
+        {
+            fprintf(output_file, "    static const uint8_t discriminator[] = {");
+            size_t byte_index;
+            json_t *byte;
+            json_array_foreach(discriminator, byte_index, byte)
+            {
+                fprintf(output_file, "%s%lld", byte_index > 0 ? ", " : "", json_integer_value(byte));
+            }
+            fprintf(output_file, "};\n\n");
+
+            fprintf(output_file, "    char *result = send_generic_transaction_with_discriminator(\n");
+            fprintf(output_file, "        client,\n");
+            fprintf(output_file, "        program_id,\n");
+            fprintf(output_file, "        \"%s\",\n", instr_name);
+            fprintf(output_file, "        SolDiscriminatorKind_Custom,\n");
+            fprintf(output_file, "        discriminator,\n");
+            fprintf(output_file, "        sizeof(discriminator),\n");
+        }
+        else
+        {
+            fprintf(output_file, "    char *result = send_generic_transaction_with_metas(\n");
+            fprintf(output_file, "        client,\n");
+            fprintf(output_file, "        program_id,\n");
+            fprintf(output_file, "        \"%s\",\n", instr_name);
+        }
+        fprintf(output_file, "        metas,\n");
         fprintf(output_file, "        account_count,\n");
         fprintf(output_file, "        signers,\n");
         fprintf(output_file, "        signer_count,\n");
         fprintf(output_file, "        NULL,\n");
-        fprintf(output_file, "        0);\n");
+        if (json_is_array(discriminator) && json_array_size(discriminator) > 0)
+        {
+            // No separate fee payer: the first signer pays
+            fprintf(output_file, "        0,\n");
+            fprintf(output_file, "        NULL);\n");
+        }
+        else
+        {
+            fprintf(output_file, "        0);\n");
+        }
+        fprintf(output_file, "    free(metas);\n");
+        fprintf(output_file, "    return result;\n");
         fprintf(output_file, "}\n\n");
     }
 }
@@ -101,7 +163,7 @@ int main(int argc, char *argv[])
 
     // Write file header
     fprintf(output_file, "// Auto-generated C interface for Solana Program: %s\n\n", program_name);
-    fprintf(output_file, "#include <stdio.h>\n#include <stdint.h>\n#include <stdlib.h>\n#include \"solana_sdk.h\"\n\n");
+    fprintf(output_file, "#include <stdio.h>\n#include <stdbool.h>\n#include <stdint.h>\n#include <stdlib.h>\n#include \"solana_sdk.h\"\n\n");
 
     // Generate dynamic function signatures
     generate_function(idl, program_name, output_file);
@@ -1,49 +1,117 @@
 // Auto-generated C interface for Solana Program: anchor_counter
 
 #include <stdio.h>
+#include <stdbool.h>
 #include <stdint.h>
 #include <stdlib.h>
 #include "solana_sdk.h"
 
 // Function to call 'decrement' dynamically
 char *anchor_counter_decrement_c(SolClient *client, const char *program_id, SolPublicKey *accounts, size_t account_count, SolKeyPair **signers, size_t signer_count) {
-    return send_generic_transaction_c(
+    // Account flags from the IDL: {is_signer, is_writable}
+    static const bool account_flags[][2] = {
+        {false, true}, // counter
+        {true, false}, // user
+    };
+    size_t idl_account_count = 2;
+
+    SolAccountMeta *metas = malloc(account_count * sizeof(SolAccountMeta));
+    for (size_t i = 0; i < account_count; i++) {
+        metas[i].pubkey = accounts[i];
+        metas[i].is_signer = i < idl_account_count && account_flags[i][0];
+        metas[i].is_writable = i < idl_account_count && account_flags[i][1];
+    }
+
+    static const uint8_t discriminator[] = {106, 227, 168, 59, 248, 27, 150, 101};
+
+    char *result = send_generic_transaction_with_discriminator(
         client,
         program_id,
         "decrement",
-        accounts,
+        SolDiscriminatorKind_Custom,
+        discriminator,
+        sizeof(discriminator),
+        metas,
         account_count,
         signers,
         signer_count,
         NULL,
-        0);
+        0,
+        NULL);
+    free(metas);
+    return result;
 }
 
 // Function to call 'increment' dynamically
 char *anchor_counter_increment_c(SolClient *client, const char *program_id, SolPublicKey *accounts, size_t account_count, SolKeyPair **signers, size_t signer_count) {
-    return send_generic_transaction_c(
+    // Account flags from the IDL: {is_signer, is_writable}
+    static const bool account_flags[][2] = {
+        {false, true}, // counter
+        {true, false}, // user
+    };
+    size_t idl_account_count = 2;
+
+    SolAccountMeta *metas = malloc(account_count * sizeof(SolAccountMeta));
+    for (size_t i = 0; i < account_count; i++) {
+        metas[i].pubkey = accounts[i];
+        metas[i].is_signer = i < idl_account_count && account_flags[i][0];
+        metas[i].is_writable = i < idl_account_count && account_flags[i][1];
+    }
+
+    static const uint8_t discriminator[] = {11, 18, 104, 9, 104, 174, 59, 33};
+
+    char *result = send_generic_transaction_with_discriminator(
         client,
         program_id,
         "increment",
-        accounts,
+        SolDiscriminatorKind_Custom,
+        discriminator,
+        sizeof(discriminator),
+        metas,
         account_count,
         signers,
         signer_count,
         NULL,
-        0);
+        0,
+        NULL);
+    free(metas);
+    return result;
 }
 
 // Function to call 'initialize' dynamically
 char *anchor_counter_initialize_c(SolClient *client, const char *program_id, SolPublicKey *accounts, size_t account_count, SolKeyPair **signers, size_t signer_count) {
-    return send_generic_transaction_c(
+    // Account flags from the IDL: {is_signer, is_writable}
+    static const bool account_flags[][2] = {
+        {true, true}, // counter
+        {true, true}, // user
+        {false, false}, // system_program
+    };
+    size_t idl_account_count = 3;
+
+    SolAccountMeta *metas = malloc(account_count * sizeof(SolAccountMeta));
+    for (size_t i = 0; i < account_count; i++) {
+        metas[i].pubkey = accounts[i];
+        metas[i].is_signer = i < idl_account_count && account_flags[i][0];
+        metas[i].is_writable = i < idl_account_count && account_flags[i][1];
+    }
+
+    static const uint8_t discriminator[] = {175, 175, 109, 31, 13, 152, 155, 237};
+
+    char *result = send_generic_transaction_with_discriminator(
         client,
         program_id,
         "initialize",
-        accounts,
+        SolDiscriminatorKind_Custom,
+        discriminator,
+        sizeof(discriminator),
+        metas,
         account_count,
         signers,
         signer_count,
         NULL,
-        0);
+        0,
+        NULL);
+    free(metas);
+    return result;
 }
 
@@ -11,17 +11,184 @@ extern "C" {
 #include <stdint.h>
 #include <stdlib.h>
 
+typedef enum SolKeystoreStatus {
+  SolKeystoreStatus_Ok,
+  SolKeystoreStatus_IoError,
+  SolKeystoreStatus_CorruptFile,
+  SolKeystoreStatus_UnsupportedVersion,
+  SolKeystoreStatus_WrongPassword,
+  SolKeystoreStatus_InvalidArgument,
+} SolKeystoreStatus;
+
+typedef enum SolMnemonicStatus {
+  SolMnemonicStatus_Valid,
+  SolMnemonicStatus_InvalidWordCount,
+  SolMnemonicStatus_InvalidWord,
+  SolMnemonicStatus_InvalidChecksum,
+  SolMnemonicStatus_InvalidEncoding,
+} SolMnemonicStatus;
+
+typedef enum SolDerivationScheme {
+  SolDerivationScheme_SolanaCli,
+  SolDerivationScheme_Phantom,
+  SolDerivationScheme_Ledger,
+  SolDerivationScheme_Bip44Root,
+} SolDerivationScheme;
+
+typedef enum SolSiwsStatus {
+  SolSiwsStatus_Ok,
+  SolSiwsStatus_InvalidMessage,
+  SolSiwsStatus_InvalidSignature,
+  SolSiwsStatus_DomainMismatch,
+  SolSiwsStatus_NonceMismatch,
+  SolSiwsStatus_Expired,
+  SolSiwsStatus_NotYetValid,
+  SolSiwsStatus_InvalidArgument,
+} SolSiwsStatus;
+
+typedef enum SolDiscriminatorKind {
+  SolDiscriminatorKind_AnchorGlobal,
+  SolDiscriminatorKind_Custom,
+  SolDiscriminatorKind_EnumTag,
+  SolDiscriminatorKind_None,
+} SolDiscriminatorKind;
+
 typedef struct SolClient SolClient;
 
+typedef struct SolIdl SolIdl;
+
+typedef struct SolKeyring SolKeyring;
+
+typedef struct SolSigner SolSigner;
+
+typedef struct SolTransaction SolTransaction;
+
+typedef struct SolTransactionBuilder SolTransactionBuilder;
+
 typedef struct SolPublicKey {
   uint8_t data[32];
 } SolPublicKey;
 
+typedef struct SolSignature {
+  uint8_t data[64];
+} SolSignature;
+
+typedef struct SolAccountMeta {
+  struct SolPublicKey pubkey;
+  bool is_signer;
+  bool is_writable;
+} SolAccountMeta;
+
+typedef struct SolDecodedInstruction {
+  struct SolPublicKey program_id;
+  struct SolAccountMeta *accounts;
+  uintptr_t account_count;
+  uint8_t *data;
+  uintptr_t data_len;
+  char *program_name;
+  char *instruction_name;
+  char *description;
+} SolDecodedInstruction;
+
+typedef struct SolDecodedTransaction {
+  bool is_versioned;
+  struct SolPublicKey fee_payer;
+  char *recent_blockhash;
+  struct SolSignature *signatures;
+  uintptr_t signature_count;
+  struct SolDecodedInstruction *instructions;
+  uintptr_t instruction_count;
+  bool lookup_tables_resolved;
+} SolDecodedTransaction;
+
 typedef struct SolKeyPair {
   uint8_t bytes[64];
   struct SolPublicKey pubkey;
 } SolKeyPair;
 
+typedef struct SolTransactionResult {
+  bool success;
+  char *signature;
+  char *error;
+  char *logs;
+  uint64_t units_consumed;
+  bool has_program_error;
+  uint8_t instruction_index;
+  struct SolPublicKey program_id;
+  uint32_t error_code;
+  char *error_name;
+  char *error_message;
+} SolTransactionResult;
+
+typedef struct SolKeyringEntry {
+  const char *label;
+  struct SolPublicKey pubkey;
+  int64_t created_at;
+  const char *derivation_path;
+  bool is_default;
+  bool encrypted;
+} SolKeyringEntry;
+
+typedef struct SolKeyringList {
+  struct SolKeyringEntry *data;
+  uintptr_t len;
+} SolKeyringList;
+
+typedef struct SolLookupTable {
+  struct SolPublicKey address;
+  struct SolPublicKey authority;
+  bool is_frozen;
+  uint64_t deactivation_slot;
+  uint64_t last_extended_slot;
+  struct SolPublicKey *addresses;
+  uintptr_t address_count;
+} SolLookupTable;
+
+typedef struct SolNonceAccount {
+  struct SolPublicKey address;
+  struct SolPublicKey authority;
+  char *nonce;
+  uint64_t lamports_per_signature;
+  uint64_t lamports;
+} SolNonceAccount;
+
+typedef struct SolPublicKeyList {
+  struct SolPublicKey *data;
+  uintptr_t len;
+} SolPublicKeyList;
+
+typedef struct SolRelayerPolicy {
+  const struct SolPublicKey *allowed_programs;
+  uintptr_t allowed_program_count;
+  uint64_t max_fee;
+  uint64_t max_payer_outflow;
+} SolRelayerPolicy;
+
+typedef bool (*SolSignCallback)(void *user_data,
+                                const uint8_t *message,
+                                uintptr_t message_len,
+                                struct SolSignature *signature_out);
+
+typedef struct SolSecretKey {
+  uint8_t data[64];
+} SolSecretKey;
+
+typedef struct SolSiwsMessage {
+  char *domain;
+  struct SolPublicKey address;
+  char *statement;
+  char *uri;
+  char *version;
+  char *chain_id;
+  char *nonce;
+  int64_t issued_at;
+  int64_t expiration_time;
+  int64_t not_before;
+  char *request_id;
+  char **resources;
+  uintptr_t resource_count;
+} SolSiwsMessage;
+
 typedef struct TokenInfo {
   const char *mint;
   const char *balance;
@@ -41,16 +208,438 @@ typedef struct SolMint {
   struct SolPublicKey *freeze_authority;
 } SolMint;
 
-typedef struct SolSecretKey {
-  uint8_t data[64];
-} SolSecretKey;
+typedef bool (*SolGrindProgressCallback)(void *user_data, uint64_t attempts);
 
 struct SolClient *new_sol_client(const char *url);
 
+struct SolClient *new_sol_client_recording(const char *url, const char *record_path);
+
+struct SolClient *new_sol_client_replay(const char *replay_path);
+
 uint64_t get_balance(struct SolClient *client, struct SolPublicKey *pubkey);
 
 bool request_airdrop(struct SolClient *client, struct SolPublicKey *pubkey, uint64_t lamports);
 
+struct SolDecodedTransaction *decode_transaction_c(struct SolClient *client,
+                                                   const uint8_t *data,
+                                                   uintptr_t data_len);
+
+struct SolDecodedTransaction *decode_sol_transaction(struct SolClient *client,
+                                                     const struct SolTransaction *transaction);
+
+struct SolDecodedTransaction *decode_transaction_with_idl(struct SolClient *client,
+                                                          const struct SolIdl *idl,
+                                                          const uint8_t *data,
+                                                          uintptr_t data_len);
+
+void free_decoded_transaction(struct SolDecodedTransaction *transaction);
+
+char *parse_events_from_logs(const struct SolIdl *idl,
+                             const char *program_id,
+                             const char *const *logs,
+                             uintptr_t log_count);
+
+char *get_transaction_events(struct SolClient *client,
+                             const struct SolIdl *idl,
+                             const char *program_id,
+                             const char *signature);
+
+struct SolIdl *load_idl_from_file(const char *file_path);
+
+struct SolIdl *load_idl_from_json(const char *json);
+
+char *idl_get_name(const struct SolIdl *idl);
+
+char *idl_get_version(const struct SolIdl *idl);
+
+bool idl_get_program_id(const struct SolIdl *idl, struct SolPublicKey *program_id_out);
+
+char *idl_to_json(const struct SolIdl *idl);
+
+uintptr_t idl_get_instruction_count(const struct SolIdl *idl);
+
+char *idl_get_instruction_name(const struct SolIdl *idl, uintptr_t index);
+
+uintptr_t idl_get_instruction_discriminator(const struct SolIdl *idl,
+                                            const char *instruction_name,
+                                            uint8_t *discriminator_out,
+                                            uintptr_t discriminator_out_len);
+
+bool sol_client_register_idl(struct SolClient *client,
+                             const struct SolIdl *idl,
+                             const char *program_id);
+
+void free_idl(struct SolIdl *idl);
+
+uintptr_t idl_resolve_accounts(struct SolClient *client,
+                               const struct SolIdl *idl,
+                               const char *program_id,
+                               const char *instruction_name,
+                               const char *args_json,
+                               const char *accounts_json,
+                               struct SolAccountMeta *accounts_out,
+                               uintptr_t accounts_out_len);
+
+char *send_idl_instruction_resolved(struct SolClient *client,
+                                    const struct SolIdl *idl,
+                                    const char *program_id,
+                                    const char *instruction_name,
+                                    const char *args_json,
+                                    const char *accounts_json,
+                                    struct SolKeyPair *const *signers,
+                                    uintptr_t signer_count);
+
+struct SolTransactionResult *send_idl_instruction_resolved_with_result(struct SolClient *client,
+                                                                       const struct SolIdl *idl,
+                                                                       const char *program_id,
+                                                                       const char *instruction_name,
+                                                                       const char *args_json,
+                                                                       const char *accounts_json,
+                                                                       struct SolKeyPair *const *signers,
+                                                                       uintptr_t signer_count);
+
+uintptr_t idl_encode_instruction_data(const struct SolIdl *idl,
+                                      const char *instruction_name,
+                                      const char *args_json,
+                                      uint8_t *data_out,
+                                      uintptr_t data_out_len);
+
+char *send_idl_instruction(struct SolClient *client,
+                           const struct SolIdl *idl,
+                           const char *program_id,
+                           const char *instruction_name,
+                           const char *args_json,
+                           const struct SolAccountMeta *accounts,
+                           uintptr_t account_count,
+                           struct SolKeyPair *const *signers,
+                           uintptr_t signer_count);
+
+struct SolTransactionResult *send_idl_instruction_with_result(struct SolClient *client,
+                                                              const struct SolIdl *idl,
+                                                              const char *program_id,
+                                                              const char *instruction_name,
+                                                              const char *args_json,
+                                                              const struct SolAccountMeta *accounts,
+                                                              uintptr_t account_count,
+                                                              struct SolKeyPair *const *signers,
+                                                              uintptr_t signer_count);
+
+char *idl_decode_account_data(const struct SolIdl *idl,
+                              const char *account_type,
+                              const uint8_t *data,
+                              uintptr_t data_len);
+
+char *fetch_account_decoded(struct SolClient *client,
+                            const struct SolIdl *idl,
+                            const char *account_type,
+                            const struct SolPublicKey *pubkey);
+
+struct SolKeyPair *wallet_from_base58(const char *secret_key);
+
+struct SolKeyPair *wallet_from_bytes(const uint8_t *bytes, uintptr_t bytes_len);
+
+struct SolKeyPair *wallet_from_hex(const char *secret_key);
+
+struct SolKeyPair *wallet_from_json(const char *json);
+
+char *export_wallet_base58(struct SolKeyPair *wallet);
+
+char *export_wallet_hex(struct SolKeyPair *wallet);
+
+char *export_wallet_json(struct SolKeyPair *wallet);
+
+bool export_wallet_seed(struct SolKeyPair *wallet, uint8_t *seed_out);
+
+bool export_wallet_to_file(struct SolKeyPair *wallet, const char *file_path);
+
+void free_secret_string(char *value);
+
+struct SolKeyring *keyring_open(const char *dir, const char *password);
+
+bool keyring_add_wallet(struct SolKeyring *keyring,
+                        const char *label,
+                        struct SolKeyPair *wallet,
+                        const char *derivation_path);
+
+struct SolKeyPair *keyring_create_wallet(struct SolKeyring *keyring, const char *label);
+
+bool keyring_import_file(struct SolKeyring *keyring, const char *label, const char *file_path);
+
+struct SolKeyPair *keyring_get_wallet(const struct SolKeyring *keyring, const char *label);
+
+struct SolKeyPair *keyring_get_default_wallet(const struct SolKeyring *keyring);
+
+bool keyring_set_default(struct SolKeyring *keyring, const char *label);
+
+bool keyring_rename(struct SolKeyring *keyring, const char *label, const char *new_label);
+
+bool keyring_remove(struct SolKeyring *keyring, const char *label);
+
+struct SolKeyringList *keyring_list(const struct SolKeyring *keyring);
+
+struct SolKeyringEntry *keyring_list_get_data(const struct SolKeyringList *list);
+
+uintptr_t keyring_list_get_len(const struct SolKeyringList *list);
+
+void free_keyring_list(struct SolKeyringList *list);
+
+void free_keyring(struct SolKeyring *keyring);
+
+struct SolKeyPair *create_and_save_encrypted_wallet(const char *file_path, const char *password);
+
+enum SolKeystoreStatus save_wallet_encrypted(struct SolKeyPair *wallet,
+                                             const char *file_path,
+                                             const char *password);
+
+struct SolKeyPair *load_encrypted_wallet(const char *file_path,
+                                         const char *password,
+                                         enum SolKeystoreStatus *status_out);
+
+struct SolKeyPair *load_wallet_any_format(const char *file_path,
+                                          const char *password,
+                                          enum SolKeystoreStatus *status_out);
+
+enum SolKeystoreStatus change_keystore_password(const char *file_path,
+                                                const char *old_password,
+                                                const char *new_password);
+
+struct SolTransactionResult *create_lookup_table(struct SolClient *client,
+                                                 const struct SolPublicKey *authority,
+                                                 struct SolKeyPair *payer,
+                                                 struct SolPublicKey *lookup_table_out);
+
+struct SolTransactionResult *extend_lookup_table(struct SolClient *client,
+                                                 const struct SolPublicKey *lookup_table,
+                                                 struct SolKeyPair *authority,
+                                                 struct SolKeyPair *payer,
+                                                 const struct SolPublicKey *addresses,
+                                                 uintptr_t address_count);
+
+struct SolTransactionResult *deactivate_lookup_table(struct SolClient *client,
+                                                     const struct SolPublicKey *lookup_table,
+                                                     struct SolKeyPair *authority,
+                                                     struct SolKeyPair *fee_payer);
+
+struct SolTransactionResult *close_lookup_table(struct SolClient *client,
+                                                const struct SolPublicKey *lookup_table,
+                                                struct SolKeyPair *authority,
+                                                const struct SolPublicKey *recipient,
+                                                struct SolKeyPair *fee_payer);
+
+struct SolLookupTable *get_lookup_table(struct SolClient *client,
+                                        const struct SolPublicKey *lookup_table);
+
+void free_lookup_table(struct SolLookupTable *lookup_table);
+
+bool sign_message(struct SolKeyPair *wallet,
+                  const uint8_t *message,
+                  uintptr_t message_len,
+                  struct SolSignature *signature_out);
+
+bool sign_message_with_signer(struct SolSigner *signer,
+                              const uint8_t *message,
+                              uintptr_t message_len,
+                              struct SolSignature *signature_out);
+
+bool verify_signature(const struct SolPublicKey *pubkey,
+                      const uint8_t *message,
+                      uintptr_t message_len,
+                      const struct SolSignature *signature);
+
+bool sign_offchain_message(struct SolKeyPair *wallet,
+                           uint8_t version,
+                           const uint8_t *message,
+                           uintptr_t message_len,
+                           struct SolSignature *signature_out);
+
+bool sign_offchain_message_with_signer(struct SolSigner *signer,
+                                       uint8_t version,
+                                       const uint8_t *message,
+                                       uintptr_t message_len,
+                                       struct SolSignature *signature_out);
+
+bool verify_offchain_message(const struct SolPublicKey *pubkey,
+                             uint8_t version,
+                             const uint8_t *message,
+                             uintptr_t message_len,
+                             const struct SolSignature *signature);
+
+uintptr_t serialize_offchain_message(uint8_t version,
+                                     const uint8_t *message,
+                                     uintptr_t message_len,
+                                     uint8_t *buffer_out,
+                                     uintptr_t buffer_out_len);
+
+char *get_signature_string(const struct SolSignature *signature);
+
+bool get_signature_from_string(const char *signature, struct SolSignature *signature_out);
+
+bool generate_mnemonic(uint32_t word_count, char *phrase_out, uintptr_t phrase_out_len);
+
+enum SolMnemonicStatus validate_mnemonic(const char *phrase);
+
+struct SolKeyPair *create_wallet_with_mnemonic(uint32_t word_count,
+                                               const char *passphrase,
+                                               char *phrase_out,
+                                               uintptr_t phrase_out_len);
+
+struct SolKeyPair *wallet_from_mnemonic(const char *phrase,
+                                        const char *passphrase,
+                                        uint32_t account_index,
+                                        uint32_t change);
+
+struct SolKeyPair *wallet_from_mnemonic_with_scheme(const char *phrase,
+                                                    const char *passphrase,
+                                                    enum SolDerivationScheme scheme,
+                                                    uint32_t account_index);
+
+struct SolKeyPair *wallet_from_mnemonic_with_path(const char *phrase,
+                                                  const char *passphrase,
+                                                  const char *derivation_path);
+
+void wipe_mnemonic_buffer(char *phrase, uintptr_t phrase_len);
+
+struct SolTransactionResult *create_nonce_account(struct SolClient *client,
+                                                  struct SolKeyPair *payer,
+                                                  struct SolKeyPair *nonce_account,
+                                                  const struct SolPublicKey *authority,
+                                                  uint64_t lamports);
+
+struct SolNonceAccount *get_nonce_account(struct SolClient *client,
+                                          const struct SolPublicKey *nonce_account);
+
+struct SolTransactionResult *advance_nonce_account(struct SolClient *client,
+                                                   const struct SolPublicKey *nonce_account,
+                                                   struct SolKeyPair *authority,
+                                                   struct SolKeyPair *fee_payer);
+
+struct SolTransactionResult *withdraw_nonce_account(struct SolClient *client,
+                                                    const struct SolPublicKey *nonce_account,
+                                                    struct SolKeyPair *authority,
+                                                    const struct SolPublicKey *recipient,
+                                                    uint64_t lamports,
+                                                    struct SolKeyPair *fee_payer);
+
+struct SolTransactionResult *authorize_nonce_account(struct SolClient *client,
+                                                     const struct SolPublicKey *nonce_account,
+                                                     struct SolKeyPair *authority,
+                                                     const struct SolPublicKey *new_authority,
+                                                     struct SolKeyPair *fee_payer);
+
+void free_nonce_account(struct SolNonceAccount *nonce_account);
+
+struct SolTransaction *sol_transaction_builder_build_unsigned(const struct SolTransactionBuilder *builder,
+                                                              struct SolClient *client);
+
+struct SolTransaction *sol_transaction_builder_build_unsigned_with_blockhash(const struct SolTransactionBuilder *builder,
+                                                                             const char *blockhash);
+
+uintptr_t sol_transaction_serialize(const struct SolTransaction *transaction,
+                                    uint8_t *buffer_out,
+                                    uintptr_t buffer_out_len);
+
+char *sol_transaction_to_base64(const struct SolTransaction *transaction);
+
+char *sol_transaction_to_base58(const struct SolTransaction *transaction);
+
+struct SolTransaction *sol_transaction_from_bytes(const uint8_t *data, uintptr_t data_len);
+
+struct SolTransaction *sol_transaction_from_base64(const char *encoded);
+
+struct SolTransaction *sol_transaction_from_base58(const char *encoded);
+
+bool sol_transaction_partial_sign(struct SolTransaction *transaction, struct SolKeyPair *wallet);
+
+bool sol_transaction_partial_sign_with_signer(struct SolTransaction *transaction,
+                                              const struct SolSigner *signer);
+
+struct SolPublicKeyList *sol_transaction_missing_signers(const struct SolTransaction *transaction);
+
+bool sol_transaction_verify_signatures(const struct SolTransaction *transaction);
+
+struct SolTransactionResult *sol_transaction_send(const struct SolTransaction *transaction,
+                                                  struct SolClient *client);
+
+struct SolPublicKey *public_key_list_get_data(const struct SolPublicKeyList *list);
+
+uintptr_t public_key_list_get_len(const struct SolPublicKeyList *list);
+
+void free_public_key_list(struct SolPublicKeyList *list);
+
+void free_sol_transaction(struct SolTransaction *transaction);
+
+bool find_program_address_c(const uint8_t *const *seeds,
+                            const uintptr_t *seed_lens,
+                            uintptr_t seed_count,
+                            const struct SolPublicKey *program_id,
+                            struct SolPublicKey *pubkey_out,
+                            uint8_t *bump_out);
+
+bool create_program_address_c(const uint8_t *const *seeds,
+                              const uintptr_t *seed_lens,
+                              uintptr_t seed_count,
+                              const struct SolPublicKey *program_id,
+                              struct SolPublicKey *pubkey_out);
+
+bool create_with_seed_c(const struct SolPublicKey *base,
+                        const char *seed,
+                        const struct SolPublicKey *owner,
+                        struct SolPublicKey *pubkey_out);
+
+bool is_on_curve(const struct SolPublicKey *pubkey);
+
+struct SolTransactionResult *relayer_sign_and_send(struct SolClient *client,
+                                                   struct SolKeyPair *fee_payer,
+                                                   const char *transaction,
+                                                   const struct SolRelayerPolicy *policy);
+
+struct SolSigner *sol_signer_new(void);
+
+struct SolSigner *sol_signer_from_keypair(struct SolKeyPair *wallet);
+
+struct SolSigner *sol_signer_from_file(const char *file_path);
+
+struct SolSigner *sol_signer_from_encrypted_file(const char *file_path,
+                                                 const char *password,
+                                                 enum SolKeystoreStatus *status_out);
+
+struct SolSigner *sol_signer_from_callback(const struct SolPublicKey *pubkey,
+                                           SolSignCallback callback,
+                                           void *user_data);
+
+struct SolPublicKey sol_signer_pubkey(const struct SolSigner *signer);
+
+bool sol_signer_is_memory_locked(const struct SolSigner *signer);
+
+bool sol_signer_export_secret_key(const struct SolSigner *signer, struct SolSecretKey *secret_out);
+
+struct SolKeyPair *sol_signer_export_keypair(const struct SolSigner *signer);
+
+void free_sol_signer(struct SolSigner *signer);
+
+char *siws_create_message(const char *domain,
+                          const struct SolPublicKey *address,
+                          const char *statement,
+                          const char *uri,
+                          const char *chain_id,
+                          const char *nonce,
+                          int64_t issued_at,
+                          int64_t expiration_time);
+
+bool siws_sign_message(struct SolKeyPair *wallet,
+                       const char *message,
+                       struct SolSignature *signature_out);
+
+struct SolSiwsMessage *siws_parse_message(const char *message);
+
+enum SolSiwsStatus siws_verify(const char *message,
+                               const struct SolSignature *signature,
+                               const char *expected_domain,
+                               const char *expected_nonce,
+                               int64_t now);
+
+void free_siws_message(struct SolSiwsMessage *message);
+
 struct SolPublicKey get_system_program_id(void);
 
 uintptr_t get_account_data_c(struct SolClient *client,
@@ -69,11 +658,88 @@ char *send_generic_transaction_c(struct SolClient *client,
                                  const uint8_t *data_ptr,
                                  uintptr_t data_len);
 
+char *send_generic_transaction_with_signers(struct SolClient *client,
+                                            const char *program_id,
+                                            const char *method_name,
+                                            const struct SolPublicKey *account_pubkeys,
+                                            uintptr_t account_count,
+                                            struct SolSigner *const *signers,
+                                            uintptr_t signer_count,
+                                            const uint8_t *data_ptr,
+                                            uintptr_t data_len,
+                                            const struct SolSigner *fee_payer);
+
+char *send_generic_transaction_with_metas(struct SolClient *client,
+                                          const char *program_id,
+                                          const char *method_name,
+                                          const struct SolAccountMeta *accounts,
+                                          uintptr_t account_count,
+                                          struct SolKeyPair *const *signers,
+                                          uintptr_t signer_count,
+                                          const uint8_t *data_ptr,
+                                          uintptr_t data_len);
+
+char *send_generic_transaction_with_fee_payer(struct SolClient *client,
+                                              const char *program_id,
+                                              const char *method_name,
+                                              const struct SolAccountMeta *accounts,
+                                              uintptr_t account_count,
+                                              struct SolKeyPair *const *signers,
+                                              uintptr_t signer_count,
+                                              const uint8_t *data_ptr,
+                                              uintptr_t data_len,
+                                              struct SolKeyPair *fee_payer);
+
+char *send_raw_instruction_c(struct SolClient *client,
+                             const char *program_id,
+                             const struct SolAccountMeta *accounts,
+                             uintptr_t account_count,
+                             struct SolKeyPair *const *signers,
+                             uintptr_t signer_count,
+                             const uint8_t *data_ptr,
+                             uintptr_t data_len,
+                             struct SolKeyPair *fee_payer);
+
+char *send_generic_transaction_with_discriminator(struct SolClient *client,
+                                                  const char *program_id,
+                                                  const char *method_name,
+                                                  enum SolDiscriminatorKind discriminator_kind,
+                                                  const uint8_t *discriminator,
+                                                  uintptr_t discriminator_len,
+                                                  const struct SolAccountMeta *accounts,
+                                                  uintptr_t account_count,
+                                                  struct SolKeyPair *const *signers,
+                                                  uintptr_t signer_count,
+                                                  const uint8_t *data_ptr,
+                                                  uintptr_t data_len,
+                                                  struct SolKeyPair *fee_payer);
+
+struct SolTransactionResult *send_generic_transaction_with_result(struct SolClient *client,
+                                                                  const char *program_id,
+                                                                  const char *method_name,
+                                                                  enum SolDiscriminatorKind discriminator_kind,
+                                                                  const uint8_t *discriminator,
+                                                                  uintptr_t discriminator_len,
+                                                                  const struct SolAccountMeta *accounts,
+                                                                  uintptr_t account_count,
+                                                                  struct SolKeyPair *const *signers,
+                                                                  uintptr_t signer_count,
+                                                                  const uint8_t *data_ptr,
+                                                                  uintptr_t data_len,
+                                                                  struct SolKeyPair *fee_payer);
+
+bool get_anchor_discriminator(const char *namespace_, const char *name, uint8_t *discriminator_out);
+
 void initialize_account_c(struct SolClient *client,
                           struct SolKeyPair *payer,
                           struct SolKeyPair *account,
                           const char *program_id);
 
+void initialize_account_with_signer(struct SolClient *client,
+                                    struct SolSigner *payer,
+                                    struct SolSigner *account,
+                                    const char *program_id);
+
 void free_client(struct SolClient *client);
 
 void free_payer(struct SolKeyPair *payer);
@@ -91,14 +757,50 @@ bool transfer_sol(struct SolClient *client,
                   struct SolPublicKey *recipient,
                   uint64_t lamports);
 
+bool transfer_sol_with_signer(struct SolClient *client,
+                              struct SolSigner *sender,
+                              struct SolPublicKey *recipient,
+                              uint64_t lamports,
+                              const struct SolSigner *fee_payer);
+
+bool transfer_sol_with_fee_payer(struct SolClient *client,
+                                 struct SolKeyPair *sender,
+                                 struct SolPublicKey *recipient,
+                                 uint64_t lamports,
+                                 struct SolKeyPair *fee_payer);
+
 bool transfer_spl(struct SolClient *client,
                   struct SolKeyPair *sender,
                   struct SolPublicKey *recipient,
                   struct SolPublicKey *mint,
                   uint64_t amount);
 
+bool transfer_spl_with_signer(struct SolClient *client,
+                              struct SolSigner *sender,
+                              struct SolPublicKey *recipient,
+                              struct SolPublicKey *mint,
+                              uint64_t amount,
+                              const struct SolSigner *fee_payer);
+
+bool transfer_spl_with_fee_payer(struct SolClient *client,
+                                 struct SolKeyPair *sender,
+                                 struct SolPublicKey *recipient,
+                                 struct SolPublicKey *mint,
+                                 uint64_t amount,
+                                 struct SolKeyPair *fee_payer);
+
 bool create_spl_token(struct SolClient *client, struct SolKeyPair *payer, struct SolKeyPair *mint);
 
+bool create_spl_token_with_fee_payer(struct SolClient *client,
+                                     struct SolKeyPair *payer,
+                                     struct SolKeyPair *mint,
+                                     struct SolKeyPair *fee_payer);
+
+bool create_spl_token_with_signer(struct SolClient *client,
+                                  struct SolSigner *payer,
+                                  struct SolSigner *mint,
+                                  const struct SolSigner *fee_payer);
+
 struct SolMint *get_mint_info(struct SolClient *client, struct SolPublicKey *mint_pubkey);
 
 struct SolPublicKey *get_or_create_associated_token_account(struct SolClient *client,
@@ -106,16 +808,80 @@ struct SolPublicKey *get_or_create_associated_token_account(struct SolClient *cl
                                                             struct SolPublicKey *owner,
                                                             struct SolKeyPair *mint);
 
+struct SolPublicKey *get_or_create_associated_token_account_with_fee_payer(struct SolClient *client,
+                                                                           struct SolKeyPair *payer,
+                                                                           struct SolPublicKey *owner,
+                                                                           struct SolKeyPair *mint,
+                                                                           struct SolKeyPair *fee_payer);
+
+struct SolPublicKey *get_or_create_associated_token_account_with_signer(struct SolClient *client,
+                                                                        struct SolSigner *payer,
+                                                                        struct SolPublicKey *owner,
+                                                                        struct SolPublicKey *mint,
+                                                                        const struct SolSigner *fee_payer);
+
 bool mint_spl(struct SolClient *client,
               struct SolKeyPair *payer,
               struct SolKeyPair *mint_authority,
               struct SolPublicKey *recipient,
               uint64_t amount);
 
+bool mint_spl_with_fee_payer(struct SolClient *client,
+                             struct SolKeyPair *payer,
+                             struct SolKeyPair *mint_authority,
+                             struct SolPublicKey *recipient,
+                             uint64_t amount,
+                             struct SolKeyPair *fee_payer);
+
+bool mint_spl_with_signer(struct SolClient *client,
+                          struct SolSigner *payer,
+                          struct SolSigner *mint_authority,
+                          struct SolPublicKey *recipient,
+                          uint64_t amount,
+                          const struct SolSigner *fee_payer);
+
 uint64_t get_associated_token_balance(struct SolClient *client,
                                       struct SolPublicKey *owner,
                                       struct SolPublicKey *mint);
 
+struct SolTransactionBuilder *sol_transaction_builder_new(void);
+
+bool sol_transaction_builder_add_instruction(struct SolTransactionBuilder *builder,
+                                             const struct SolPublicKey *program_id,
+                                             const struct SolAccountMeta *accounts,
+                                             uintptr_t account_count,
+                                             const uint8_t *data,
+                                             uintptr_t data_len);
+
+void sol_transaction_builder_set_fee_payer(struct SolTransactionBuilder *builder,
+                                           const struct SolPublicKey *fee_payer);
+
+void sol_transaction_builder_add_signer(struct SolTransactionBuilder *builder,
+                                        struct SolKeyPair *signer);
+
+void sol_transaction_builder_add_sol_signer(struct SolTransactionBuilder *builder,
+                                            const struct SolSigner *signer);
+
+void sol_transaction_builder_add_lookup_table(struct SolTransactionBuilder *builder,
+                                              const struct SolPublicKey *lookup_table);
+
+void sol_transaction_builder_set_nonce(struct SolTransactionBuilder *builder,
+                                       const struct SolPublicKey *nonce_account,
+                                       const struct SolPublicKey *nonce_authority);
+
+struct SolTransactionResult *sol_transaction_builder_send(const struct SolTransactionBuilder *builder,
+                                                          struct SolClient *client);
+
+struct SolTransactionResult *sol_transaction_builder_simulate(const struct SolTransactionBuilder *builder,
+                                                              struct SolClient *client);
+
+char *sol_transaction_builder_serialize(const struct SolTransactionBuilder *builder,
+                                        struct SolClient *client);
+
+void free_sol_transaction_builder(struct SolTransactionBuilder *builder);
+
+void free_transaction_result(struct SolTransactionResult *result);
+
 struct SolPublicKey *get_public_key(struct SolKeyPair *wallet);
 
 struct SolSecretKey *get_secret_key(struct SolKeyPair *wallet);
@@ -134,6 +900,19 @@ struct SolPublicKey *get_pubkey_from_address(const char *address);
 
 char *get_address_from_pubkey(const struct SolPublicKey *pubkey);
 
+struct SolKeyPair *grind_keypair(const char *prefix,
+                                 const char *suffix,
+                                 bool case_insensitive,
+                                 uint32_t threads,
+                                 uint64_t max_attempts,
+                                 SolGrindProgressCallback progress_callback,
+                                 void *user_data,
+                                 const char *file_path);
+
+void free_keypair(struct SolKeyPair *wallet);
+
+void free_secret_key(struct SolSecretKey *secret_key);
+
 #endif  /* SOLANA_SDK_H */
 
 #ifdef __cplusplus
@@ -1,11 +1,31 @@
 use std::ffi::{c_char, CStr};
-use solana_client::rpc_client::RpcClient;
-use solana_sdk::pubkey::Pubkey;
+use solana_client::rpc_client::{RpcClient, RpcClientConfig};
+use solana_sdk::{commitment_config::CommitmentConfig, pubkey::Pubkey};
 
+use crate::idl::Idl;
+use crate::rpc_recorder::{RecordingSender, ReplaySender};
 use crate::wallet::SolPublicKey;
 
 pub struct SolClient {
     pub rpc_client: RpcClient,
+    pub idls: Vec<(Pubkey, Idl)>, // Registered with `sol_client_register_idl`
+}
+
+impl SolClient {
+    pub fn new(rpc_client: RpcClient) -> Self {
+        SolClient {
+            rpc_client,
+            idls: Vec::new(),
+        }
+    }
+
+    // The IDL registered for a program, if any
+    pub fn idl(&self, program_id: &Pubkey) -> Option<&Idl> {
+        self.idls
+            .iter()
+            .find(|(program, _)| program == program_id)
+            .map(|(_, idl)| idl)
+    }
 }
 
 #[no_mangle]
@@ -19,10 +39,69 @@ pub extern "C" fn new_sol_client(url: *const c_char) -> *mut SolClient {
 
     // Create a new Solana client
     let rpc_client = RpcClient::new(url_str.to_string());
-    let client = SolClient { rpc_client };
+    let client = SolClient::new(rpc_client);
     Box::into_raw(Box::new(client))
 }
 
+// Create a client that talks to the node and appends every RPC exchange to a JSONL file
+#[no_mangle]
+pub extern "C" fn new_sol_client_recording(
+    url: *const c_char,
+    record_path: *const c_char,
+) -> *mut SolClient {
+    let url_str = match unsafe { CStr::from_ptr(url) }.to_str() {
+        Ok(str) => str,
+        Err(_) => return std::ptr::null_mut(),
+    };
+    let record_path_str = match unsafe { CStr::from_ptr(record_path) }.to_str() {
+        Ok(str) => str,
+        Err(_) => return std::ptr::null_mut(),
+    };
+
+    let sender = match RecordingSender::new(url_str, record_path_str) {
+        Ok(sender) => sender,
+        Err(err) => {
+            eprintln!(
+                "Failed to open RPC recording {}: {:?}",
+                record_path_str, err
+            );
+            return std::ptr::null_mut();
+        }
+    };
+
+    let rpc_client = RpcClient::new_sender(
+        sender,
+        RpcClientConfig::with_commitment(CommitmentConfig::default()),
+    );
+    Box::into_raw(Box::new(SolClient::new(rpc_client)))
+}
+
+// Create a client that answers every RPC call from a recording made by `new_sol_client_recording`
+#[no_mangle]
+pub extern "C" fn new_sol_client_replay(replay_path: *const c_char) -> *mut SolClient {
+    let replay_path_str = match unsafe { CStr::from_ptr(replay_path) }.to_str() {
+        Ok(str) => str,
+        Err(_) => return std::ptr::null_mut(),
+    };
+
+    let sender = match ReplaySender::new(replay_path_str) {
+        Ok(sender) => sender,
+        Err(err) => {
+            eprintln!(
+                "Failed to load RPC recording {}: {:?}",
+                replay_path_str, err
+            );
+            return std::ptr::null_mut();
+        }
+    };
+
+    let rpc_client = RpcClient::new_sender(
+        sender,
+        RpcClientConfig::with_commitment(CommitmentConfig::default()),
+    );
+    Box::into_raw(Box::new(SolClient::new(rpc_client)))
+}
+
 #[no_mangle]
 pub extern "C" fn get_balance(client: *mut SolClient, pubkey: *mut SolPublicKey) -> u64 {
     let client = unsafe {
@@ -0,0 +1,792 @@
+use solana_sdk::address_lookup_table::AddressLookupTableAccount;
+use solana_sdk::compute_budget;
+use solana_sdk::instruction::{AccountMeta, Instruction};
+use solana_sdk::message::VersionedMessage;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::system_instruction::SystemInstruction;
+use solana_sdk::system_program;
+use solana_sdk::transaction::VersionedTransaction;
+use spl_token::instruction::TokenInstruction;
+use std::ffi::CString;
+use std::os::raw::c_char;
+
+use crate::client::SolClient;
+use crate::idl::{Idl, SolIdl};
+use crate::idl_codec::decode_instruction_data;
+use crate::lookup_table::fetch_lookup_table;
+use crate::offline::{deserialize_transaction, SolTransaction};
+use crate::transaction::SolAccountMeta;
+use crate::wallet::{SolPublicKey, SolSignature};
+
+const MEMO_PROGRAM_ID: Pubkey = solana_sdk::pubkey!("MemoSq4gqABAXKb96qnH1TysNcWxMyWCqXgDLGmfcHr");
+const MEMO_V1_PROGRAM_ID: Pubkey =
+    solana_sdk::pubkey!("Memo1UhkJRfHyvLMcVucJwxXeuD728EqVDDwQDxFMNo");
+const TOKEN_2022_PROGRAM_ID: Pubkey =
+    solana_sdk::pubkey!("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb");
+
+// One instruction of a decoded transaction. Text fields are NULL when the
+// program or instruction is not recognized.
+#[repr(C)]
+pub struct SolDecodedInstruction {
+    pub program_id: SolPublicKey,
+    pub accounts: *mut SolAccountMeta,
+    pub account_count: usize,
+    pub data: *mut u8,
+    pub data_len: usize,
+    pub program_name: *mut c_char,
+    pub instruction_name: *mut c_char,
+    pub description: *mut c_char,
+}
+
+// Release with `free_decoded_transaction`
+#[repr(C)]
+pub struct SolDecodedTransaction {
+    pub is_versioned: bool,
+    pub fee_payer: SolPublicKey,
+    pub recent_blockhash: *mut c_char,
+    pub signatures: *mut SolSignature, // Unsigned slots are all zeroes
+    pub signature_count: usize,
+    pub instructions: *mut SolDecodedInstruction,
+    pub instruction_count: usize,
+    // False when lookup table accounts could not be resolved; they are left as zero keys
+    pub lookup_tables_resolved: bool,
+}
+
+// Program and instruction names plus a one-line summary
+pub struct InstructionDescription {
+    pub program_name: String,
+    pub instruction_name: Option<String>,
+    pub description: Option<String>,
+}
+
+impl InstructionDescription {
+    fn new(program_name: &str, instruction_name: &str, description: String) -> Self {
+        InstructionDescription {
+            program_name: program_name.to_string(),
+            instruction_name: Some(instruction_name.to_string()),
+            description: Some(description),
+        }
+    }
+
+    fn program_only(program_name: &str) -> Self {
+        InstructionDescription {
+            program_name: program_name.to_string(),
+            instruction_name: None,
+            description: None,
+        }
+    }
+}
+
+// ==================== Instruction Recovery ==================== //
+
+// Expand compiled instructions back to program ids and account metas. Accounts
+// from unresolved lookup tables become `Pubkey::default()`.
+pub fn decompile_instructions(
+    transaction: &VersionedTransaction,
+    lookup_tables: &[AddressLookupTableAccount],
+) -> (Vec<Instruction>, bool) {
+    let message = &transaction.message;
+    let mut keys: Vec<(Pubkey, bool)> = message
+        .static_account_keys()
+        .iter()
+        .enumerate()
+        .map(|(index, key)| (*key, message.is_maybe_writable(index, None)))
+        .collect();
+
+    // Loaded addresses follow the static keys: all writable ones, then all read-only ones
+    let mut resolved = true;
+    if let Some(lookups) = message.address_table_lookups() {
+        let mut writable = Vec::new();
+        let mut readonly = Vec::new();
+        for lookup in lookups {
+            let table = lookup_tables
+                .iter()
+                .find(|table| table.key == lookup.account_key);
+            let mut load = |indexes: &[u8], out: &mut Vec<(Pubkey, bool)>, is_writable: bool| {
+                for index in indexes {
+                    let key = table.and_then(|table| table.addresses.get(usize::from(*index)));
+                    if key.is_none() {
+                        resolved = false;
+                    }
+                    out.push((key.copied().unwrap_or_default(), is_writable));
+                }
+            };
+            load(&lookup.writable_indexes, &mut writable, true);
+            load(&lookup.readonly_indexes, &mut readonly, false);
+        }
+        keys.extend(writable);
+        keys.extend(readonly);
+    }
+
+    let instructions = message
+        .instructions()
+        .iter()
+        .map(|compiled| {
+            let accounts = compiled
+                .accounts
+                .iter()
+                .map(|index| {
+                    let index = usize::from(*index);
+                    let (pubkey, is_writable) = keys.get(index).copied().unwrap_or_default();
+                    AccountMeta {
+                        pubkey,
+                        is_signer: message.is_signer(index),
+                        is_writable,
+                    }
+                })
+                .collect();
+            Instruction {
+                program_id: keys
+                    .get(usize::from(compiled.program_id_index))
+                    .map(|(key, _)| *key)
+                    .unwrap_or_default(),
+                accounts,
+                data: compiled.data.clone(),
+            }
+        })
+        .collect();
+    (instructions, resolved)
+}
+
+// ==================== Known Programs ==================== //
+
+fn account(instruction: &Instruction, index: usize) -> String {
+    instruction
+        .accounts
+        .get(index)
+        .map_or_else(|| "?".to_string(), |meta| meta.pubkey.to_string())
+}
+
+fn describe_system(instruction: &Instruction) -> InstructionDescription {
+    const PROGRAM: &str = "System Program";
+    let decoded: SystemInstruction = match bincode::deserialize(&instruction.data) {
+        Ok(decoded) => decoded,
+        Err(_) => return InstructionDescription::program_only(PROGRAM),
+    };
+    let a = |index| account(instruction, index);
+
+    match decoded {
+        SystemInstruction::CreateAccount {
+            lamports,
+            space,
+            owner,
+        } => InstructionDescription::new(
+            PROGRAM,
+            "CreateAccount",
+            format!(
+                "Create account {} with {} lamports and {} bytes owned by {}, funded by {}",
+                a(1),
+                lamports,
+                space,
+                owner,
+                a(0)
+            ),
+        ),
+        SystemInstruction::CreateAccountWithSeed {
+            base,
+            seed,
+            lamports,
+            space,
+            owner,
+        } => InstructionDescription::new(
+            PROGRAM,
+            "CreateAccountWithSeed",
+            format!(
+                "Create account {} from base {} and seed \"{}\" with {} lamports and {} bytes owned by {}",
+                a(1),
+                base,
+                seed,
+                lamports,
+                space,
+                owner
+            ),
+        ),
+        SystemInstruction::Assign { owner } => InstructionDescription::new(
+            PROGRAM,
+            "Assign",
+            format!("Assign account {} to program {}", a(0), owner),
+        ),
+        SystemInstruction::Transfer { lamports } => InstructionDescription::new(
+            PROGRAM,
+            "Transfer",
+            format!("Transfer {} lamports from {} to {}", lamports, a(0), a(1)),
+        ),
+        SystemInstruction::TransferWithSeed { lamports, .. } => InstructionDescription::new(
+            PROGRAM,
+            "TransferWithSeed",
+            format!("Transfer {} lamports from {} to {}", lamports, a(0), a(2)),
+        ),
+        SystemInstruction::Allocate { space } => InstructionDescription::new(
+            PROGRAM,
+            "Allocate",
+            format!("Allocate {} bytes for account {}", space, a(0)),
+        ),
+        SystemInstruction::AdvanceNonceAccount => InstructionDescription::new(
+            PROGRAM,
+            "AdvanceNonceAccount",
+            format!("Advance nonce account {} (authority {})", a(0), a(2)),
+        ),
+        SystemInstruction::WithdrawNonceAccount(lamports) => InstructionDescription::new(
+            PROGRAM,
+            "WithdrawNonceAccount",
+            format!(
+                "Withdraw {} lamports from nonce account {} to {}",
+                lamports,
+                a(0),
+                a(1)
+            ),
+        ),
+        SystemInstruction::InitializeNonceAccount(authority) => InstructionDescription::new(
+            PROGRAM,
+            "InitializeNonceAccount",
+            format!(
+                "Initialize nonce account {} with authority {}",
+                a(0),
+                authority
+            ),
+        ),
+        SystemInstruction::AuthorizeNonceAccount(authority) => InstructionDescription::new(
+            PROGRAM,
+            "AuthorizeNonceAccount",
+            format!(
+                "Set the authority of nonce account {} to {}",
+                a(0),
+                authority
+            ),
+        ),
+        other => {
+            let name = format!("{:?}", other);
+            let name = name
+                .split(|c: char| !c.is_alphanumeric())
+                .next()
+                .unwrap_or_default();
+            InstructionDescription::new(PROGRAM, name, name.to_string())
+        }
+    }
+}
+
+fn describe_token(instruction: &Instruction, program_name: &str) -> InstructionDescription {
+    let decoded = match TokenInstruction::unpack(&instruction.data) {
+        Ok(decoded) => decoded,
+        Err(_) => return InstructionDescription::program_only(program_name),
+    };
+    let a = |index| account(instruction, index);
+
+    let (name, description) = match decoded {
+        TokenInstruction::InitializeMint {
+            decimals,
+            mint_authority,
+            ..
+        }
+        | TokenInstruction::InitializeMint2 {
+            decimals,
+            mint_authority,
+            ..
+        } => (
+            "InitializeMint",
+            format!(
+                "Initialize mint {} with {} decimals and mint authority {}",
+                a(0),
+                decimals,
+                mint_authority
+            ),
+        ),
+        TokenInstruction::InitializeAccount => (
+            "InitializeAccount",
+            format!(
+                "Initialize token account {} for mint {} owned by {}",
+                a(0),
+                a(1),
+                a(2)
+            ),
+        ),
+        TokenInstruction::InitializeAccount2 { owner }
+        | TokenInstruction::InitializeAccount3 { owner } => (
+            "InitializeAccount",
+            format!(
+                "Initialize token account {} for mint {} owned by {}",
+                a(0),
+                a(1),
+                owner
+            ),
+        ),
+        TokenInstruction::Transfer { amount } => (
+            "Transfer",
+            format!(
+                "Transfer {} tokens from {} to {} (authority {})",
+                amount,
+                a(0),
+                a(1),
+                a(2)
+            ),
+        ),
+        TokenInstruction::TransferChecked { amount, decimals } => (
+            "TransferChecked",
+            format!(
+                "Transfer {} tokens ({} decimals) of mint {} from {} to {} (authority {})",
+                amount,
+                decimals,
+                a(1),
+                a(0),
+                a(2),
+                a(3)
+            ),
+        ),
+        TokenInstruction::Approve { amount } => (
+            "Approve",
+            format!(
+                "Allow delegate {} to spend {} tokens from {}",
+                a(1),
+                amount,
+                a(0)
+            ),
+        ),
+        TokenInstruction::ApproveChecked { amount, decimals } => (
+            "ApproveChecked",
+            format!(
+                "Allow delegate {} to spend {} tokens ({} decimals) from {}",
+                a(2),
+                amount,
+                decimals,
+                a(0)
+            ),
+        ),
+        TokenInstruction::Revoke => ("Revoke", format!("Revoke the delegate of {}", a(0))),
+        TokenInstruction::SetAuthority {
+            authority_type,
+            new_authority,
+        } => (
+            "SetAuthority",
+            format!(
+                "Set {:?} authority of {} to {}",
+                authority_type,
+                a(0),
+                Option::<Pubkey>::from(new_authority)
+                    .map_or_else(|| "none".to_string(), |key| key.to_string())
+            ),
+        ),
+        TokenInstruction::MintTo { amount } => (
+            "MintTo",
+            format!("Mint {} tokens of mint {} to {}", amount, a(0), a(1)),
+        ),
+        TokenInstruction::MintToChecked { amount, decimals } => (
+            "MintToChecked",
+            format!(
+                "Mint {} tokens ({} decimals) of mint {} to {}",
+                amount,
+                decimals,
+                a(0),
+                a(1)
+            ),
+        ),
+        TokenInstruction::Burn { amount } => (
+            "Burn",
+            format!("Burn {} tokens of mint {} from {}", amount, a(1), a(0)),
+        ),
+        TokenInstruction::BurnChecked { amount, decimals } => (
+            "BurnChecked",
+            format!(
+                "Burn {} tokens ({} decimals) of mint {} from {}",
+                amount,
+                decimals,
+                a(1),
+                a(0)
+            ),
+        ),
+        TokenInstruction::CloseAccount => (
+            "CloseAccount",
+            format!("Close token account {} and send its rent to {}", a(0), a(1)),
+        ),
+        TokenInstruction::FreezeAccount => {
+            ("FreezeAccount", format!("Freeze token account {}", a(0)))
+        }
+        TokenInstruction::ThawAccount => ("ThawAccount", format!("Thaw token account {}", a(0))),
+        TokenInstruction::SyncNative => (
+            "SyncNative",
+            format!("Sync the wrapped SOL balance of {}", a(0)),
+        ),
+        other => {
+            let name = format!("{:?}", other);
+            let name = name
+                .split(|c: char| !c.is_alphanumeric())
+                .next()
+                .unwrap_or_default()
+                .to_string();
+            return InstructionDescription {
+                program_name: program_name.to_string(),
+                instruction_name: Some(name.clone()),
+                description: Some(name),
+            };
+        }
+    };
+    InstructionDescription::new(program_name, name, description)
+}
+
+fn describe_associated_token(instruction: &Instruction) -> InstructionDescription {
+    const PROGRAM: &str = "Associated Token Account Program";
+    let a = |index| account(instruction, index);
+
+    match instruction.data.first() {
+        None | Some(0) => InstructionDescription::new(
+            PROGRAM,
+            "Create",
+            format!(
+                "Create token account {} for owner {} and mint {}, funded by {}",
+                a(1),
+                a(2),
+                a(3),
+                a(0)
+            ),
+        ),
+        Some(1) => InstructionDescription::new(
+            PROGRAM,
+            "CreateIdempotent",
+            format!(
+                "Create token account {} for owner {} and mint {} if missing, funded by {}",
+                a(1),
+                a(2),
+                a(3),
+                a(0)
+            ),
+        ),
+        Some(2) => InstructionDescription::new(
+            PROGRAM,
+            "RecoverNested",
+            format!("Recover nested token account {} to {}", a(0), a(2)),
+        ),
+        Some(_) => InstructionDescription::program_only(PROGRAM),
+    }
+}
+
+fn describe_memo(instruction: &Instruction) -> InstructionDescription {
+    const PROGRAM: &str = "Memo Program";
+    match std::str::from_utf8(&instruction.data) {
+        Ok(memo) => InstructionDescription::new(PROGRAM, "Memo", format!("Memo: \"{}\"", memo)),
+        Err(_) => InstructionDescription::program_only(PROGRAM),
+    }
+}
+
+fn describe_compute_budget(instruction: &Instruction) -> InstructionDescription {
+    const PROGRAM: &str = "Compute Budget Program";
+    let data = &instruction.data;
+    let u32_arg = || {
+        data.get(1..5)
+            .map(|bytes| u32::from_le_bytes(bytes.try_into().unwrap()))
+    };
+    let u64_arg = || {
+        data.get(1..9)
+            .map(|bytes| u64::from_le_bytes(bytes.try_into().unwrap()))
+    };
+
+    let described = match data.first() {
+        Some(1) => u32_arg().map(|bytes| {
+            (
+                "RequestHeapFrame",
+                format!("Request a heap frame of {} bytes", bytes),
+            )
+        }),
+        Some(2) => u32_arg().map(|units| {
+            (
+                "SetComputeUnitLimit",
+                format!("Set the compute unit limit to {}", units),
+            )
+        }),
+        Some(3) => u64_arg().map(|price| {
+            (
+                "SetComputeUnitPrice",
+                format!("Set the compute unit price to {} micro-lamports", price),
+            )
+        }),
+        Some(4) => u32_arg().map(|bytes| {
+            (
+                "SetLoadedAccountsDataSizeLimit",
+                format!("Limit loaded account data to {} bytes", bytes),
+            )
+        }),
+        _ => None,
+    };
+    match described {
+        Some((name, description)) => InstructionDescription::new(PROGRAM, name, description),
+        None => InstructionDescription::program_only(PROGRAM),
+    }
+}
+
+// Describe instructions of the System, SPL Token, Token-2022, Associated Token
+// Account, Memo and Compute Budget programs
+pub fn describe_instruction(instruction: &Instruction) -> Option<InstructionDescription> {
+    let program_id = instruction.program_id;
+    if program_id == system_program::ID {
+        Some(describe_system(instruction))
+    } else if program_id == spl_token::ID {
+        Some(describe_token(instruction, "Token Program"))
+    } else if program_id == TOKEN_2022_PROGRAM_ID {
+        Some(describe_token(instruction, "Token-2022 Program"))
+    } else if program_id == spl_associated_token_account::ID {
+        Some(describe_associated_token(instruction))
+    } else if program_id == MEMO_PROGRAM_ID || program_id == MEMO_V1_PROGRAM_ID {
+        Some(describe_memo(instruction))
+    } else if program_id == compute_budget::ID {
+        Some(describe_compute_budget(instruction))
+    } else {
+        None
+    }
+}
+
+// Instructions of the IDL's program, described as the instruction name and its
+// arguments as JSON
+pub fn describe_idl_instruction(
+    idl: &Idl,
+    instruction: &Instruction,
+) -> Option<InstructionDescription> {
+    if idl
+        .address
+        .is_some_and(|address| address != instruction.program_id)
+    {
+        return None;
+    }
+    // Without a program address, other programs' instructions are unknown
+    let known = idl
+        .instructions
+        .iter()
+        .any(|idl_instruction| instruction.data.starts_with(&idl_instruction.discriminator));
+    if idl.address.is_none() && !known {
+        return None;
+    }
+    Some(describe_program_instruction(idl, instruction))
+}
+
+// Same for an IDL known to belong to the instruction's program, e.g. one
+// registered on the client for another deployment than the IDL address
+fn describe_program_instruction(idl: &Idl, instruction: &Instruction) -> InstructionDescription {
+    match decode_instruction_data(idl, &instruction.data) {
+        Some((idl_instruction, Ok(args))) => InstructionDescription::new(
+            &idl.name,
+            &idl_instruction.name,
+            format!("{} {}", idl_instruction.name, args),
+        ),
+        Some((idl_instruction, Err(err))) => InstructionDescription::new(
+            &idl.name,
+            &idl_instruction.name,
+            format!("{} (invalid arguments: {})", idl_instruction.name, err),
+        ),
+        None => InstructionDescription::program_only(&idl.name),
+    }
+}
+
+// ==================== C Conversion ==================== //
+
+fn to_c_string(value: Option<String>) -> *mut c_char {
+    value
+        .and_then(|value| CString::new(value).ok())
+        .map_or(std::ptr::null_mut(), CString::into_raw)
+}
+
+fn boxed_slice_into_raw<T>(values: Vec<T>) -> (*mut T, usize) {
+    let values = values.into_boxed_slice();
+    let len = values.len();
+    (Box::into_raw(values) as *mut T, len)
+}
+
+// A given IDL is tried first, then the IDL registered for the program
+fn decoded_instruction(
+    client: Option<&SolClient>,
+    instruction: Instruction,
+    idl: Option<&Idl>,
+) -> SolDecodedInstruction {
+    let description = describe_instruction(&instruction)
+        .or_else(|| idl.and_then(|idl| describe_idl_instruction(idl, &instruction)))
+        .or_else(|| {
+            client
+                .and_then(|client| client.idl(&instruction.program_id))
+                .map(|idl| describe_program_instruction(idl, &instruction))
+        });
+    let (accounts, account_count) = boxed_slice_into_raw(
+        instruction
+            .accounts
+            .iter()
+            .map(|meta| SolAccountMeta {
+                pubkey: SolPublicKey::new(meta.pubkey),
+                is_signer: meta.is_signer,
+                is_writable: meta.is_writable,
+            })
+            .collect(),
+    );
+    let (data, data_len) = boxed_slice_into_raw(instruction.data);
+    let (program_name, instruction_name, description) = match description {
+        Some(description) => (
+            Some(description.program_name),
+            description.instruction_name,
+            description.description,
+        ),
+        None => (None, None, None),
+    };
+
+    SolDecodedInstruction {
+        program_id: SolPublicKey::new(instruction.program_id),
+        accounts,
+        account_count,
+        data,
+        data_len,
+        program_name: to_c_string(program_name),
+        instruction_name: to_c_string(instruction_name),
+        description: to_c_string(description),
+    }
+}
+
+// Lookup tables are fetched only when a client is given
+fn decode(
+    client: Option<&SolClient>,
+    idl: Option<&Idl>,
+    transaction: &VersionedTransaction,
+) -> *mut SolDecodedTransaction {
+    let mut lookup_tables = Vec::new();
+    if let (Some(client), Some(lookups)) = (client, transaction.message.address_table_lookups()) {
+        for lookup in lookups {
+            match fetch_lookup_table(client, &lookup.account_key) {
+                Ok(table) => lookup_tables.push(table),
+                Err(err) => eprintln!("{}", err),
+            }
+        }
+    }
+
+    let (instructions, lookup_tables_resolved) =
+        decompile_instructions(transaction, &lookup_tables);
+    let (instructions, instruction_count) = boxed_slice_into_raw(
+        instructions
+            .into_iter()
+            .map(|instruction| decoded_instruction(client, instruction, idl))
+            .collect(),
+    );
+    let (signatures, signature_count) = boxed_slice_into_raw(
+        transaction
+            .signatures
+            .iter()
+            .map(|signature| SolSignature::new(*signature))
+            .collect(),
+    );
+
+    Box::into_raw(Box::new(SolDecodedTransaction {
+        is_versioned: matches!(transaction.message, VersionedMessage::V0(_)),
+        fee_payer: SolPublicKey::new(
+            transaction
+                .message
+                .static_account_keys()
+                .first()
+                .copied()
+                .unwrap_or_default(),
+        ),
+        recent_blockhash: to_c_string(Some(transaction.message.recent_blockhash().to_string())),
+        signatures,
+        signature_count,
+        instructions,
+        instruction_count,
+        lookup_tables_resolved,
+    }))
+}
+
+// ==================== Decoding Functions ==================== //
+
+// Decode a serialized legacy or v0 transaction. `client` may be NULL; with a
+// client, accounts from address lookup tables are resolved and instructions of
+// programs registered with `sol_client_register_idl` are decoded.
+#[no_mangle]
+pub extern "C" fn decode_transaction_c(
+    client: *mut SolClient,
+    data: *const u8,
+    data_len: usize,
+) -> *mut SolDecodedTransaction {
+    if data.is_null() {
+        return std::ptr::null_mut();
+    }
+    let data = unsafe { std::slice::from_raw_parts(data, data_len) };
+
+    match deserialize_transaction(data) {
+        Ok(transaction) => decode(unsafe { client.as_ref() }, None, &transaction),
+        Err(err) => {
+            eprintln!("{}", err);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+// Decode a transaction held in a `SolTransaction`, e.g. one received as base64
+#[no_mangle]
+pub extern "C" fn decode_sol_transaction(
+    client: *mut SolClient,
+    transaction: *const SolTransaction,
+) -> *mut SolDecodedTransaction {
+    let transaction = unsafe {
+        assert!(!transaction.is_null());
+        &*transaction
+    };
+
+    decode(unsafe { client.as_ref() }, None, &transaction.transaction)
+}
+
+// Like `decode_transaction_c`, also decoding instructions of the IDL's program
+// with their arguments
+#[no_mangle]
+pub extern "C" fn decode_transaction_with_idl(
+    client: *mut SolClient,
+    idl: *const SolIdl,
+    data: *const u8,
+    data_len: usize,
+) -> *mut SolDecodedTransaction {
+    let idl = unsafe {
+        assert!(!idl.is_null());
+        &*idl
+    };
+    if data.is_null() {
+        return std::ptr::null_mut();
+    }
+    let data = unsafe { std::slice::from_raw_parts(data, data_len) };
+
+    match deserialize_transaction(data) {
+        Ok(transaction) => decode(unsafe { client.as_ref() }, Some(&idl.idl), &transaction),
+        Err(err) => {
+            eprintln!("{}", err);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn free_decoded_transaction(transaction: *mut SolDecodedTransaction) {
+    if transaction.is_null() {
+        return;
+    }
+    unsafe {
+        let transaction = Box::from_raw(transaction);
+        if !transaction.recent_blockhash.is_null() {
+            drop(CString::from_raw(transaction.recent_blockhash));
+        }
+        drop(Box::from_raw(std::ptr::slice_from_raw_parts_mut(
+            transaction.signatures,
+            transaction.signature_count,
+        )));
+
+        let instructions = Box::from_raw(std::ptr::slice_from_raw_parts_mut(
+            transaction.instructions,
+            transaction.instruction_count,
+        ));
+        for instruction in instructions.iter() {
+            drop(Box::from_raw(std::ptr::slice_from_raw_parts_mut(
+                instruction.accounts,
+                instruction.account_count,
+            )));
+            drop(Box::from_raw(std::ptr::slice_from_raw_parts_mut(
+                instruction.data,
+                instruction.data_len,
+            )));
+            for text in [
+                instruction.program_name,
+                instruction.instruction_name,
+                instruction.description,
+            ] {
+                if !text.is_null() {
+                    drop(CString::from_raw(text));
+                }
+            }
+        }
+    }
+}
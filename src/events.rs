@@ -0,0 +1,239 @@
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use serde_json::{json, Value};
+use solana_client::rpc_config::RpcTransactionConfig;
+use solana_sdk::bs58;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use solana_transaction_status::{UiInstruction, UiLoadedAddresses, UiTransactionEncoding};
+use std::ffi::{CStr, CString};
+use std::os::raw::c_char;
+use std::str::FromStr;
+
+use crate::client::SolClient;
+use crate::idl::{program_id_from_c, Idl, IdlType, SolIdl};
+use crate::idl_codec::Decoder;
+
+// Prefix of the self-invoked instruction `emit_cpi!` uses to carry an event
+const EVENT_IX_TAG_LE: [u8; 8] = [0xe4, 0x45, 0xa5, 0x2e, 0x51, 0xcb, 0x9a, 0x1d];
+
+// ==================== Event Parsing ==================== //
+
+// `{"name": ..., "data": {...}}` for data starting with an IDL event discriminator
+pub fn decode_event(idl: &Idl, data: &[u8]) -> Option<Result<Value, String>> {
+    let event = idl
+        .events
+        .iter()
+        .find(|event| data.starts_with(&event.discriminator))?;
+    let ty = IdlType::Defined {
+        name: event.name.clone(),
+        generics: Vec::new(),
+    };
+    let mut decoder = Decoder::new(idl, &data[event.discriminator.len()..]);
+    Some(
+        decoder
+            .decode(&ty, &Default::default())
+            .map(|data| json!({ "name": event.name, "data": data }))
+            .map_err(|err| format!("Invalid {} event: {}", event.name, err)),
+    )
+}
+
+fn push_event(events: &mut Vec<Value>, idl: &Idl, data: &[u8]) {
+    match decode_event(idl, data) {
+        Some(Ok(event)) => events.push(event),
+        Some(Err(err)) => eprintln!("{}", err),
+        None => {}
+    }
+}
+
+// Events from `Program data:` lines written while `program_id` was the running
+// program. Without a program id, data from any program is matched.
+pub fn parse_log_events(idl: &Idl, program_id: Option<&Pubkey>, logs: &[String]) -> Vec<Value> {
+    let program_id = program_id.map(Pubkey::to_string);
+    let mut call_stack: Vec<&str> = Vec::new();
+    let mut events = Vec::new();
+
+    for line in logs {
+        let Some(rest) = line.strip_prefix("Program ") else {
+            continue;
+        };
+        if let Some(data) = rest.strip_prefix("data: ") {
+            let running = call_stack.last().copied();
+            // Log subscriptions may start mid-transaction, with no invoke line seen
+            if program_id
+                .as_deref()
+                .is_some_and(|id| running.is_some_and(|running| running != id))
+            {
+                continue;
+            }
+            match BASE64.decode(data.trim()) {
+                Ok(data) => push_event(&mut events, idl, &data),
+                Err(err) => eprintln!("Invalid program data: {}", err),
+            }
+        } else if let Some((program, status)) = rest.split_once(' ') {
+            if status.starts_with("invoke [") {
+                call_stack.push(program);
+            } else if status == "success" || status.starts_with("failed") {
+                call_stack.pop();
+            }
+        }
+    }
+    events
+}
+
+// Events carried by `emit_cpi!` self-invocations, given (program id, data) pairs
+pub fn parse_cpi_events<'a>(
+    idl: &Idl,
+    program_id: Option<&Pubkey>,
+    instructions: impl IntoIterator<Item = (Pubkey, &'a [u8])>,
+) -> Vec<Value> {
+    let mut events = Vec::new();
+    for (instruction_program, data) in instructions {
+        if program_id.is_some_and(|id| *id != instruction_program) {
+            continue;
+        }
+        if let Some(data) = data.strip_prefix(&EVENT_IX_TAG_LE[..]) {
+            push_event(&mut events, idl, data);
+        }
+    }
+    events
+}
+
+// Fetch a confirmed transaction and collect its log and `emit_cpi!` events
+pub fn fetch_transaction_events(
+    client: &SolClient,
+    idl: &Idl,
+    program_id: Option<&Pubkey>,
+    signature: &Signature,
+) -> Result<Vec<Value>, String> {
+    let config = RpcTransactionConfig {
+        encoding: Some(UiTransactionEncoding::Base64),
+        commitment: Some(CommitmentConfig::confirmed()),
+        max_supported_transaction_version: Some(0),
+    };
+    let transaction = client
+        .rpc_client
+        .get_transaction_with_config(signature, config)
+        .map_err(|err| format!("Failed to fetch transaction {}: {:?}", signature, err))?
+        .transaction;
+    let meta = transaction
+        .meta
+        .ok_or_else(|| format!("Transaction {} has no status metadata", signature))?;
+    let decoded = transaction
+        .transaction
+        .decode()
+        .ok_or_else(|| format!("Failed to decode transaction {}", signature))?;
+
+    let logs: Option<Vec<String>> = meta.log_messages.into();
+    let mut events = parse_log_events(idl, program_id, &logs.unwrap_or_default());
+
+    // Inner instructions index static keys, then loaded writable and read-only keys
+    let mut account_keys = decoded.message.static_account_keys().to_vec();
+    let loaded: Option<UiLoadedAddresses> = meta.loaded_addresses.into();
+    if let Some(loaded) = loaded {
+        for address in loaded.writable.iter().chain(&loaded.readonly) {
+            account_keys.push(Pubkey::from_str(address).unwrap_or_default());
+        }
+    }
+    let inner_instructions: Option<Vec<_>> = meta.inner_instructions.into();
+    let mut instructions = Vec::new();
+    for inner in inner_instructions.unwrap_or_default() {
+        for instruction in inner.instructions {
+            if let UiInstruction::Compiled(instruction) = instruction {
+                let program = account_keys
+                    .get(usize::from(instruction.program_id_index))
+                    .copied()
+                    .unwrap_or_default();
+                if let Ok(data) = bs58::decode(&instruction.data).into_vec() {
+                    instructions.push((program, data));
+                }
+            }
+        }
+    }
+    events.extend(parse_cpi_events(
+        idl,
+        program_id,
+        instructions
+            .iter()
+            .map(|(program, data)| (*program, data.as_slice())),
+    ));
+    Ok(events)
+}
+
+fn events_to_c(events: Vec<Value>) -> *mut c_char {
+    CString::new(Value::Array(events).to_string()).map_or(std::ptr::null_mut(), CString::into_raw)
+}
+
+// ==================== Event Functions ==================== //
+
+// Decode events from log lines, e.g. from a simulation or logs subscription.
+// Returns a JSON array of `{"name": ..., "data": {...}}`.
+#[no_mangle]
+pub extern "C" fn parse_events_from_logs(
+    idl: *const SolIdl,
+    program_id: *const c_char,
+    logs: *const *const c_char,
+    log_count: usize,
+) -> *mut c_char {
+    let idl = unsafe {
+        assert!(!idl.is_null());
+        &*idl
+    };
+    let program_id = match program_id_from_c(&idl.idl, program_id) {
+        Ok(program_id) => program_id,
+        Err(err) => {
+            eprintln!("{}", err);
+            return std::ptr::null_mut();
+        }
+    };
+    let logs: Vec<String> = if logs.is_null() || log_count == 0 {
+        Vec::new()
+    } else {
+        unsafe { std::slice::from_raw_parts(logs, log_count) }
+            .iter()
+            .filter(|line| !line.is_null())
+            .map(|line| {
+                unsafe { CStr::from_ptr(*line) }
+                    .to_string_lossy()
+                    .into_owned()
+            })
+            .collect()
+    };
+
+    events_to_c(parse_log_events(&idl.idl, program_id.as_ref(), &logs))
+}
+
+// Decode the events of a confirmed transaction, including `emit_cpi!` events
+#[no_mangle]
+pub extern "C" fn get_transaction_events(
+    client: *mut SolClient,
+    idl: *const SolIdl,
+    program_id: *const c_char,
+    signature: *const c_char,
+) -> *mut c_char {
+    let client = unsafe {
+        assert!(!client.is_null());
+        &*client
+    };
+    let idl = unsafe {
+        assert!(!idl.is_null());
+        &*idl
+    };
+    let signature = match unsafe { CStr::from_ptr(signature) }.to_str() {
+        Ok(str) => str,
+        Err(_) => return std::ptr::null_mut(),
+    };
+
+    let result = program_id_from_c(&idl.idl, program_id).and_then(|program_id| {
+        let signature = Signature::from_str(signature.trim())
+            .map_err(|err| format!("Invalid signature: {}", err))?;
+        fetch_transaction_events(client, &idl.idl, program_id.as_ref(), &signature)
+    });
+    match result {
+        Ok(events) => events_to_c(events),
+        Err(err) => {
+            eprintln!("{}", err);
+            std::ptr::null_mut()
+        }
+    }
+}
@@ -0,0 +1,1131 @@
+use serde_json::{json, Value};
+use solana_sdk::pubkey::Pubkey;
+use std::ffi::{CStr, CString};
+use std::os::raw::c_char;
+use std::str::FromStr;
+
+use crate::client::SolClient;
+use crate::smart_contract::{anchor_discriminator, program_pubkey_from_c};
+use crate::wallet::SolPublicKey;
+
+// An Anchor IDL. Legacy (pre-0.30) IDLs are converted to the 0.30+ layout:
+// discriminators are computed, inline account and event types are moved to
+// `types` and PDA seed constants are stored as bytes.
+#[derive(Clone, Debug)]
+pub struct Idl {
+    pub address: Option<Pubkey>,
+    pub name: String,
+    pub version: String,
+    pub instructions: Vec<IdlInstruction>,
+    pub accounts: Vec<IdlAccountDef>,
+    pub events: Vec<IdlEventDef>,
+    pub errors: Vec<IdlErrorCode>,
+    pub types: Vec<IdlTypeDef>,
+}
+
+#[derive(Clone, Debug)]
+pub struct IdlInstruction {
+    pub name: String,
+    pub discriminator: Vec<u8>,
+    pub accounts: Vec<IdlInstructionAccountItem>,
+    pub args: Vec<IdlField>,
+    pub returns: Option<IdlType>,
+}
+
+#[derive(Clone, Debug)]
+pub enum IdlInstructionAccountItem {
+    Single(IdlInstructionAccount),
+    Composite {
+        name: String,
+        accounts: Vec<IdlInstructionAccountItem>,
+    },
+}
+
+#[derive(Clone, Debug)]
+pub struct IdlInstructionAccount {
+    pub name: String,
+    pub writable: bool,
+    pub signer: bool,
+    pub optional: bool,
+    pub address: Option<Pubkey>,
+    pub pda: Option<IdlPda>,
+    pub relations: Vec<String>,
+}
+
+#[derive(Clone, Debug)]
+pub struct IdlPda {
+    pub seeds: Vec<IdlSeed>,
+    pub program: Option<IdlSeed>,
+}
+
+#[derive(Clone, Debug)]
+pub enum IdlSeed {
+    Const {
+        value: Vec<u8>,
+    },
+    Arg {
+        path: String,
+    },
+    Account {
+        path: String,
+        account: Option<String>,
+    },
+}
+
+#[derive(Clone, Debug)]
+pub struct IdlField {
+    pub name: String,
+    pub ty: IdlType,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum IdlType {
+    Bool,
+    U8,
+    I8,
+    U16,
+    I16,
+    U32,
+    I32,
+    F32,
+    U64,
+    I64,
+    F64,
+    U128,
+    I128,
+    U256,
+    I256,
+    Bytes,
+    String,
+    Pubkey,
+    Option(Box<IdlType>),
+    COption(Box<IdlType>),
+    Vec(Box<IdlType>),
+    Array(Box<IdlType>, IdlArrayLen),
+    Defined {
+        name: String,
+        generics: Vec<IdlGenericArg>,
+    },
+    Generic(String),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum IdlArrayLen {
+    Value(usize),
+    Generic(String),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum IdlGenericArg {
+    Type(IdlType),
+    Const(String),
+}
+
+#[derive(Clone, Debug)]
+pub struct IdlTypeDef {
+    pub name: String,
+    pub generics: Vec<String>,
+    pub serialization: IdlSerialization,
+    pub repr: Option<IdlRepr>,
+    pub ty: IdlTypeDefTy,
+}
+
+// How values of a type are laid out; only Borsh is supported by the codec
+#[derive(Clone, Debug, PartialEq)]
+pub enum IdlSerialization {
+    Borsh,
+    Bytemuck,
+    BytemuckUnsafe,
+    Custom(String),
+}
+
+// `#[repr]` of a zero-copy type, e.g. `{"kind":"c","packed":true}`
+#[derive(Clone, Debug, PartialEq)]
+pub struct IdlRepr {
+    pub kind: String,
+    pub packed: bool,
+    pub align: Option<usize>,
+}
+
+#[derive(Clone, Debug)]
+pub enum IdlTypeDefTy {
+    Struct { fields: IdlDefinedFields },
+    Enum { variants: Vec<IdlEnumVariant> },
+    Type { alias: IdlType },
+}
+
+#[derive(Clone, Debug)]
+pub enum IdlDefinedFields {
+    None,
+    Named(Vec<IdlField>),
+    Tuple(Vec<IdlType>),
+}
+
+#[derive(Clone, Debug)]
+pub struct IdlEnumVariant {
+    pub name: String,
+    pub fields: IdlDefinedFields,
+}
+
+#[derive(Clone, Debug)]
+pub struct IdlAccountDef {
+    pub name: String,
+    pub discriminator: Vec<u8>,
+}
+
+#[derive(Clone, Debug)]
+pub struct IdlEventDef {
+    pub name: String,
+    pub discriminator: Vec<u8>,
+}
+
+#[derive(Clone, Debug)]
+pub struct IdlErrorCode {
+    pub code: u32,
+    pub name: String,
+    pub msg: Option<String>,
+}
+
+impl Idl {
+    pub fn from_json(json: &str) -> Result<Idl, String> {
+        let value: Value =
+            serde_json::from_str(json).map_err(|err| format!("Invalid IDL JSON: {}", err))?;
+        let legacy = value.pointer("/metadata/spec").is_none();
+        Parser { legacy }.idl(&value)
+    }
+
+    // Names match exactly or after converting camelCase to snake_case
+    pub fn instruction(&self, name: &str) -> Option<&IdlInstruction> {
+        find_by_name(&self.instructions, name, |instruction| &instruction.name)
+    }
+
+    pub fn account(&self, name: &str) -> Option<&IdlAccountDef> {
+        find_by_name(&self.accounts, name, |account| &account.name)
+    }
+
+    pub fn error(&self, code: u32) -> Option<&IdlErrorCode> {
+        self.errors.iter().find(|error| error.code == code)
+    }
+
+    pub fn type_def(&self, name: &str) -> Option<&IdlTypeDef> {
+        self.types.iter().find(|ty| ty.name == name)
+    }
+}
+
+fn find_by_name<'a, T>(
+    items: &'a [T],
+    name: &str,
+    item_name: impl Fn(&T) -> &String,
+) -> Option<&'a T> {
+    items
+        .iter()
+        .find(|item| item_name(item) == name)
+        .or_else(|| {
+            let name = to_snake_case(name);
+            items
+                .iter()
+                .find(|item| to_snake_case(item_name(item)) == name)
+        })
+}
+
+pub fn to_snake_case(name: &str) -> String {
+    let mut snake = String::with_capacity(name.len() + 4);
+    let mut previous_lower = false;
+    for c in name.chars() {
+        if c.is_uppercase() {
+            if previous_lower {
+                snake.push('_');
+            }
+            snake.extend(c.to_lowercase());
+            previous_lower = false;
+        } else {
+            snake.push(c);
+            previous_lower = c.is_lowercase() || c.is_ascii_digit();
+        }
+    }
+    snake
+}
+
+// ==================== Parsing ==================== //
+
+struct Parser {
+    legacy: bool,
+}
+
+fn field<'a>(value: &'a Value, key: &str, context: &str) -> Result<&'a Value, String> {
+    value
+        .get(key)
+        .ok_or_else(|| format!("IDL {} is missing \"{}\"", context, key))
+}
+
+fn string(value: &Value, key: &str, context: &str) -> Result<String, String> {
+    field(value, key, context)?
+        .as_str()
+        .map(str::to_string)
+        .ok_or_else(|| format!("IDL {} has a non-string \"{}\"", context, key))
+}
+
+fn array<'a>(value: &'a Value, key: &str) -> &'a [Value] {
+    value
+        .get(key)
+        .and_then(Value::as_array)
+        .map_or(&[], Vec::as_slice)
+}
+
+fn flag(value: &Value, keys: &[&str]) -> bool {
+    keys.iter()
+        .any(|key| value.get(*key).and_then(Value::as_bool).unwrap_or(false))
+}
+
+fn bytes(value: &Value, context: &str) -> Result<Vec<u8>, String> {
+    value
+        .as_array()
+        .ok_or_else(|| format!("IDL {} is not a byte array", context))?
+        .iter()
+        .map(|byte| {
+            byte.as_u64()
+                .and_then(|byte| u8::try_from(byte).ok())
+                .ok_or_else(|| format!("IDL {} has an invalid byte", context))
+        })
+        .collect()
+}
+
+fn pubkey(value: &str, context: &str) -> Result<Pubkey, String> {
+    Pubkey::from_str(value)
+        .map_err(|err| format!("IDL {} has an invalid address: {}", context, err))
+}
+
+impl Parser {
+    fn idl(&self, value: &Value) -> Result<Idl, String> {
+        let (name, version, address) = if self.legacy {
+            (
+                string(value, "name", "root")?,
+                string(value, "version", "root")?,
+                value.pointer("/metadata/address").and_then(Value::as_str),
+            )
+        } else {
+            let metadata = field(value, "metadata", "root")?;
+            (
+                string(metadata, "name", "metadata")?,
+                string(metadata, "version", "metadata")?,
+                value.get("address").and_then(Value::as_str),
+            )
+        };
+        let address = address.map(|address| pubkey(address, "root")).transpose()?;
+
+        let mut types = array(value, "types")
+            .iter()
+            .map(|ty| self.type_def(ty))
+            .collect::<Result<Vec<_>, _>>()?;
+        let accounts = self.account_defs(array(value, "accounts"), &mut types)?;
+        let events = self.event_defs(array(value, "events"), &mut types)?;
+
+        Ok(Idl {
+            address,
+            name,
+            version,
+            instructions: array(value, "instructions")
+                .iter()
+                .map(|instruction| self.instruction(instruction))
+                .collect::<Result<_, _>>()?,
+            accounts,
+            events,
+            errors: array(value, "errors")
+                .iter()
+                .map(|error| {
+                    Ok(IdlErrorCode {
+                        code: field(error, "code", "error")?
+                            .as_u64()
+                            .and_then(|code| u32::try_from(code).ok())
+                            .ok_or("IDL error has an invalid code")?,
+                        name: string(error, "name", "error")?,
+                        msg: error.get("msg").and_then(Value::as_str).map(str::to_string),
+                    })
+                })
+                .collect::<Result<_, String>>()?,
+            types,
+        })
+    }
+
+    fn discriminator(&self, value: &Value, namespace: &str, name: &str) -> Result<Vec<u8>, String> {
+        match value.get("discriminator") {
+            Some(discriminator) => bytes(discriminator, &format!("discriminator of {}", name)),
+            None if self.legacy => {
+                let name = if namespace == "global" {
+                    to_snake_case(name)
+                } else {
+                    name.to_string()
+                };
+                Ok(anchor_discriminator(namespace, &name).to_vec())
+            }
+            None => Err(format!("IDL {} is missing \"discriminator\"", name)),
+        }
+    }
+
+    fn instruction(&self, value: &Value) -> Result<IdlInstruction, String> {
+        let name = string(value, "name", "instruction")?;
+        Ok(IdlInstruction {
+            discriminator: self.discriminator(value, "global", &name)?,
+            accounts: array(value, "accounts")
+                .iter()
+                .map(|account| self.account_item(account))
+                .collect::<Result<_, _>>()?,
+            args: self.fields(array(value, "args"))?,
+            returns: value
+                .get("returns")
+                .filter(|returns| !returns.is_null())
+                .map(|returns| self.ty(returns))
+                .transpose()?,
+            name,
+        })
+    }
+
+    fn account_item(&self, value: &Value) -> Result<IdlInstructionAccountItem, String> {
+        let name = string(value, "name", "instruction account")?;
+        if let Some(accounts) = value.get("accounts").and_then(Value::as_array) {
+            return Ok(IdlInstructionAccountItem::Composite {
+                name,
+                accounts: accounts
+                    .iter()
+                    .map(|account| self.account_item(account))
+                    .collect::<Result<_, _>>()?,
+            });
+        }
+
+        let address = value
+            .get("address")
+            .and_then(Value::as_str)
+            .map(|address| pubkey(address, &name))
+            .transpose()?;
+        let pda = match value.get("pda") {
+            Some(pda) => Some(IdlPda {
+                seeds: array(pda, "seeds")
+                    .iter()
+                    .map(|seed| self.seed(seed))
+                    .collect::<Result<_, _>>()?,
+                program: pda
+                    .get("program")
+                    .or_else(|| pda.get("programId"))
+                    .map(|program| self.seed(program))
+                    .transpose()?,
+            }),
+            None => None,
+        };
+
+        Ok(IdlInstructionAccountItem::Single(IdlInstructionAccount {
+            writable: flag(value, &["writable", "isMut"]),
+            signer: flag(value, &["signer", "isSigner"]),
+            optional: flag(value, &["optional", "isOptional"]),
+            address,
+            pda,
+            relations: array(value, "relations")
+                .iter()
+                .filter_map(Value::as_str)
+                .map(str::to_string)
+                .collect(),
+            name,
+        }))
+    }
+
+    fn seed(&self, value: &Value) -> Result<IdlSeed, String> {
+        match string(value, "kind", "seed")?.as_str() {
+            "const" => Ok(IdlSeed::Const {
+                value: self.const_seed(value)?,
+            }),
+            "arg" => Ok(IdlSeed::Arg {
+                path: string(value, "path", "seed")?,
+            }),
+            "account" => Ok(IdlSeed::Account {
+                path: string(value, "path", "seed")?,
+                account: value
+                    .get("account")
+                    .and_then(Value::as_str)
+                    .map(str::to_string),
+            }),
+            kind => Err(format!("Unknown IDL seed kind \"{}\"", kind)),
+        }
+    }
+
+    // Legacy constants are typed values; 0.30+ constants are already bytes
+    fn const_seed(&self, value: &Value) -> Result<Vec<u8>, String> {
+        let seed = field(value, "value", "seed")?;
+        if let Some(text) = seed.as_str() {
+            return match value.get("type").map(|ty| self.ty(ty)).transpose()? {
+                Some(IdlType::Pubkey) => Ok(pubkey(text, "seed")?.to_bytes().to_vec()),
+                _ => Ok(text.as_bytes().to_vec()),
+            };
+        }
+        if let Some(number) = seed.as_u64() {
+            let ty = value.get("type").map(|ty| self.ty(ty)).transpose()?;
+            let too_large = |_| format!("Seed value {} is too large for its type", number);
+            return Ok(match ty {
+                Some(IdlType::U16) => u16::try_from(number)
+                    .map_err(too_large)?
+                    .to_le_bytes()
+                    .to_vec(),
+                Some(IdlType::U32) => u32::try_from(number)
+                    .map_err(too_large)?
+                    .to_le_bytes()
+                    .to_vec(),
+                Some(IdlType::U64) => number.to_le_bytes().to_vec(),
+                // Untyped numbers are single bytes
+                _ => vec![u8::try_from(number).map_err(too_large)?],
+            });
+        }
+        bytes(seed, "seed")
+    }
+
+    fn fields(&self, values: &[Value]) -> Result<Vec<IdlField>, String> {
+        values
+            .iter()
+            .map(|value| {
+                Ok(IdlField {
+                    name: string(value, "name", "field")?,
+                    ty: self.ty(field(value, "type", "field")?)?,
+                })
+            })
+            .collect()
+    }
+
+    fn defined_fields(&self, value: Option<&Value>) -> Result<IdlDefinedFields, String> {
+        let values = match value.and_then(Value::as_array) {
+            Some(values) if !values.is_empty() => values,
+            _ => return Ok(IdlDefinedFields::None),
+        };
+        if values[0].get("name").is_some() {
+            Ok(IdlDefinedFields::Named(self.fields(values)?))
+        } else {
+            Ok(IdlDefinedFields::Tuple(
+                values
+                    .iter()
+                    .map(|value| self.ty(value))
+                    .collect::<Result<_, _>>()?,
+            ))
+        }
+    }
+
+    fn type_def(&self, value: &Value) -> Result<IdlTypeDef, String> {
+        let name = string(value, "name", "type")?;
+        let ty = field(value, "type", &name)?;
+        let ty = match string(ty, "kind", &name)?.as_str() {
+            "struct" => IdlTypeDefTy::Struct {
+                fields: self.defined_fields(ty.get("fields"))?,
+            },
+            "enum" => IdlTypeDefTy::Enum {
+                variants: array(ty, "variants")
+                    .iter()
+                    .map(|variant| {
+                        Ok(IdlEnumVariant {
+                            name: string(variant, "name", "enum variant")?,
+                            fields: self.defined_fields(variant.get("fields"))?,
+                        })
+                    })
+                    .collect::<Result<_, String>>()?,
+            },
+            "type" => IdlTypeDefTy::Type {
+                alias: self.ty(field(ty, "alias", &name)?)?,
+            },
+            kind => return Err(format!("Unknown IDL type kind \"{}\" for {}", kind, name)),
+        };
+
+        let serialization = match value.get("serialization").and_then(Value::as_str) {
+            None | Some("borsh") => IdlSerialization::Borsh,
+            Some("bytemuck") => IdlSerialization::Bytemuck,
+            Some("bytemuckunsafe") => IdlSerialization::BytemuckUnsafe,
+            Some(custom) => IdlSerialization::Custom(custom.to_string()),
+        };
+        let repr = match value.get("repr") {
+            Some(repr) => Some(IdlRepr {
+                kind: string(repr, "kind", &name)?,
+                packed: repr.get("packed").and_then(Value::as_bool).unwrap_or(false),
+                align: repr
+                    .get("align")
+                    .and_then(Value::as_u64)
+                    .map(|align| align as usize),
+            }),
+            None => None,
+        };
+
+        Ok(IdlTypeDef {
+            generics: array(value, "generics")
+                .iter()
+                .filter_map(|generic| generic.get("name").and_then(Value::as_str))
+                .map(str::to_string)
+                .collect(),
+            name,
+            serialization,
+            repr,
+            ty,
+        })
+    }
+
+    fn account_defs(
+        &self,
+        values: &[Value],
+        types: &mut Vec<IdlTypeDef>,
+    ) -> Result<Vec<IdlAccountDef>, String> {
+        values
+            .iter()
+            .map(|value| {
+                let name = string(value, "name", "account")?;
+                if value.get("type").is_some() && !types.iter().any(|ty| ty.name == name) {
+                    types.push(self.type_def(value)?);
+                }
+                Ok(IdlAccountDef {
+                    discriminator: self.discriminator(value, "account", &name)?,
+                    name,
+                })
+            })
+            .collect()
+    }
+
+    fn event_defs(
+        &self,
+        values: &[Value],
+        types: &mut Vec<IdlTypeDef>,
+    ) -> Result<Vec<IdlEventDef>, String> {
+        values
+            .iter()
+            .map(|value| {
+                let name = string(value, "name", "event")?;
+                if value.get("fields").is_some() && !types.iter().any(|ty| ty.name == name) {
+                    types.push(IdlTypeDef {
+                        name: name.clone(),
+                        generics: Vec::new(),
+                        serialization: IdlSerialization::Borsh,
+                        repr: None,
+                        ty: IdlTypeDefTy::Struct {
+                            fields: self.defined_fields(value.get("fields"))?,
+                        },
+                    });
+                }
+                Ok(IdlEventDef {
+                    discriminator: self.discriminator(value, "event", &name)?,
+                    name,
+                })
+            })
+            .collect()
+    }
+
+    fn ty(&self, value: &Value) -> Result<IdlType, String> {
+        if let Some(name) = value.as_str() {
+            return Ok(match name {
+                "bool" => IdlType::Bool,
+                "u8" => IdlType::U8,
+                "i8" => IdlType::I8,
+                "u16" => IdlType::U16,
+                "i16" => IdlType::I16,
+                "u32" => IdlType::U32,
+                "i32" => IdlType::I32,
+                "f32" => IdlType::F32,
+                "u64" => IdlType::U64,
+                "i64" => IdlType::I64,
+                "f64" => IdlType::F64,
+                "u128" => IdlType::U128,
+                "i128" => IdlType::I128,
+                "u256" => IdlType::U256,
+                "i256" => IdlType::I256,
+                "bytes" => IdlType::Bytes,
+                "string" => IdlType::String,
+                "pubkey" | "publicKey" => IdlType::Pubkey,
+                other => return Err(format!("Unknown IDL type \"{}\"", other)),
+            });
+        }
+
+        let object = value
+            .as_object()
+            .ok_or_else(|| format!("Invalid IDL type {}", value))?;
+        let (kind, inner) = object
+            .iter()
+            .next()
+            .ok_or_else(|| format!("Invalid IDL type {}", value))?;
+        match kind.as_str() {
+            "option" => Ok(IdlType::Option(Box::new(self.ty(inner)?))),
+            "coption" => Ok(IdlType::COption(Box::new(self.ty(inner)?))),
+            "vec" => Ok(IdlType::Vec(Box::new(self.ty(inner)?))),
+            "array" => {
+                let parts = inner
+                    .as_array()
+                    .filter(|parts| parts.len() == 2)
+                    .ok_or_else(|| format!("Invalid IDL array type {}", value))?;
+                let len = match (parts[1].as_u64(), parts[1].get("generic")) {
+                    (Some(len), _) => IdlArrayLen::Value(len as usize),
+                    (None, Some(Value::String(generic))) => IdlArrayLen::Generic(generic.clone()),
+                    _ => return Err(format!("Invalid IDL array length {}", parts[1])),
+                };
+                Ok(IdlType::Array(Box::new(self.ty(&parts[0])?), len))
+            }
+            "defined" => match inner {
+                // Legacy IDLs name the type directly
+                Value::String(name) => Ok(IdlType::Defined {
+                    name: name.clone(),
+                    generics: Vec::new(),
+                }),
+                _ => Ok(IdlType::Defined {
+                    name: string(inner, "name", "defined type")?,
+                    generics: array(inner, "generics")
+                        .iter()
+                        .map(
+                            |generic| match generic.get("kind").and_then(Value::as_str) {
+                                Some("const") => Ok(IdlGenericArg::Const(string(
+                                    generic,
+                                    "value",
+                                    "generic argument",
+                                )?)),
+                                _ => Ok(IdlGenericArg::Type(self.ty(field(
+                                    generic,
+                                    "type",
+                                    "generic argument",
+                                )?)?)),
+                            },
+                        )
+                        .collect::<Result<_, String>>()?,
+                }),
+            },
+            "generic" => Ok(IdlType::Generic(
+                inner
+                    .as_str()
+                    .ok_or_else(|| format!("Invalid IDL generic type {}", value))?
+                    .to_string(),
+            )),
+            _ => Err(format!("Unknown IDL type {}", value)),
+        }
+    }
+}
+
+// ==================== Serialization ==================== //
+
+impl Idl {
+    // The model in the 0.30+ JSON layout, e.g. to upgrade a legacy IDL
+    pub fn to_json(&self) -> Value {
+        let mut idl = json!({
+            "metadata": { "name": self.name, "version": self.version, "spec": "0.1.0" },
+            "instructions": self.instructions.iter().map(IdlInstruction::to_json).collect::<Vec<_>>(),
+            "accounts": self.accounts.iter().map(|account| json!({
+                "name": account.name,
+                "discriminator": account.discriminator,
+            })).collect::<Vec<_>>(),
+            "events": self.events.iter().map(|event| json!({
+                "name": event.name,
+                "discriminator": event.discriminator,
+            })).collect::<Vec<_>>(),
+            "errors": self.errors.iter().map(|error| {
+                let mut value = json!({ "code": error.code, "name": error.name });
+                if let Some(msg) = &error.msg {
+                    value["msg"] = json!(msg);
+                }
+                value
+            }).collect::<Vec<_>>(),
+            "types": self.types.iter().map(IdlTypeDef::to_json).collect::<Vec<_>>(),
+        });
+        if let Some(address) = self.address {
+            idl["address"] = json!(address.to_string());
+        }
+        idl
+    }
+}
+
+impl IdlInstruction {
+    fn to_json(&self) -> Value {
+        let mut value = json!({
+            "name": self.name,
+            "discriminator": self.discriminator,
+            "accounts": self.accounts.iter().map(IdlInstructionAccountItem::to_json).collect::<Vec<_>>(),
+            "args": self.args.iter().map(IdlField::to_json).collect::<Vec<_>>(),
+        });
+        if let Some(returns) = &self.returns {
+            value["returns"] = returns.to_json();
+        }
+        value
+    }
+}
+
+impl IdlInstructionAccountItem {
+    fn to_json(&self) -> Value {
+        let account = match self {
+            IdlInstructionAccountItem::Composite { name, accounts } => {
+                return json!({
+                    "name": name,
+                    "accounts": accounts.iter().map(IdlInstructionAccountItem::to_json).collect::<Vec<_>>(),
+                })
+            }
+            IdlInstructionAccountItem::Single(account) => account,
+        };
+
+        let mut value = json!({ "name": account.name });
+        for (key, set) in [
+            ("writable", account.writable),
+            ("signer", account.signer),
+            ("optional", account.optional),
+        ] {
+            if set {
+                value[key] = json!(true);
+            }
+        }
+        if let Some(address) = account.address {
+            value["address"] = json!(address.to_string());
+        }
+        if let Some(pda) = &account.pda {
+            value["pda"] =
+                json!({ "seeds": pda.seeds.iter().map(IdlSeed::to_json).collect::<Vec<_>>() });
+            if let Some(program) = &pda.program {
+                value["pda"]["program"] = program.to_json();
+            }
+        }
+        if !account.relations.is_empty() {
+            value["relations"] = json!(account.relations);
+        }
+        value
+    }
+}
+
+impl IdlSeed {
+    fn to_json(&self) -> Value {
+        match self {
+            IdlSeed::Const { value } => json!({ "kind": "const", "value": value }),
+            IdlSeed::Arg { path } => json!({ "kind": "arg", "path": path }),
+            IdlSeed::Account { path, account } => {
+                let mut value = json!({ "kind": "account", "path": path });
+                if let Some(account) = account {
+                    value["account"] = json!(account);
+                }
+                value
+            }
+        }
+    }
+}
+
+impl IdlField {
+    fn to_json(&self) -> Value {
+        json!({ "name": self.name, "type": self.ty.to_json() })
+    }
+}
+
+impl IdlType {
+    fn to_json(&self) -> Value {
+        match self {
+            IdlType::Bool => json!("bool"),
+            IdlType::U8 => json!("u8"),
+            IdlType::I8 => json!("i8"),
+            IdlType::U16 => json!("u16"),
+            IdlType::I16 => json!("i16"),
+            IdlType::U32 => json!("u32"),
+            IdlType::I32 => json!("i32"),
+            IdlType::F32 => json!("f32"),
+            IdlType::U64 => json!("u64"),
+            IdlType::I64 => json!("i64"),
+            IdlType::F64 => json!("f64"),
+            IdlType::U128 => json!("u128"),
+            IdlType::I128 => json!("i128"),
+            IdlType::U256 => json!("u256"),
+            IdlType::I256 => json!("i256"),
+            IdlType::Bytes => json!("bytes"),
+            IdlType::String => json!("string"),
+            IdlType::Pubkey => json!("pubkey"),
+            IdlType::Option(inner) => json!({ "option": inner.to_json() }),
+            IdlType::COption(inner) => json!({ "coption": inner.to_json() }),
+            IdlType::Vec(inner) => json!({ "vec": inner.to_json() }),
+            IdlType::Array(inner, len) => {
+                let len = match len {
+                    IdlArrayLen::Value(len) => json!(len),
+                    IdlArrayLen::Generic(generic) => json!({ "generic": generic }),
+                };
+                json!({ "array": [inner.to_json(), len] })
+            }
+            IdlType::Defined { name, generics } => {
+                let mut defined = json!({ "name": name });
+                if !generics.is_empty() {
+                    defined["generics"] = generics
+                        .iter()
+                        .map(|generic| match generic {
+                            IdlGenericArg::Type(ty) => {
+                                json!({ "kind": "type", "type": ty.to_json() })
+                            }
+                            IdlGenericArg::Const(value) => {
+                                json!({ "kind": "const", "value": value })
+                            }
+                        })
+                        .collect();
+                }
+                json!({ "defined": defined })
+            }
+            IdlType::Generic(name) => json!({ "generic": name }),
+        }
+    }
+}
+
+impl IdlDefinedFields {
+    fn to_json(&self) -> Option<Value> {
+        match self {
+            IdlDefinedFields::None => None,
+            IdlDefinedFields::Named(fields) => Some(fields.iter().map(IdlField::to_json).collect()),
+            IdlDefinedFields::Tuple(types) => Some(types.iter().map(IdlType::to_json).collect()),
+        }
+    }
+}
+
+impl IdlTypeDef {
+    fn to_json(&self) -> Value {
+        let ty = match &self.ty {
+            IdlTypeDefTy::Struct { fields } => {
+                let mut ty = json!({ "kind": "struct" });
+                if let Some(fields) = fields.to_json() {
+                    ty["fields"] = fields;
+                }
+                ty
+            }
+            IdlTypeDefTy::Enum { variants } => json!({
+                "kind": "enum",
+                "variants": variants.iter().map(|variant| {
+                    let mut value = json!({ "name": variant.name });
+                    if let Some(fields) = variant.fields.to_json() {
+                        value["fields"] = fields;
+                    }
+                    value
+                }).collect::<Vec<_>>(),
+            }),
+            IdlTypeDefTy::Type { alias } => json!({ "kind": "type", "alias": alias.to_json() }),
+        };
+
+        let mut value = json!({ "name": self.name, "type": ty });
+        if !self.generics.is_empty() {
+            value["generics"] = self
+                .generics
+                .iter()
+                .map(|generic| json!({ "kind": "type", "name": generic }))
+                .collect();
+        }
+        match &self.serialization {
+            IdlSerialization::Borsh => {}
+            IdlSerialization::Bytemuck => value["serialization"] = json!("bytemuck"),
+            IdlSerialization::BytemuckUnsafe => value["serialization"] = json!("bytemuckunsafe"),
+            IdlSerialization::Custom(custom) => value["serialization"] = json!(custom),
+        }
+        if let Some(repr) = &self.repr {
+            let mut json = json!({ "kind": repr.kind });
+            if repr.packed {
+                json["packed"] = json!(true);
+            }
+            if let Some(align) = repr.align {
+                json["align"] = json!(align);
+            }
+            value["repr"] = json;
+        }
+        value
+    }
+}
+
+// The IDL address unless the caller passes another deployment's program id
+pub fn program_id_from_c(idl: &Idl, program_id: *const c_char) -> Result<Option<Pubkey>, String> {
+    if program_id.is_null() {
+        return Ok(idl.address);
+    }
+    program_pubkey_from_c(program_id).map(Some)
+}
+
+// Same, for calls that need a program id to send or register against
+pub fn program_address_from_c(idl: &Idl, program_id: *const c_char) -> Result<Pubkey, String> {
+    program_id_from_c(idl, program_id)?
+        .ok_or_else(|| "The IDL has no program address; pass a program id".to_string())
+}
+
+// ==================== IDL Functions ==================== //
+
+// A parsed IDL; release with `free_idl`
+pub struct SolIdl {
+    pub idl: Idl,
+}
+
+fn idl_into_raw(result: Result<Idl, String>) -> *mut SolIdl {
+    match result {
+        Ok(idl) => Box::into_raw(Box::new(SolIdl { idl })),
+        Err(err) => {
+            eprintln!("{}", err);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn load_idl_from_file(file_path: *const c_char) -> *mut SolIdl {
+    let file_path = match unsafe { CStr::from_ptr(file_path) }.to_str() {
+        Ok(str) => str,
+        Err(_) => return std::ptr::null_mut(),
+    };
+
+    idl_into_raw(
+        std::fs::read_to_string(file_path)
+            .map_err(|err| format!("Failed to read IDL {}: {}", file_path, err))
+            .and_then(|json| Idl::from_json(&json)),
+    )
+}
+
+#[no_mangle]
+pub extern "C" fn load_idl_from_json(json: *const c_char) -> *mut SolIdl {
+    let json = match unsafe { CStr::from_ptr(json) }.to_str() {
+        Ok(str) => str,
+        Err(_) => return std::ptr::null_mut(),
+    };
+
+    idl_into_raw(Idl::from_json(json))
+}
+
+#[no_mangle]
+pub extern "C" fn idl_get_name(idl: *const SolIdl) -> *mut c_char {
+    let idl = unsafe {
+        assert!(!idl.is_null());
+        &*idl
+    };
+
+    CString::new(idl.idl.name.as_str()).map_or(std::ptr::null_mut(), CString::into_raw)
+}
+
+#[no_mangle]
+pub extern "C" fn idl_get_version(idl: *const SolIdl) -> *mut c_char {
+    let idl = unsafe {
+        assert!(!idl.is_null());
+        &*idl
+    };
+
+    CString::new(idl.idl.version.as_str()).map_or(std::ptr::null_mut(), CString::into_raw)
+}
+
+// False when the IDL does not record the program address
+#[no_mangle]
+pub extern "C" fn idl_get_program_id(
+    idl: *const SolIdl,
+    program_id_out: *mut SolPublicKey,
+) -> bool {
+    let idl = unsafe {
+        assert!(!idl.is_null());
+        &*idl
+    };
+    assert!(!program_id_out.is_null());
+
+    match idl.idl.address {
+        Some(address) => {
+            unsafe { *program_id_out = SolPublicKey::new(address) };
+            true
+        }
+        None => false,
+    }
+}
+
+// The IDL in the 0.30+ JSON format; legacy IDLs come back converted
+#[no_mangle]
+pub extern "C" fn idl_to_json(idl: *const SolIdl) -> *mut c_char {
+    let idl = unsafe {
+        assert!(!idl.is_null());
+        &*idl
+    };
+
+    match serde_json::to_string_pretty(&idl.idl.to_json()) {
+        Ok(json) => CString::new(json).map_or(std::ptr::null_mut(), CString::into_raw),
+        Err(err) => {
+            eprintln!("Failed to serialize IDL: {}", err);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn idl_get_instruction_count(idl: *const SolIdl) -> usize {
+    let idl = unsafe {
+        assert!(!idl.is_null());
+        &*idl
+    };
+
+    idl.idl.instructions.len()
+}
+
+// NULL when `index` is out of range
+#[no_mangle]
+pub extern "C" fn idl_get_instruction_name(idl: *const SolIdl, index: usize) -> *mut c_char {
+    let idl = unsafe {
+        assert!(!idl.is_null());
+        &*idl
+    };
+
+    idl.idl
+        .instructions
+        .get(index)
+        .and_then(|instruction| CString::new(instruction.name.as_str()).ok())
+        .map_or(std::ptr::null_mut(), CString::into_raw)
+}
+
+// Writes up to 8 discriminator bytes for the named instruction
+#[no_mangle]
+pub extern "C" fn idl_get_instruction_discriminator(
+    idl: *const SolIdl,
+    instruction_name: *const c_char,
+    discriminator_out: *mut u8,
+    discriminator_out_len: usize,
+) -> usize {
+    let idl = unsafe {
+        assert!(!idl.is_null());
+        &*idl
+    };
+    let instruction_name = match unsafe { CStr::from_ptr(instruction_name) }.to_str() {
+        Ok(str) => str,
+        Err(_) => return 0,
+    };
+
+    let instruction = match idl.idl.instruction(instruction_name) {
+        Some(instruction) => instruction,
+        None => {
+            eprintln!("Instruction {} not found in IDL", instruction_name);
+            return 0;
+        }
+    };
+    let discriminator = &instruction.discriminator;
+    if discriminator_out.is_null() || discriminator_out_len < discriminator.len() {
+        return discriminator.len();
+    }
+    unsafe {
+        std::ptr::copy_nonoverlapping(
+            discriminator.as_ptr(),
+            discriminator_out,
+            discriminator.len(),
+        )
+    };
+    discriminator.len()
+}
+
+// Keep a copy of the IDL on the client so failed transactions report the
+// program's error names. `program_id` may be NULL to use the IDL address.
+#[no_mangle]
+pub extern "C" fn sol_client_register_idl(
+    client: *mut SolClient,
+    idl: *const SolIdl,
+    program_id: *const c_char,
+) -> bool {
+    let client = unsafe {
+        assert!(!client.is_null());
+        &mut *client
+    };
+    let idl = unsafe {
+        assert!(!idl.is_null());
+        &*idl
+    };
+    let program_id = match program_address_from_c(&idl.idl, program_id) {
+        Ok(program_id) => program_id,
+        Err(err) => {
+            eprintln!("{}", err);
+            return false;
+        }
+    };
+
+    client.idls.retain(|(program, _)| *program != program_id);
+    client.idls.push((program_id, idl.idl.clone()));
+    true
+}
+
+#[no_mangle]
+pub extern "C" fn free_idl(idl: *mut SolIdl) {
+    if idl.is_null() {
+        return;
+    }
+    unsafe {
+        drop(Box::from_raw(idl));
+    }
+}
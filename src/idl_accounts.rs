@@ -0,0 +1,573 @@
+use serde_json::{Map, Value};
+use solana_sdk::instruction::{AccountMeta, Instruction};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::{system_program, sysvar};
+use std::collections::HashMap;
+use std::ffi::CStr;
+use std::os::raw::c_char;
+use std::str::FromStr;
+
+use crate::client::SolClient;
+use crate::idl::{
+    program_address_from_c, to_snake_case, Idl, IdlDefinedFields, IdlInstruction,
+    IdlInstructionAccount, IdlInstructionAccountItem, IdlPda, IdlSeed, IdlType, IdlTypeDefTy,
+    SolIdl,
+};
+use crate::idl_codec::{
+    args_from_c, decode_account_data, encode_instruction_data, object_field, send_signed_by,
+    Encoder,
+};
+use crate::smart_contract::result_text;
+use crate::transaction::{SolAccountMeta, SolTransactionResult};
+use crate::wallet::{SolKeyPair, SolPublicKey};
+
+// Accounts legacy IDLs leave to the client by name, without an address
+const WELL_KNOWN_ACCOUNTS: &[(&str, Pubkey)] = &[
+    ("system_program", system_program::ID),
+    ("token_program", spl_token::ID),
+    ("associated_token_program", spl_associated_token_account::ID),
+    ("rent", sysvar::rent::ID),
+    ("clock", sysvar::clock::ID),
+];
+
+// ==================== Account Resolution ==================== //
+
+// An instruction account with its path, e.g. `vault.authority` inside a
+// composite `vault` group
+struct FlatAccount<'a> {
+    path: String,
+    account: &'a IdlInstructionAccount,
+}
+
+fn flatten<'a>(
+    items: &'a [IdlInstructionAccountItem],
+    prefix: &str,
+    out: &mut Vec<FlatAccount<'a>>,
+) {
+    for item in items {
+        match item {
+            IdlInstructionAccountItem::Single(account) => out.push(FlatAccount {
+                path: format!("{}{}", prefix, to_snake_case(&account.name)),
+                account,
+            }),
+            IdlInstructionAccountItem::Composite { name, accounts } => flatten(
+                accounts,
+                &format!("{}{}.", prefix, to_snake_case(name)),
+                out,
+            ),
+        }
+    }
+}
+
+// `a.bCd` -> `a.b_cd`, so paths match whichever case the IDL used
+fn snake_path(path: &str) -> String {
+    path.split('.')
+        .map(to_snake_case)
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+// The type of a field reached from `ty` through struct field names
+fn field_type(idl: &Idl, ty: &IdlType, fields: &[&str]) -> Result<IdlType, String> {
+    let Some((field, rest)) = fields.split_first() else {
+        return Ok(ty.clone());
+    };
+    let IdlType::Defined { name, .. } = ty else {
+        return Err(format!("Cannot take field {} of {:?}", field, ty));
+    };
+    let type_def = idl
+        .type_def(name)
+        .ok_or_else(|| format!("Type {} not found in IDL", name))?;
+    let IdlTypeDefTy::Struct {
+        fields: IdlDefinedFields::Named(named),
+    } = &type_def.ty
+    else {
+        return Err(format!("Type {} has no named fields", name));
+    };
+    let next = named
+        .iter()
+        .find(|named| to_snake_case(&named.name) == to_snake_case(field))
+        .ok_or_else(|| format!("Type {} has no field {}", name, field))?;
+    field_type(idl, &next.ty, rest)
+}
+
+// A JSON value reached through object field names
+fn field_value<'v>(value: &'v Value, fields: &[&str]) -> Result<&'v Value, String> {
+    fields.iter().try_fold(value, |value, field| {
+        value
+            .as_object()
+            .and_then(|object| object_field(object, field))
+            .ok_or_else(|| format!("Missing field {}", field))
+    })
+}
+
+// Seed bytes as Anchor builds them: strings and byte vectors are used raw,
+// everything else in its Borsh form (little-endian integers, 32-byte keys)
+fn seed_bytes(idl: &Idl, ty: &IdlType, value: &Value) -> Result<Vec<u8>, String> {
+    if let (IdlType::String, Some(text)) = (ty, value.as_str()) {
+        return Ok(text.as_bytes().to_vec());
+    }
+    let mut encoder = Encoder::new(idl);
+    encoder.encode(ty, value, &Default::default())?;
+    let mut data = encoder.finish();
+    let raw = match ty {
+        IdlType::Bytes => true,
+        IdlType::Vec(item) => **item == IdlType::U8,
+        _ => false,
+    };
+    if raw {
+        // Drop the Borsh length prefix
+        data.drain(..4);
+    }
+    Ok(data)
+}
+
+struct Resolver<'a> {
+    client: Option<&'a SolClient>,
+    idl: &'a Idl,
+    program_id: Pubkey,
+    instruction: &'a IdlInstruction,
+    args: Value,
+    resolved: HashMap<String, Pubkey>,
+    account_data: HashMap<Pubkey, Option<(String, Value)>>,
+}
+
+impl<'a> Resolver<'a> {
+    // The IDL account type and decoded data of an account, matched by its
+    // discriminator unless the type is given. None if it does not exist yet.
+    fn account_data(
+        &mut self,
+        pubkey: &Pubkey,
+        account_type: Option<&str>,
+    ) -> Result<Option<(String, Value)>, String> {
+        if let Some(data) = self.account_data.get(pubkey) {
+            return Ok(data.clone());
+        }
+        let client = self
+            .client
+            .ok_or_else(|| format!("A client is needed to read account {}", pubkey))?;
+        let account = client
+            .rpc_client
+            .get_account_with_commitment(pubkey, client.rpc_client.commitment())
+            .map_err(|err| format!("Failed to fetch account {}: {:?}", pubkey, err))?
+            .value;
+
+        let decoded = match account {
+            Some(account) => {
+                let account_type = match account_type {
+                    Some(account_type) => account_type.to_string(),
+                    None => self
+                        .idl
+                        .accounts
+                        .iter()
+                        .find(|def| account.data.starts_with(&def.discriminator))
+                        .map(|def| def.name.clone())
+                        .ok_or_else(|| format!("Account {} is not an IDL account", pubkey))?,
+                };
+                let data = decode_account_data(self.idl, &account_type, &account.data)?;
+                Some((account_type, data))
+            }
+            None => None,
+        };
+        self.account_data.insert(*pubkey, decoded.clone());
+        Ok(decoded)
+    }
+
+    // An account path, relative to the composite group of the account being
+    // resolved, followed by a field path into that account's data
+    fn split_account_path<'p>(&self, group: &str, path: &'p str) -> Option<(Pubkey, Vec<&'p str>)> {
+        let segments: Vec<&str> = path.split('.').collect();
+        (1..=segments.len()).rev().find_map(|len| {
+            let account_path = snake_path(&segments[..len].join("."));
+            self.resolved
+                .get(&format!("{}{}", group, account_path))
+                .or_else(|| self.resolved.get(&account_path))
+                .map(|pubkey| (*pubkey, segments[len..].to_vec()))
+        })
+    }
+
+    // Ok(None) when the seed depends on something not known yet
+    fn seed(&mut self, group: &str, seed: &IdlSeed) -> Result<Option<Vec<u8>>, String> {
+        match seed {
+            IdlSeed::Const { value } => Ok(Some(value.clone())),
+            IdlSeed::Arg { path } => {
+                let segments: Vec<&str> = path.split('.').collect();
+                let arg = self
+                    .instruction
+                    .args
+                    .iter()
+                    .find(|arg| to_snake_case(&arg.name) == to_snake_case(segments[0]))
+                    .ok_or_else(|| format!("Seed argument {} not found in instruction", path))?;
+                let ty = field_type(self.idl, &arg.ty, &segments[1..])?;
+                let value = field_value(&self.args, &segments)
+                    .map_err(|err| format!("Seed argument {}: {}", path, err))?;
+                seed_bytes(self.idl, &ty, value).map(Some)
+            }
+            IdlSeed::Account { path, account } => {
+                let Some((pubkey, fields)) = self.split_account_path(group, path) else {
+                    return Ok(None);
+                };
+                if fields.is_empty() {
+                    return Ok(Some(pubkey.to_bytes().to_vec()));
+                }
+                let Some((account_type, data)) = self.account_data(&pubkey, account.as_deref())?
+                else {
+                    return Err(format!("Seed account {} does not exist", pubkey));
+                };
+                let ty = field_type(self.idl, &defined(&account_type), &fields)?;
+                let value = field_value(&data, &fields)
+                    .map_err(|err| format!("Seed account {}: {}", path, err))?;
+                seed_bytes(self.idl, &ty, value).map(Some)
+            }
+        }
+    }
+
+    fn derive(&mut self, group: &str, pda: &IdlPda) -> Result<Option<Pubkey>, String> {
+        let mut seeds = Vec::new();
+        for seed in &pda.seeds {
+            match self.seed(group, seed)? {
+                Some(seed) => seeds.push(seed),
+                None => return Ok(None),
+            }
+        }
+        let program_id = match &pda.program {
+            Some(program) => match self.seed(group, program)? {
+                Some(bytes) => Pubkey::try_from(bytes.as_slice())
+                    .map_err(|_| "PDA program seed is not a public key".to_string())?,
+                None => return Ok(None),
+            },
+            None => self.program_id,
+        };
+        let seeds: Vec<&[u8]> = seeds.iter().map(Vec::as_slice).collect();
+        Ok(Some(Pubkey::find_program_address(&seeds, &program_id).0))
+    }
+
+    // An account named in the data of a related account, e.g. the `authority`
+    // stored in a `vault` that has `has_one = authority`. Accounts that cannot
+    // be read or decoded are skipped; the caller can still pass the account.
+    fn relation(&mut self, group: &str, name: &str, relations: &[String]) -> Option<Pubkey> {
+        relations.iter().find_map(|relation| {
+            let (pubkey, _) = self.split_account_path(group, relation)?;
+            let (_, data) = self.account_data(&pubkey, None).ok()??;
+            let address = object_field(data.as_object()?, name)?.as_str()?;
+            Pubkey::from_str(address).ok()
+        })
+    }
+
+    fn resolve_one(&mut self, flat: &FlatAccount) -> Result<Option<Pubkey>, String> {
+        let account = flat.account;
+        let name = to_snake_case(&account.name);
+        if let Some(address) = account.address {
+            // The program's own address follows the deployment being called
+            return Ok(Some(if Some(address) == self.idl.address {
+                self.program_id
+            } else {
+                address
+            }));
+        }
+        let group = &flat.path[..flat.path.len() - name.len()];
+        if let Some(pda) = &account.pda {
+            return self.derive(group, pda);
+        }
+        if let Some((_, address)) = WELL_KNOWN_ACCOUNTS.iter().find(|(known, _)| *known == name) {
+            return Ok(Some(*address));
+        }
+        Ok(self.relation(group, &name, &account.relations))
+    }
+}
+
+fn defined(name: &str) -> IdlType {
+    IdlType::Defined {
+        name: name.to_string(),
+        generics: Vec::new(),
+    }
+}
+
+// Build the account list of an instruction from the IDL. `accounts` holds the
+// accounts that cannot be inferred, keyed by name (or `group.name` inside a
+// composite), as base58 strings; `null` leaves out an optional account.
+pub fn resolve_accounts(
+    client: Option<&SolClient>,
+    idl: &Idl,
+    program_id: &Pubkey,
+    instruction_name: &str,
+    args: &Value,
+    accounts: &Map<String, Value>,
+) -> Result<Vec<AccountMeta>, String> {
+    let instruction = idl
+        .instruction(instruction_name)
+        .ok_or_else(|| format!("Instruction {} not found in IDL", instruction_name))?;
+    let mut flat = Vec::new();
+    flatten(&instruction.accounts, "", &mut flat);
+
+    // Positional arguments are matched to names for arg seeds
+    let args = match args {
+        Value::Array(values) => Value::Object(
+            instruction
+                .args
+                .iter()
+                .zip(values)
+                .map(|(arg, value)| (arg.name.clone(), value.clone()))
+                .collect(),
+        ),
+        _ => args.clone(),
+    };
+
+    let mut resolver = Resolver {
+        client,
+        idl,
+        program_id: *program_id,
+        instruction,
+        args,
+        resolved: HashMap::new(),
+        account_data: HashMap::new(),
+    };
+    let mut omitted = Vec::new();
+    for (key, value) in accounts {
+        let key = snake_path(key);
+        let path = flat
+            .iter()
+            .find(|flat| flat.path == key)
+            .or_else(|| {
+                let mut named = flat
+                    .iter()
+                    .filter(|flat| flat.path.rsplit('.').next() == Some(&key));
+                named.next().filter(|_| named.next().is_none())
+            })
+            .map(|flat| flat.path.clone())
+            .ok_or_else(|| format!("Instruction {} has no account {}", instruction.name, key))?;
+        match value {
+            Value::Null => omitted.push(path),
+            Value::String(address) => {
+                let pubkey = Pubkey::from_str(address)
+                    .map_err(|err| format!("Invalid account {}: {}", key, err))?;
+                resolver.resolved.insert(path, pubkey);
+            }
+            _ => return Err(format!("Account {} must be a base58 string or null", key)),
+        }
+    }
+
+    // Seeds may depend on accounts that are themselves derived, so repeat
+    // until nothing more can be resolved
+    loop {
+        let mut progress = false;
+        for flat in &flat {
+            if resolver.resolved.contains_key(&flat.path) || omitted.contains(&flat.path) {
+                continue;
+            }
+            if let Some(pubkey) = resolver.resolve_one(flat)? {
+                resolver.resolved.insert(flat.path.clone(), pubkey);
+                progress = true;
+            }
+        }
+        if !progress {
+            break;
+        }
+    }
+
+    let missing: Vec<&str> = flat
+        .iter()
+        .filter(|flat| !flat.account.optional && !resolver.resolved.contains_key(&flat.path))
+        .map(|flat| flat.path.as_str())
+        .collect();
+    if !missing.is_empty() {
+        return Err(format!(
+            "Cannot resolve accounts of {}: {}",
+            instruction.name,
+            missing.join(", ")
+        ));
+    }
+
+    // Anchor passes the program id in place of an absent optional account
+    Ok(flat
+        .iter()
+        .map(|flat| match resolver.resolved.get(&flat.path) {
+            Some(pubkey) if flat.account.writable => AccountMeta::new(*pubkey, flat.account.signer),
+            Some(pubkey) => AccountMeta::new_readonly(*pubkey, flat.account.signer),
+            None => AccountMeta::new_readonly(*program_id, false),
+        })
+        .collect())
+}
+
+// NULL or empty JSON means no accounts given
+fn accounts_from_c(accounts_json: *const c_char) -> Result<Map<String, Value>, String> {
+    if accounts_json.is_null() {
+        return Ok(Map::new());
+    }
+    let accounts_json = unsafe { CStr::from_ptr(accounts_json) }
+        .to_str()
+        .map_err(|_| "Invalid accounts string".to_string())?;
+    if accounts_json.trim().is_empty() {
+        return Ok(Map::new());
+    }
+    match serde_json::from_str(accounts_json) {
+        Ok(Value::Object(accounts)) => Ok(accounts),
+        Ok(_) => Err("Accounts JSON must be an object".to_string()),
+        Err(err) => Err(format!("Invalid accounts JSON: {}", err)),
+    }
+}
+
+fn resolve_from_c(
+    client: Option<&SolClient>,
+    idl: &Idl,
+    program_id: *const c_char,
+    instruction_name: *const c_char,
+    args_json: *const c_char,
+    accounts_json: *const c_char,
+) -> Result<(Pubkey, Value, Vec<AccountMeta>), String> {
+    let program_id = program_address_from_c(idl, program_id)?;
+    let instruction_name = unsafe { CStr::from_ptr(instruction_name) }
+        .to_str()
+        .map_err(|_| "Invalid instruction name string".to_string())?;
+    let args = args_from_c(args_json)?;
+    let accounts = accounts_from_c(accounts_json)?;
+    let metas = resolve_accounts(client, idl, &program_id, instruction_name, &args, &accounts)?;
+    Ok((program_id, args, metas))
+}
+
+// ==================== IDL Account Resolution Functions ==================== //
+
+// Write the full, ordered account list of an instruction, resolved from the
+// IDL. `client` may be NULL when no seed or relation needs account data.
+// Returns the account count or 0 on error. Nothing is written when the buffer
+// is NULL or too small, so the count can be queried first.
+#[no_mangle]
+pub extern "C" fn idl_resolve_accounts(
+    client: *mut SolClient,
+    idl: *const SolIdl,
+    program_id: *const c_char,
+    instruction_name: *const c_char,
+    args_json: *const c_char,
+    accounts_json: *const c_char,
+    accounts_out: *mut SolAccountMeta,
+    accounts_out_len: usize,
+) -> usize {
+    let client = unsafe { client.as_ref() };
+    let idl = unsafe {
+        assert!(!idl.is_null());
+        &*idl
+    };
+
+    let metas = match resolve_from_c(
+        client,
+        &idl.idl,
+        program_id,
+        instruction_name,
+        args_json,
+        accounts_json,
+    ) {
+        Ok((_, _, metas)) => metas,
+        Err(err) => {
+            eprintln!("{}", err);
+            return 0;
+        }
+    };
+    if accounts_out.is_null() || accounts_out_len < metas.len() {
+        return metas.len();
+    }
+    let accounts_out = unsafe { std::slice::from_raw_parts_mut(accounts_out, metas.len()) };
+    for (out, meta) in accounts_out.iter_mut().zip(&metas) {
+        *out = SolAccountMeta {
+            pubkey: SolPublicKey::new(meta.pubkey),
+            is_signer: meta.is_signer,
+            is_writable: meta.is_writable,
+        };
+    }
+    metas.len()
+}
+
+// Call an instruction by name, passing only the accounts the IDL cannot
+// supply, e.g. `{"user": "<pubkey>"}`
+#[no_mangle]
+pub extern "C" fn send_idl_instruction_resolved(
+    client: *mut SolClient,
+    idl: *const SolIdl,
+    program_id: *const c_char,
+    instruction_name: *const c_char,
+    args_json: *const c_char,
+    accounts_json: *const c_char,
+    signers: *const *mut SolKeyPair, // List of signers, the first one pays the fees
+    signer_count: usize,
+) -> *mut c_char {
+    let client = unsafe {
+        assert!(!client.is_null());
+        &*client
+    };
+    let idl = unsafe {
+        assert!(!idl.is_null());
+        &*idl
+    };
+
+    match resolved_instruction_from_c(
+        client,
+        &idl.idl,
+        program_id,
+        instruction_name,
+        args_json,
+        accounts_json,
+    ) {
+        Ok(instruction) => result_text(send_signed_by(client, instruction, signers, signer_count)),
+        Err(err) => {
+            eprintln!("{}", err);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+// Same as `send_idl_instruction_resolved`, returning the full result so a custom
+// program error can be read as `error_code` and `error_name`
+#[no_mangle]
+pub extern "C" fn send_idl_instruction_resolved_with_result(
+    client: *mut SolClient,
+    idl: *const SolIdl,
+    program_id: *const c_char,
+    instruction_name: *const c_char,
+    args_json: *const c_char,
+    accounts_json: *const c_char,
+    signers: *const *mut SolKeyPair, // List of signers, the first one pays the fees
+    signer_count: usize,
+) -> *mut SolTransactionResult {
+    let client = unsafe {
+        assert!(!client.is_null());
+        &*client
+    };
+    let idl = unsafe {
+        assert!(!idl.is_null());
+        &*idl
+    };
+
+    match resolved_instruction_from_c(
+        client,
+        &idl.idl,
+        program_id,
+        instruction_name,
+        args_json,
+        accounts_json,
+    ) {
+        Ok(instruction) => send_signed_by(client, instruction, signers, signer_count),
+        Err(err) => SolTransactionResult::failed(err),
+    }
+    .into_raw()
+}
+
+fn resolved_instruction_from_c(
+    client: &SolClient,
+    idl: &Idl,
+    program_id: *const c_char,
+    instruction_name: *const c_char,
+    args_json: *const c_char,
+    accounts_json: *const c_char,
+) -> Result<Instruction, String> {
+    let (program_id, args, metas) = resolve_from_c(
+        Some(client),
+        idl,
+        program_id,
+        instruction_name,
+        args_json,
+        accounts_json,
+    )?;
+    let name = unsafe { CStr::from_ptr(instruction_name) }.to_string_lossy();
+    // The discriminator is already part of `data`
+    let data = encode_instruction_data(idl, &name, &args)?;
+    Ok(Instruction::new_with_bytes(program_id, &data, metas))
+}
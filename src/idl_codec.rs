@@ -0,0 +1,1000 @@
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use serde_json::{Map, Value};
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signer;
+use std::collections::HashMap;
+use std::ffi::{CStr, CString};
+use std::os::raw::c_char;
+use std::str::FromStr;
+
+use crate::client::SolClient;
+use crate::idl::{
+    program_address_from_c, to_snake_case, Idl, IdlArrayLen, IdlDefinedFields, IdlField,
+    IdlGenericArg, IdlInstruction, IdlSerialization, IdlType, IdlTypeDef, IdlTypeDefTy, SolIdl,
+};
+use crate::smart_contract::{keypairs_from_c, result_text};
+use crate::transaction::{
+    account_metas_from_c, send_instructions, SolAccountMeta, SolTransactionResult,
+};
+use crate::wallet::{SolKeyPair, SolPublicKey};
+
+// Generic parameters of the type definition being processed
+type Generics = HashMap<String, IdlGenericArg>;
+
+// A JSON object entry by name, also matching camelCase keys to snake_case names
+pub fn object_field<'a>(object: &'a Map<String, Value>, name: &str) -> Option<&'a Value> {
+    object.get(name).or_else(|| {
+        let name = to_snake_case(name);
+        object
+            .iter()
+            .find(|(key, _)| to_snake_case(key) == name)
+            .map(|(_, value)| value)
+    })
+}
+
+// Zero-copy types are laid out in memory rather than in Borsh, with padding
+// set by `repr`; refuse them instead of producing wrong data
+fn borsh_only(type_def: &IdlTypeDef) -> Result<(), String> {
+    match type_def.serialization {
+        IdlSerialization::Borsh => Ok(()),
+        _ => Err(format!("{}: zero-copy layout unsupported", type_def.name)),
+    }
+}
+
+fn resolve_generic(generics: &Generics, name: &str) -> Result<IdlGenericArg, String> {
+    generics
+        .get(name)
+        .cloned()
+        .ok_or_else(|| format!("Unbound IDL generic {}", name))
+}
+
+fn array_len(len: &IdlArrayLen, generics: &Generics) -> Result<usize, String> {
+    match len {
+        IdlArrayLen::Value(len) => Ok(*len),
+        IdlArrayLen::Generic(name) => match resolve_generic(generics, name)? {
+            IdlGenericArg::Const(value) => value
+                .parse()
+                .map_err(|_| format!("Invalid array length {} for {}", value, name)),
+            IdlGenericArg::Type(_) => Err(format!("Generic {} is not a length", name)),
+        },
+    }
+}
+
+// Bind a type definition's generic parameters to the arguments of a use site
+fn bind_generics(
+    names: &[String],
+    args: &[IdlGenericArg],
+    outer: &Generics,
+) -> Result<Generics, String> {
+    names
+        .iter()
+        .zip(args)
+        .map(|(name, arg)| {
+            let arg = match arg {
+                IdlGenericArg::Type(IdlType::Generic(generic)) => resolve_generic(outer, generic)?,
+                IdlGenericArg::Const(value) => match outer.get(value) {
+                    Some(bound) => bound.clone(),
+                    None => arg.clone(),
+                },
+                _ => arg.clone(),
+            };
+            Ok((name.clone(), arg))
+        })
+        .collect()
+}
+
+// ==================== Integer Parsing ==================== //
+
+// Accepts JSON numbers and decimal strings, which carry values beyond 2^53
+fn integer_text(value: &Value) -> Result<String, String> {
+    match value {
+        Value::Number(number) => Ok(number.to_string()),
+        Value::String(text) => Ok(text.trim().to_string()),
+        _ => Err(format!("Expected an integer, got {}", value)),
+    }
+}
+
+fn unsigned(value: &Value, bits: u32) -> Result<u128, String> {
+    let text = integer_text(value)?;
+    let number: u128 = text
+        .parse()
+        .map_err(|_| format!("Invalid u{} value {}", bits, text))?;
+    if bits < 128 && number >> bits != 0 {
+        return Err(format!("Value {} does not fit in u{}", text, bits));
+    }
+    Ok(number)
+}
+
+fn signed(value: &Value, bits: u32) -> Result<i128, String> {
+    let text = integer_text(value)?;
+    let number: i128 = text
+        .parse()
+        .map_err(|_| format!("Invalid i{} value {}", bits, text))?;
+    if bits < 128 {
+        let limit = 1i128 << (bits - 1);
+        if number < -limit || number >= limit {
+            return Err(format!("Value {} does not fit in i{}", text, bits));
+        }
+    }
+    Ok(number)
+}
+
+// 256-bit integers as 32 little-endian bytes, from a decimal string
+fn wide_integer(value: &Value, is_signed: bool) -> Result<[u8; 32], String> {
+    let text = integer_text(value)?;
+    let (negative, digits) = match text.strip_prefix('-') {
+        Some(digits) if is_signed => (true, digits),
+        _ => (false, text.as_str()),
+    };
+    if digits.is_empty() || !digits.bytes().all(|digit| digit.is_ascii_digit()) {
+        return Err(format!("Invalid 256-bit integer {}", text));
+    }
+
+    let mut bytes = [0u8; 32];
+    for digit in digits.bytes() {
+        let mut carry = u32::from(digit - b'0');
+        for byte in bytes.iter_mut() {
+            let product = u32::from(*byte) * 10 + carry;
+            *byte = product as u8;
+            carry = product >> 8;
+        }
+        if carry != 0 {
+            return Err(format!("Value {} does not fit in 256 bits", text));
+        }
+    }
+    if is_signed
+        && bytes[31] & 0x80 != 0
+        && !(negative && bytes[31] == 0x80 && bytes[..31].iter().all(|byte| *byte == 0))
+    {
+        return Err(format!("Value {} does not fit in i256", text));
+    }
+    if negative {
+        // Two's complement
+        let mut carry = 1u16;
+        for byte in bytes.iter_mut() {
+            let sum = u16::from(!*byte) + carry;
+            *byte = sum as u8;
+            carry = sum >> 8;
+        }
+    }
+    Ok(bytes)
+}
+
+fn float(value: &Value) -> Result<f64, String> {
+    match value {
+        Value::Number(number) => number.as_f64(),
+        Value::String(text) => text.trim().parse().ok(),
+        _ => None,
+    }
+    .ok_or_else(|| format!("Expected a number, got {}", value))
+}
+
+// ==================== Encoding ==================== //
+
+// Borsh-serialize JSON values according to IDL types
+pub struct Encoder<'a> {
+    idl: &'a Idl,
+    data: Vec<u8>,
+}
+
+impl<'a> Encoder<'a> {
+    pub fn new(idl: &'a Idl) -> Self {
+        Encoder {
+            idl,
+            data: Vec::new(),
+        }
+    }
+
+    pub fn finish(self) -> Vec<u8> {
+        self.data
+    }
+
+    fn length_prefix(&mut self, len: usize) -> Result<(), String> {
+        let len = u32::try_from(len).map_err(|_| "Length does not fit in u32".to_string())?;
+        self.data.extend_from_slice(&len.to_le_bytes());
+        Ok(())
+    }
+
+    // Instruction arguments, from an object keyed by name or a positional array
+    pub fn encode_args(&mut self, args: &[IdlField], value: &Value) -> Result<(), String> {
+        match value {
+            Value::Array(values) => {
+                if values.len() != args.len() {
+                    return Err(format!(
+                        "Expected {} arguments, got {}",
+                        args.len(),
+                        values.len()
+                    ));
+                }
+                for (arg, value) in args.iter().zip(values) {
+                    self.encode(&arg.ty, value, &Generics::new())
+                        .map_err(|err| format!("Argument {}: {}", arg.name, err))?;
+                }
+                Ok(())
+            }
+            Value::Null if args.is_empty() => Ok(()),
+            _ => self.encode_named_fields(args, value, &Generics::new()),
+        }
+    }
+
+    fn encode_named_fields(
+        &mut self,
+        fields: &[IdlField],
+        value: &Value,
+        generics: &Generics,
+    ) -> Result<(), String> {
+        let object = value
+            .as_object()
+            .ok_or_else(|| format!("Expected an object, got {}", value))?;
+        // A misspelled key would otherwise leave an option as None
+        if let Some(key) = object.keys().find(|key| {
+            let key_name = to_snake_case(key);
+            !fields
+                .iter()
+                .any(|field| field.name == **key || to_snake_case(&field.name) == key_name)
+        }) {
+            return Err(format!("Unknown field {}", key));
+        }
+        for field in fields {
+            // A missing field is only allowed for options
+            let value = object_field(object, &field.name).unwrap_or(&Value::Null);
+            self.encode(&field.ty, value, generics)
+                .map_err(|err| format!("Field {}: {}", field.name, err))?;
+        }
+        Ok(())
+    }
+
+    fn encode_defined_fields(
+        &mut self,
+        fields: &IdlDefinedFields,
+        value: &Value,
+        generics: &Generics,
+    ) -> Result<(), String> {
+        match fields {
+            IdlDefinedFields::None => Ok(()),
+            IdlDefinedFields::Named(fields) => self.encode_named_fields(fields, value, generics),
+            IdlDefinedFields::Tuple(types) => {
+                let values = match value {
+                    Value::Array(values) => values.as_slice(),
+                    // A single-field tuple may be given without the array
+                    _ if types.len() == 1 => std::slice::from_ref(value),
+                    _ => return Err(format!("Expected an array, got {}", value)),
+                };
+                if values.len() != types.len() {
+                    return Err(format!(
+                        "Expected {} tuple fields, got {}",
+                        types.len(),
+                        values.len()
+                    ));
+                }
+                for (ty, value) in types.iter().zip(values) {
+                    self.encode(ty, value, generics)?;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    pub fn encode(
+        &mut self,
+        ty: &IdlType,
+        value: &Value,
+        generics: &Generics,
+    ) -> Result<(), String> {
+        match ty {
+            IdlType::Bool => {
+                let value = value
+                    .as_bool()
+                    .ok_or_else(|| format!("Expected a bool, got {}", value))?;
+                self.data.push(u8::from(value));
+            }
+            IdlType::U8 => self.data.push(unsigned(value, 8)? as u8),
+            IdlType::U16 => self
+                .data
+                .extend_from_slice(&(unsigned(value, 16)? as u16).to_le_bytes()),
+            IdlType::U32 => self
+                .data
+                .extend_from_slice(&(unsigned(value, 32)? as u32).to_le_bytes()),
+            IdlType::U64 => self
+                .data
+                .extend_from_slice(&(unsigned(value, 64)? as u64).to_le_bytes()),
+            IdlType::U128 => self
+                .data
+                .extend_from_slice(&unsigned(value, 128)?.to_le_bytes()),
+            IdlType::I8 => self.data.push(signed(value, 8)? as i8 as u8),
+            IdlType::I16 => self
+                .data
+                .extend_from_slice(&(signed(value, 16)? as i16).to_le_bytes()),
+            IdlType::I32 => self
+                .data
+                .extend_from_slice(&(signed(value, 32)? as i32).to_le_bytes()),
+            IdlType::I64 => self
+                .data
+                .extend_from_slice(&(signed(value, 64)? as i64).to_le_bytes()),
+            IdlType::I128 => self
+                .data
+                .extend_from_slice(&signed(value, 128)?.to_le_bytes()),
+            IdlType::U256 => self.data.extend_from_slice(&wide_integer(value, false)?),
+            IdlType::I256 => self.data.extend_from_slice(&wide_integer(value, true)?),
+            IdlType::F32 => self
+                .data
+                .extend_from_slice(&(float(value)? as f32).to_le_bytes()),
+            IdlType::F64 => self.data.extend_from_slice(&float(value)?.to_le_bytes()),
+            IdlType::String => {
+                let text = value
+                    .as_str()
+                    .ok_or_else(|| format!("Expected a string, got {}", value))?;
+                self.length_prefix(text.len())?;
+                self.data.extend_from_slice(text.as_bytes());
+            }
+            IdlType::Bytes => {
+                // A byte array or a base64 string
+                let bytes = match value {
+                    Value::String(text) => BASE64
+                        .decode(text)
+                        .map_err(|err| format!("Invalid base64 bytes: {}", err))?,
+                    _ => value
+                        .as_array()
+                        .ok_or_else(|| format!("Expected bytes, got {}", value))?
+                        .iter()
+                        .map(|byte| unsigned(byte, 8).map(|byte| byte as u8))
+                        .collect::<Result<_, _>>()?,
+                };
+                self.length_prefix(bytes.len())?;
+                self.data.extend_from_slice(&bytes);
+            }
+            IdlType::Pubkey => {
+                let text = value
+                    .as_str()
+                    .ok_or_else(|| format!("Expected a base58 address, got {}", value))?;
+                let pubkey = Pubkey::from_str(text)
+                    .map_err(|err| format!("Invalid address {}: {}", text, err))?;
+                self.data.extend_from_slice(pubkey.as_ref());
+            }
+            IdlType::Option(inner) => {
+                if value.is_null() {
+                    self.data.push(0);
+                } else {
+                    self.data.push(1);
+                    self.encode(inner, value, generics)?;
+                }
+            }
+            IdlType::COption(inner) => {
+                if value.is_null() {
+                    self.data.extend_from_slice(&0u32.to_le_bytes());
+                } else {
+                    self.data.extend_from_slice(&1u32.to_le_bytes());
+                    self.encode(inner, value, generics)?;
+                }
+            }
+            IdlType::Vec(inner) => {
+                let values = value
+                    .as_array()
+                    .ok_or_else(|| format!("Expected an array, got {}", value))?;
+                self.length_prefix(values.len())?;
+                for value in values {
+                    self.encode(inner, value, generics)?;
+                }
+            }
+            IdlType::Array(inner, len) => {
+                let len = array_len(len, generics)?;
+                let values = value
+                    .as_array()
+                    .ok_or_else(|| format!("Expected an array, got {}", value))?;
+                if values.len() != len {
+                    return Err(format!(
+                        "Expected {} array elements, got {}",
+                        len,
+                        values.len()
+                    ));
+                }
+                for value in values {
+                    self.encode(inner, value, generics)?;
+                }
+            }
+            IdlType::Generic(name) => match resolve_generic(generics, name)? {
+                IdlGenericArg::Type(ty) => self.encode(&ty, value, generics)?,
+                IdlGenericArg::Const(_) => {
+                    return Err(format!("Generic {} is not a type", name));
+                }
+            },
+            IdlType::Defined {
+                name,
+                generics: args,
+            } => {
+                let type_def = self
+                    .idl
+                    .type_def(name)
+                    .ok_or_else(|| format!("Type {} not found in IDL", name))?;
+                borsh_only(type_def)?;
+                let generics = bind_generics(&type_def.generics, args, generics)?;
+                match &type_def.ty {
+                    IdlTypeDefTy::Struct { fields } => {
+                        self.encode_defined_fields(fields, value, &generics)?
+                    }
+                    IdlTypeDefTy::Type { alias } => self.encode(alias, value, &generics)?,
+                    IdlTypeDefTy::Enum { variants } => {
+                        // "Variant" for unit variants, {"Variant": fields} otherwise
+                        let (variant_name, fields) = match value {
+                            Value::String(variant) => (variant.as_str(), &Value::Null),
+                            Value::Object(object) if object.len() == 1 => {
+                                let (variant, fields) = object.iter().next().unwrap();
+                                (variant.as_str(), fields)
+                            }
+                            _ => return Err(format!("Expected a {} variant, got {}", name, value)),
+                        };
+                        let index = variants
+                            .iter()
+                            .position(|variant| variant.name == variant_name)
+                            .or_else(|| {
+                                let snake = to_snake_case(variant_name);
+                                variants
+                                    .iter()
+                                    .position(|variant| to_snake_case(&variant.name) == snake)
+                            })
+                            .ok_or_else(|| format!("Unknown {} variant {}", name, variant_name))?;
+                        self.data.push(
+                            u8::try_from(index)
+                                .map_err(|_| format!("Too many variants in {}", name))?,
+                        );
+                        self.encode_defined_fields(&variants[index].fields, fields, &generics)?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+// Discriminator followed by the Borsh-encoded arguments
+pub fn encode_instruction_data(
+    idl: &Idl,
+    instruction_name: &str,
+    args: &Value,
+) -> Result<Vec<u8>, String> {
+    let instruction = idl
+        .instruction(instruction_name)
+        .ok_or_else(|| format!("Instruction {} not found in IDL", instruction_name))?;
+    let mut encoder = Encoder::new(idl);
+    encoder.data.extend_from_slice(&instruction.discriminator);
+    encoder.encode_args(&instruction.args, args)?;
+    Ok(encoder.finish())
+}
+
+// ==================== Decoding ==================== //
+
+// Vectors of zero-sized elements are not bounded by the data, so cap their length
+const MAX_ZERO_SIZED_ELEMENTS: usize = 65_536;
+
+// Turn Borsh data back into JSON in the form `Encoder` accepts. Integers up to
+// 64 bits become JSON numbers, wider ones decimal strings and bytes base64.
+pub struct Decoder<'a> {
+    idl: &'a Idl,
+    data: &'a [u8],
+    offset: usize,
+}
+
+fn wide_integer_text(bytes: &[u8], is_signed: bool) -> String {
+    let negative = is_signed && bytes[31] & 0x80 != 0;
+    let mut magnitude = bytes.to_vec();
+    if negative {
+        let mut carry = 1u16;
+        for byte in magnitude.iter_mut() {
+            let sum = u16::from(!*byte) + carry;
+            *byte = sum as u8;
+            carry = sum >> 8;
+        }
+    }
+
+    // Repeated division by 10, most significant byte first
+    let mut digits = Vec::new();
+    while magnitude.iter().any(|byte| *byte != 0) {
+        let mut remainder = 0u32;
+        for byte in magnitude.iter_mut().rev() {
+            let value = (remainder << 8) | u32::from(*byte);
+            *byte = (value / 10) as u8;
+            remainder = value % 10;
+        }
+        digits.push(b'0' + remainder as u8);
+    }
+    if digits.is_empty() {
+        digits.push(b'0');
+    }
+    if negative {
+        digits.push(b'-');
+    }
+    digits.reverse();
+    String::from_utf8(digits).unwrap()
+}
+
+fn float_value(value: f64) -> Value {
+    serde_json::Number::from_f64(value).map_or(Value::Null, Value::Number)
+}
+
+impl<'a> Decoder<'a> {
+    pub fn new(idl: &'a Idl, data: &'a [u8]) -> Self {
+        Decoder {
+            idl,
+            data,
+            offset: 0,
+        }
+    }
+
+    pub fn remaining(&self) -> usize {
+        self.data.len() - self.offset
+    }
+
+    fn read(&mut self, len: usize) -> Result<&'a [u8], String> {
+        if self.remaining() < len {
+            return Err(format!(
+                "Data ended at byte {} while reading {} more bytes",
+                self.data.len(),
+                len
+            ));
+        }
+        let bytes = &self.data[self.offset..self.offset + len];
+        self.offset += len;
+        Ok(bytes)
+    }
+
+    fn read_array<const N: usize>(&mut self) -> Result<[u8; N], String> {
+        Ok(self.read(N)?.try_into().unwrap())
+    }
+
+    fn read_len(&mut self) -> Result<usize, String> {
+        Ok(u32::from_le_bytes(self.read_array()?) as usize)
+    }
+
+    // Whether values of `ty` take no bytes at all, e.g. a struct without fields
+    fn is_zero_sized(&self, ty: &IdlType, generics: &Generics) -> Result<bool, String> {
+        Ok(match ty {
+            IdlType::Array(inner, len) => {
+                array_len(len, generics)? == 0 || self.is_zero_sized(inner, generics)?
+            }
+            IdlType::Generic(name) => match resolve_generic(generics, name)? {
+                IdlGenericArg::Type(ty) => self.is_zero_sized(&ty, generics)?,
+                IdlGenericArg::Const(_) => return Err(format!("Generic {} is not a type", name)),
+            },
+            IdlType::Defined {
+                name,
+                generics: args,
+            } => {
+                let type_def = self
+                    .idl
+                    .type_def(name)
+                    .ok_or_else(|| format!("Type {} not found in IDL", name))?;
+                let generics = bind_generics(&type_def.generics, args, generics)?;
+                let field_types: Vec<&IdlType> = match &type_def.ty {
+                    IdlTypeDefTy::Struct {
+                        fields: IdlDefinedFields::None,
+                    } => Vec::new(),
+                    IdlTypeDefTy::Struct {
+                        fields: IdlDefinedFields::Named(fields),
+                    } => fields.iter().map(|field| &field.ty).collect(),
+                    IdlTypeDefTy::Struct {
+                        fields: IdlDefinedFields::Tuple(types),
+                    } => types.iter().collect(),
+                    IdlTypeDefTy::Type { alias } => vec![alias],
+                    // The variant tag takes a byte
+                    IdlTypeDefTy::Enum { .. } => return Ok(false),
+                };
+                for ty in field_types {
+                    if !self.is_zero_sized(ty, &generics)? {
+                        return Ok(false);
+                    }
+                }
+                true
+            }
+            _ => false,
+        })
+    }
+
+    pub fn decode_fields(&mut self, fields: &[IdlField]) -> Result<Value, String> {
+        self.decode_named_fields(fields, &Generics::new())
+    }
+
+    fn decode_named_fields(
+        &mut self,
+        fields: &[IdlField],
+        generics: &Generics,
+    ) -> Result<Value, String> {
+        let mut object = Map::new();
+        for field in fields {
+            let value = self
+                .decode(&field.ty, generics)
+                .map_err(|err| format!("Field {}: {}", field.name, err))?;
+            object.insert(field.name.clone(), value);
+        }
+        Ok(Value::Object(object))
+    }
+
+    fn decode_defined_fields(
+        &mut self,
+        fields: &IdlDefinedFields,
+        generics: &Generics,
+    ) -> Result<Option<Value>, String> {
+        match fields {
+            IdlDefinedFields::None => Ok(None),
+            IdlDefinedFields::Named(fields) => self.decode_named_fields(fields, generics).map(Some),
+            IdlDefinedFields::Tuple(types) => types
+                .iter()
+                .map(|ty| self.decode(ty, generics))
+                .collect::<Result<Vec<_>, _>>()
+                .map(|values| Some(Value::Array(values))),
+        }
+    }
+
+    pub fn decode(&mut self, ty: &IdlType, generics: &Generics) -> Result<Value, String> {
+        Ok(match ty {
+            IdlType::Bool => match self.read(1)?[0] {
+                0 => Value::Bool(false),
+                1 => Value::Bool(true),
+                byte => return Err(format!("Invalid bool {}", byte)),
+            },
+            IdlType::U8 => Value::from(self.read(1)?[0]),
+            IdlType::U16 => Value::from(u16::from_le_bytes(self.read_array()?)),
+            IdlType::U32 => Value::from(u32::from_le_bytes(self.read_array()?)),
+            IdlType::U64 => Value::from(u64::from_le_bytes(self.read_array()?)),
+            IdlType::U128 => Value::from(u128::from_le_bytes(self.read_array()?).to_string()),
+            IdlType::I8 => Value::from(self.read(1)?[0] as i8),
+            IdlType::I16 => Value::from(i16::from_le_bytes(self.read_array()?)),
+            IdlType::I32 => Value::from(i32::from_le_bytes(self.read_array()?)),
+            IdlType::I64 => Value::from(i64::from_le_bytes(self.read_array()?)),
+            IdlType::I128 => Value::from(i128::from_le_bytes(self.read_array()?).to_string()),
+            IdlType::U256 => Value::from(wide_integer_text(self.read(32)?, false)),
+            IdlType::I256 => Value::from(wide_integer_text(self.read(32)?, true)),
+            IdlType::F32 => float_value(f64::from(f32::from_le_bytes(self.read_array()?))),
+            IdlType::F64 => float_value(f64::from_le_bytes(self.read_array()?)),
+            IdlType::String => {
+                let len = self.read_len()?;
+                let bytes = self.read(len)?;
+                Value::from(
+                    std::str::from_utf8(bytes).map_err(|err| format!("Invalid string: {}", err))?,
+                )
+            }
+            IdlType::Bytes => {
+                let len = self.read_len()?;
+                Value::from(BASE64.encode(self.read(len)?))
+            }
+            IdlType::Pubkey => Value::from(Pubkey::new_from_array(self.read_array()?).to_string()),
+            IdlType::Option(inner) => match self.read(1)?[0] {
+                0 => Value::Null,
+                1 => self.decode(inner, generics)?,
+                tag => return Err(format!("Invalid option tag {}", tag)),
+            },
+            IdlType::COption(inner) => match u32::from_le_bytes(self.read_array()?) {
+                0 => Value::Null,
+                1 => self.decode(inner, generics)?,
+                tag => return Err(format!("Invalid option tag {}", tag)),
+            },
+            IdlType::Vec(inner) => {
+                let len = self.read_len()?;
+                // Elements that take at least one byte cannot outnumber the
+                // remaining bytes; zero-sized ones are capped instead
+                let max_len = if self.is_zero_sized(inner, generics)? {
+                    MAX_ZERO_SIZED_ELEMENTS
+                } else {
+                    self.remaining()
+                };
+                if len > max_len {
+                    return Err(format!("Vector length {} exceeds the data", len));
+                }
+                Value::Array(
+                    (0..len)
+                        .map(|_| self.decode(inner, generics))
+                        .collect::<Result<_, _>>()?,
+                )
+            }
+            IdlType::Array(inner, len) => {
+                let len = array_len(len, generics)?;
+                Value::Array(
+                    (0..len)
+                        .map(|_| self.decode(inner, generics))
+                        .collect::<Result<_, _>>()?,
+                )
+            }
+            IdlType::Generic(name) => match resolve_generic(generics, name)? {
+                IdlGenericArg::Type(ty) => self.decode(&ty, generics)?,
+                IdlGenericArg::Const(_) => return Err(format!("Generic {} is not a type", name)),
+            },
+            IdlType::Defined {
+                name,
+                generics: args,
+            } => {
+                let type_def = self
+                    .idl
+                    .type_def(name)
+                    .ok_or_else(|| format!("Type {} not found in IDL", name))?;
+                borsh_only(type_def)?;
+                let generics = bind_generics(&type_def.generics, args, generics)?;
+                match &type_def.ty {
+                    IdlTypeDefTy::Struct { fields } => self
+                        .decode_defined_fields(fields, &generics)?
+                        .unwrap_or_else(|| Value::Object(Map::new())),
+                    IdlTypeDefTy::Type { alias } => self.decode(alias, &generics)?,
+                    IdlTypeDefTy::Enum { variants } => {
+                        let tag = self.read(1)?[0];
+                        let variant = variants
+                            .get(usize::from(tag))
+                            .ok_or_else(|| format!("Invalid {} variant {}", name, tag))?;
+                        match self.decode_defined_fields(&variant.fields, &generics)? {
+                            None => Value::from(variant.name.clone()),
+                            Some(fields) => {
+                                let mut object = Map::new();
+                                object.insert(variant.name.clone(), fields);
+                                Value::Object(object)
+                            }
+                        }
+                    }
+                }
+            }
+        })
+    }
+}
+
+// Check the account discriminator and decode the account's type. Trailing
+// bytes are allowed since accounts are often allocated with spare space.
+pub fn decode_account_data(idl: &Idl, account_type: &str, data: &[u8]) -> Result<Value, String> {
+    let account = idl
+        .account(account_type)
+        .ok_or_else(|| format!("Account type {} not found in IDL", account_type))?;
+    if !data.starts_with(&account.discriminator) {
+        return Err(format!("Account data is not a {}", account.name));
+    }
+
+    let ty = IdlType::Defined {
+        name: account.name.clone(),
+        generics: Vec::new(),
+    };
+    Decoder::new(idl, &data[account.discriminator.len()..]).decode(&ty, &Generics::new())
+}
+
+// Match the discriminator of one of the IDL's instructions and decode its arguments
+pub fn decode_instruction_data<'a>(
+    idl: &'a Idl,
+    data: &[u8],
+) -> Option<(&'a IdlInstruction, Result<Value, String>)> {
+    let instruction = idl
+        .instructions
+        .iter()
+        .find(|instruction| data.starts_with(&instruction.discriminator))?;
+    let mut decoder = Decoder::new(idl, &data[instruction.discriminator.len()..]);
+    Some((instruction, decoder.decode_fields(&instruction.args)))
+}
+
+fn json_to_c(result: Result<Value, String>) -> *mut c_char {
+    match result {
+        Ok(value) => {
+            CString::new(value.to_string()).map_or(std::ptr::null_mut(), CString::into_raw)
+        }
+        Err(err) => {
+            eprintln!("{}", err);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+// NULL or empty JSON means no arguments
+pub fn args_from_c(args_json: *const c_char) -> Result<Value, String> {
+    if args_json.is_null() {
+        return Ok(Value::Null);
+    }
+    let args_json = unsafe { CStr::from_ptr(args_json) }
+        .to_str()
+        .map_err(|_| "Invalid arguments string".to_string())?;
+    if args_json.trim().is_empty() {
+        return Ok(Value::Null);
+    }
+    serde_json::from_str(args_json).map_err(|err| format!("Invalid arguments JSON: {}", err))
+}
+
+// ==================== IDL Instruction Functions ==================== //
+
+// Write the discriminator and Borsh-encoded `args_json` for an instruction.
+// Returns the data length or 0 on error. Nothing is written when the buffer
+// is NULL or too small, so the length can be queried first.
+#[no_mangle]
+pub extern "C" fn idl_encode_instruction_data(
+    idl: *const SolIdl,
+    instruction_name: *const c_char,
+    args_json: *const c_char,
+    data_out: *mut u8,
+    data_out_len: usize,
+) -> usize {
+    let idl = unsafe {
+        assert!(!idl.is_null());
+        &*idl
+    };
+    let instruction_name = match unsafe { CStr::from_ptr(instruction_name) }.to_str() {
+        Ok(str) => str,
+        Err(_) => return 0,
+    };
+
+    let data = match args_from_c(args_json)
+        .and_then(|args| encode_instruction_data(&idl.idl, instruction_name, &args))
+    {
+        Ok(data) => data,
+        Err(err) => {
+            eprintln!("{}", err);
+            return 0;
+        }
+    };
+    if data_out.is_null() || data_out_len < data.len() {
+        return data.len();
+    }
+    unsafe { std::ptr::copy_nonoverlapping(data.as_ptr(), data_out, data.len()) };
+    data.len()
+}
+
+// Call an instruction by name with JSON arguments. `program_id` may be NULL to
+// use the address recorded in the IDL.
+#[no_mangle]
+pub extern "C" fn send_idl_instruction(
+    client: *mut SolClient,
+    idl: *const SolIdl,
+    program_id: *const c_char,
+    instruction_name: *const c_char,
+    args_json: *const c_char,
+    accounts: *const SolAccountMeta,
+    account_count: usize,
+    signers: *const *mut SolKeyPair, // List of signers, the first one pays the fees
+    signer_count: usize,
+) -> *mut c_char {
+    let client = unsafe {
+        assert!(!client.is_null());
+        &*client
+    };
+    let idl = unsafe {
+        assert!(!idl.is_null());
+        &*idl
+    };
+
+    match idl_instruction_from_c(
+        &idl.idl,
+        program_id,
+        instruction_name,
+        args_json,
+        accounts,
+        account_count,
+    ) {
+        Ok(instruction) => result_text(send_signed_by(client, instruction, signers, signer_count)),
+        Err(err) => {
+            eprintln!("{}", err);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+// Same as `send_idl_instruction`, returning the full result so a custom program
+// error can be read as `error_code` and `error_name`
+#[no_mangle]
+pub extern "C" fn send_idl_instruction_with_result(
+    client: *mut SolClient,
+    idl: *const SolIdl,
+    program_id: *const c_char,
+    instruction_name: *const c_char,
+    args_json: *const c_char,
+    accounts: *const SolAccountMeta,
+    account_count: usize,
+    signers: *const *mut SolKeyPair, // List of signers, the first one pays the fees
+    signer_count: usize,
+) -> *mut SolTransactionResult {
+    let client = unsafe {
+        assert!(!client.is_null());
+        &*client
+    };
+    let idl = unsafe {
+        assert!(!idl.is_null());
+        &*idl
+    };
+
+    match idl_instruction_from_c(
+        &idl.idl,
+        program_id,
+        instruction_name,
+        args_json,
+        accounts,
+        account_count,
+    ) {
+        Ok(instruction) => send_signed_by(client, instruction, signers, signer_count),
+        Err(err) => SolTransactionResult::failed(err),
+    }
+    .into_raw()
+}
+
+fn idl_instruction_from_c(
+    idl: &Idl,
+    program_id: *const c_char,
+    instruction_name: *const c_char,
+    args_json: *const c_char,
+    accounts: *const SolAccountMeta,
+    account_count: usize,
+) -> Result<Instruction, String> {
+    let program_id = program_address_from_c(idl, program_id)?;
+    let instruction_name = unsafe { CStr::from_ptr(instruction_name) }
+        .to_str()
+        .map_err(|_| "Invalid instruction name string".to_string())?;
+    // The discriminator is already part of `data`
+    let data = encode_instruction_data(idl, instruction_name, &args_from_c(args_json)?)?;
+    let accounts = account_metas_from_c(accounts, account_count);
+    Ok(Instruction::new_with_bytes(program_id, &data, accounts))
+}
+
+// Send one instruction signed by keypairs from C; the first one pays the fees
+pub fn send_signed_by(
+    client: &SolClient,
+    instruction: Instruction,
+    signers: *const *mut SolKeyPair,
+    signer_count: usize,
+) -> SolTransactionResult {
+    let signer_keypairs = keypairs_from_c(signers, signer_count);
+    let payer = match signer_keypairs.first() {
+        Some(payer) => payer.pubkey(),
+        None => return SolTransactionResult::failed("At least one signer (payer) required"),
+    };
+    let signer_refs: Vec<&dyn Signer> = signer_keypairs.iter().map(|s| s as &dyn Signer).collect();
+    send_instructions(client, &[instruction], &payer, &signer_refs)
+}
+
+// ==================== IDL Account Functions ==================== //
+
+// Decode raw account data of an IDL account type to JSON, e.g. `{"count":5}`.
+// Returns NULL if the discriminator does not match or the data is malformed.
+#[no_mangle]
+pub extern "C" fn idl_decode_account_data(
+    idl: *const SolIdl,
+    account_type: *const c_char,
+    data: *const u8,
+    data_len: usize,
+) -> *mut c_char {
+    let idl = unsafe {
+        assert!(!idl.is_null());
+        &*idl
+    };
+    let account_type = match unsafe { CStr::from_ptr(account_type) }.to_str() {
+        Ok(str) => str,
+        Err(_) => return std::ptr::null_mut(),
+    };
+    if data.is_null() {
+        return std::ptr::null_mut();
+    }
+    let data = unsafe { std::slice::from_raw_parts(data, data_len) };
+
+    json_to_c(decode_account_data(&idl.idl, account_type, data))
+}
+
+// Fetch an account and decode it as `account_type`
+#[no_mangle]
+pub extern "C" fn fetch_account_decoded(
+    client: *mut SolClient,
+    idl: *const SolIdl,
+    account_type: *const c_char,
+    pubkey: *const SolPublicKey,
+) -> *mut c_char {
+    let client = unsafe {
+        assert!(!client.is_null());
+        &*client
+    };
+    let idl = unsafe {
+        assert!(!idl.is_null());
+        &*idl
+    };
+    let account_type = match unsafe { CStr::from_ptr(account_type) }.to_str() {
+        Ok(str) => str,
+        Err(_) => return std::ptr::null_mut(),
+    };
+    let pubkey = unsafe {
+        assert!(!pubkey.is_null());
+        &*pubkey
+    }
+    .to_pubkey();
+
+    json_to_c(
+        client
+            .rpc_client
+            .get_account(&pubkey)
+            .map_err(|err| format!("Failed to fetch account {}: {:?}", pubkey, err))
+            .and_then(|account| decode_account_data(&idl.idl, account_type, &account.data)),
+    )
+}
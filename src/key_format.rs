@@ -0,0 +1,216 @@
+use solana_sdk::bs58;
+use solana_sdk::signature::{keypair_from_seed, Keypair};
+use std::ffi::{CStr, CString};
+use std::os::raw::c_char;
+use zeroize::Zeroizing;
+
+use crate::wallet::{keypair_from_secret_bytes, save_wallet_to_file, SolKeyPair};
+
+// ==================== Helpers ==================== //
+
+fn into_wallet(result: Result<Keypair, String>) -> *mut SolKeyPair {
+    match result {
+        Ok(keypair) => Box::into_raw(Box::new(SolKeyPair::from_existing(keypair))),
+        Err(err) => {
+            eprintln!("{}", err);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+// 32 bytes are an ed25519 seed, 64 bytes a full secret key (seed + public key)
+fn keypair_from_key_bytes(bytes: &[u8]) -> Result<Keypair, String> {
+    match bytes.len() {
+        32 => keypair_from_seed(bytes).map_err(|err| format!("Invalid seed: {}", err)),
+        64 => keypair_from_secret_bytes(bytes),
+        len => Err(format!(
+            "Invalid key length: {} bytes (expected 32 or 64)",
+            len
+        )),
+    }
+}
+
+fn decode_hex(value: &str) -> Result<Zeroizing<Vec<u8>>, String> {
+    let value = value.trim();
+    let value = value.strip_prefix("0x").unwrap_or(value);
+    if !value.len().is_multiple_of(2) {
+        return Err("Hex string has an odd number of digits".to_string());
+    }
+
+    let mut bytes = Zeroizing::new(Vec::with_capacity(value.len() / 2));
+    for pair in value.as_bytes().chunks(2) {
+        let digits = std::str::from_utf8(pair).map_err(|_| "Invalid hex string".to_string())?;
+        let byte = u8::from_str_radix(digits, 16).map_err(|_| "Invalid hex string".to_string())?;
+        bytes.push(byte);
+    }
+    Ok(bytes)
+}
+
+fn encode_hex(bytes: &[u8]) -> Zeroizing<String> {
+    let mut value = Zeroizing::new(String::with_capacity(bytes.len() * 2));
+    for byte in bytes {
+        value.push_str(&format!("{:02x}", byte));
+    }
+    value
+}
+
+// Hand a secret string to C; release it with `free_secret_string`
+fn secret_to_c_string(value: &str) -> *mut c_char {
+    match CString::new(value) {
+        Ok(c_str) => c_str.into_raw(),
+        Err(_) => std::ptr::null_mut(),
+    }
+}
+
+// ==================== Import ==================== //
+
+// Import a base58 secret key as exported by Phantom, Solflare or `solana-keygen`
+#[no_mangle]
+pub extern "C" fn wallet_from_base58(secret_key: *const c_char) -> *mut SolKeyPair {
+    let c_str = unsafe {
+        assert!(!secret_key.is_null());
+        CStr::from_ptr(secret_key)
+    };
+    let secret_key = match c_str.to_str() {
+        Ok(str) => str.trim(),
+        Err(_) => return std::ptr::null_mut(),
+    };
+
+    into_wallet(
+        bs58::decode(secret_key)
+            .into_vec()
+            .map(Zeroizing::new)
+            .map_err(|err| format!("Invalid base58 secret key: {}", err))
+            .and_then(|bytes| keypair_from_key_bytes(&bytes)),
+    )
+}
+
+// Import a 32-byte seed or a 64-byte secret key
+#[no_mangle]
+pub extern "C" fn wallet_from_bytes(bytes: *const u8, bytes_len: usize) -> *mut SolKeyPair {
+    if bytes.is_null() {
+        return std::ptr::null_mut();
+    }
+    let bytes = unsafe { std::slice::from_raw_parts(bytes, bytes_len) };
+
+    into_wallet(keypair_from_key_bytes(bytes))
+}
+
+// Import a hex encoded 32-byte seed or 64-byte secret key (an optional 0x prefix is allowed)
+#[no_mangle]
+pub extern "C" fn wallet_from_hex(secret_key: *const c_char) -> *mut SolKeyPair {
+    let c_str = unsafe {
+        assert!(!secret_key.is_null());
+        CStr::from_ptr(secret_key)
+    };
+    let secret_key = match c_str.to_str() {
+        Ok(str) => str,
+        Err(_) => return std::ptr::null_mut(),
+    };
+
+    into_wallet(decode_hex(secret_key).and_then(|bytes| keypair_from_key_bytes(&bytes)))
+}
+
+// Import a Solana CLI JSON byte array held in memory, e.g. "[12,34,...]"
+#[no_mangle]
+pub extern "C" fn wallet_from_json(json: *const c_char) -> *mut SolKeyPair {
+    let c_str = unsafe {
+        assert!(!json.is_null());
+        CStr::from_ptr(json)
+    };
+    let json = match c_str.to_str() {
+        Ok(str) => str,
+        Err(_) => return std::ptr::null_mut(),
+    };
+
+    into_wallet(
+        serde_json::from_str::<Vec<u8>>(json)
+            .map(Zeroizing::new)
+            .map_err(|err| format!("Invalid keypair JSON: {}", err))
+            .and_then(|bytes| keypair_from_secret_bytes(&bytes)),
+    )
+}
+
+// ==================== Export ==================== //
+
+// Export the 64-byte secret key as base58, the format Phantom imports
+#[no_mangle]
+pub extern "C" fn export_wallet_base58(wallet: *mut SolKeyPair) -> *mut c_char {
+    let wallet = unsafe {
+        assert!(!wallet.is_null());
+        &*wallet
+    };
+
+    let value = Zeroizing::new(bs58::encode(&wallet.bytes).into_string());
+    secret_to_c_string(&value)
+}
+
+#[no_mangle]
+pub extern "C" fn export_wallet_hex(wallet: *mut SolKeyPair) -> *mut c_char {
+    let wallet = unsafe {
+        assert!(!wallet.is_null());
+        &*wallet
+    };
+
+    secret_to_c_string(&encode_hex(&wallet.bytes))
+}
+
+// Export the Solana CLI JSON byte array as a string
+#[no_mangle]
+pub extern "C" fn export_wallet_json(wallet: *mut SolKeyPair) -> *mut c_char {
+    let wallet = unsafe {
+        assert!(!wallet.is_null());
+        &*wallet
+    };
+
+    match serde_json::to_string(&wallet.bytes[..]) {
+        Ok(json) => secret_to_c_string(&Zeroizing::new(json)),
+        Err(_) => std::ptr::null_mut(),
+    }
+}
+
+// Copy the 32-byte ed25519 seed into a caller-owned buffer
+#[no_mangle]
+pub extern "C" fn export_wallet_seed(wallet: *mut SolKeyPair, seed_out: *mut u8) -> bool {
+    let wallet = unsafe {
+        assert!(!wallet.is_null());
+        &*wallet
+    };
+    if seed_out.is_null() {
+        return false;
+    }
+
+    unsafe { std::ptr::copy_nonoverlapping(wallet.bytes.as_ptr(), seed_out, 32) };
+    true
+}
+
+// Save a wallet to a file in Solana CLI format
+#[no_mangle]
+pub extern "C" fn export_wallet_to_file(wallet: *mut SolKeyPair, file_path: *const c_char) -> bool {
+    let wallet = unsafe {
+        assert!(!wallet.is_null());
+        &*wallet
+    };
+    let file_path = match unsafe { CStr::from_ptr(file_path) }.to_str() {
+        Ok(str) => str,
+        Err(_) => return false,
+    };
+
+    match save_wallet_to_file(&wallet.to_keypair(), file_path) {
+        Ok(_) => true,
+        Err(err) => {
+            eprintln!("Failed to save wallet {}: {}", file_path, err);
+            false
+        }
+    }
+}
+
+// Zeroize and free a string returned by the `export_wallet_*` functions
+#[no_mangle]
+pub extern "C" fn free_secret_string(value: *mut c_char) {
+    if value.is_null() {
+        return;
+    }
+    let value = unsafe { CString::from_raw(value) };
+    drop(Zeroizing::new(value.into_bytes()));
+}
@@ -0,0 +1,504 @@
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use solana_sdk::signature::{Keypair, Signer};
+use std::ffi::{CStr, CString};
+use std::fs::{self, File};
+use std::io::{BufReader, Write};
+use std::os::raw::c_char;
+use std::path::{Path, PathBuf};
+use zeroize::Zeroizing;
+
+use crate::keystore::{load_encrypted_keypair, save_encrypted_keypair};
+use crate::wallet::{load_wallet, save_wallet_to_file, SolKeyPair, SolPublicKey};
+
+const KEYRING_INDEX_FILE: &str = "keyring.json";
+const KEYRING_VERSION: u32 = 1;
+
+// ==================== Keyring Index ==================== //
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct KeyringEntry {
+    pub label: String,
+    pub pubkey: String,
+    pub created_at: i64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub derivation_path: Option<String>,
+    pub file: String,
+    pub encrypted: bool,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+pub struct KeyringIndex {
+    pub version: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default: Option<String>,
+    pub entries: Vec<KeyringEntry>,
+}
+
+// A directory holding one key file per wallet plus a `keyring.json` index.
+// When opened with a password, new keys are stored as encrypted keystores.
+pub struct SolKeyring {
+    dir: PathBuf,
+    password: Option<Zeroizing<String>>,
+    index: KeyringIndex,
+}
+
+impl SolKeyring {
+    pub fn open(dir: &Path, password: Option<&str>) -> Result<Self, String> {
+        fs::create_dir_all(dir)
+            .map_err(|err| format!("Failed to create keyring {}: {}", dir.display(), err))?;
+
+        let index_path = dir.join(KEYRING_INDEX_FILE);
+        let index = if index_path.exists() {
+            let file = File::open(&index_path)
+                .map_err(|err| format!("Failed to open {}: {}", index_path.display(), err))?;
+            let index: KeyringIndex = serde_json::from_reader(BufReader::new(file))
+                .map_err(|err| format!("Corrupt keyring index: {}", err))?;
+            if index.version != KEYRING_VERSION {
+                return Err(format!("Unsupported keyring version {}", index.version));
+            }
+            index
+        } else {
+            KeyringIndex {
+                version: KEYRING_VERSION,
+                ..Default::default()
+            }
+        };
+
+        Ok(SolKeyring {
+            dir: dir.to_path_buf(),
+            password: password.map(|password| Zeroizing::new(password.to_string())),
+            index,
+        })
+    }
+
+    fn save_index(&self) -> Result<(), String> {
+        let index_path = self.dir.join(KEYRING_INDEX_FILE);
+        let temp_path = self.dir.join(format!("{}.tmp", KEYRING_INDEX_FILE));
+        let json_data = serde_json::to_string_pretty(&self.index)
+            .map_err(|err| format!("Failed to serialize keyring index: {}", err))?;
+
+        File::create(&temp_path)
+            .and_then(|mut file| {
+                file.write_all(json_data.as_bytes())?;
+                file.sync_all()
+            })
+            .and_then(|_| fs::rename(&temp_path, &index_path))
+            .map_err(|err| format!("Failed to write {}: {}", index_path.display(), err))
+    }
+
+    fn entry(&self, label: &str) -> Result<&KeyringEntry, String> {
+        self.index
+            .entries
+            .iter()
+            .find(|entry| entry.label == label)
+            .ok_or_else(|| format!("No keyring entry labelled {}", label))
+    }
+
+    pub fn add(
+        &mut self,
+        label: &str,
+        keypair: &Keypair,
+        derivation_path: Option<&str>,
+    ) -> Result<(), String> {
+        if label.is_empty() {
+            return Err("Keyring label must not be empty".to_string());
+        }
+        if self.entry(label).is_ok() {
+            return Err(format!("Keyring label {} already exists", label));
+        }
+        let pubkey = keypair.pubkey().to_string();
+        if let Some(entry) = self.index.entries.iter().find(|e| e.pubkey == pubkey) {
+            return Err(format!("{} is already stored as {}", pubkey, entry.label));
+        }
+
+        let file = format!("{}.json", pubkey);
+        let file_path = self.dir.join(&file);
+        let file_path = file_path.to_string_lossy();
+        match &self.password {
+            Some(password) => save_encrypted_keypair(keypair, &file_path, password)
+                .map_err(|err| err.to_string())?,
+            None => save_wallet_to_file(keypair, &file_path)
+                .map_err(|err| format!("Failed to save {}: {}", file_path, err))?,
+        }
+
+        self.index.entries.push(KeyringEntry {
+            label: label.to_string(),
+            pubkey,
+            created_at: Utc::now().timestamp(),
+            derivation_path: derivation_path.map(str::to_string),
+            file,
+            encrypted: self.password.is_some(),
+        });
+        // The first wallet added becomes the default
+        if self.index.default.is_none() {
+            self.index.default = Some(label.to_string());
+        }
+        self.save_index()
+    }
+
+    pub fn keypair(&self, label: &str) -> Result<Keypair, String> {
+        let entry = self.entry(label)?;
+        let file_path = self.dir.join(&entry.file);
+        let file_path = file_path.to_string_lossy();
+
+        if entry.encrypted {
+            let password = self.password.as_ref().ok_or_else(|| {
+                format!("{} is encrypted; open the keyring with a password", label)
+            })?;
+            load_encrypted_keypair(&file_path, password).map_err(|err| err.to_string())
+        } else {
+            load_wallet(&file_path).map_err(|err| format!("Failed to load {}: {}", label, err))
+        }
+    }
+
+    pub fn default_keypair(&self) -> Result<Keypair, String> {
+        match &self.index.default {
+            Some(label) => self.keypair(label),
+            None => Err("Keyring has no default wallet".to_string()),
+        }
+    }
+
+    pub fn set_default(&mut self, label: &str) -> Result<(), String> {
+        self.entry(label)?;
+        self.index.default = Some(label.to_string());
+        self.save_index()
+    }
+
+    pub fn rename(&mut self, label: &str, new_label: &str) -> Result<(), String> {
+        if new_label.is_empty() {
+            return Err("Keyring label must not be empty".to_string());
+        }
+        if self.entry(new_label).is_ok() {
+            return Err(format!("Keyring label {} already exists", new_label));
+        }
+        self.entry(label)?;
+
+        for entry in self.index.entries.iter_mut() {
+            if entry.label == label {
+                entry.label = new_label.to_string();
+            }
+        }
+        if self.index.default.as_deref() == Some(label) {
+            self.index.default = Some(new_label.to_string());
+        }
+        self.save_index()
+    }
+
+    // Remove the entry and delete its key file
+    pub fn remove(&mut self, label: &str) -> Result<(), String> {
+        let file = self.entry(label)?.file.clone();
+        self.index.entries.retain(|entry| entry.label != label);
+        if self.index.default.as_deref() == Some(label) {
+            self.index.default = None;
+        }
+        self.save_index()?;
+
+        let file_path = self.dir.join(file);
+        fs::remove_file(&file_path)
+            .map_err(|err| format!("Failed to delete {}: {}", file_path.display(), err))
+    }
+
+    pub fn entries(&self) -> &[KeyringEntry] {
+        &self.index.entries
+    }
+
+    pub fn is_default(&self, label: &str) -> bool {
+        self.index.default.as_deref() == Some(label)
+    }
+}
+
+// ==================== C Types ==================== //
+
+#[repr(C)]
+pub struct SolKeyringEntry {
+    pub label: *const c_char,
+    pub pubkey: SolPublicKey,
+    pub created_at: i64,
+    pub derivation_path: *const c_char, // NULL when the key was not derived from a mnemonic
+    pub is_default: bool,
+    pub encrypted: bool,
+}
+
+#[repr(C)]
+pub struct SolKeyringList {
+    data: *mut SolKeyringEntry,
+    len: usize,
+}
+
+fn label_from_c<'a>(label: *const c_char) -> Option<&'a str> {
+    if label.is_null() {
+        return None;
+    }
+    unsafe { CStr::from_ptr(label) }.to_str().ok()
+}
+
+fn report(result: Result<(), String>) -> bool {
+    match result {
+        Ok(_) => true,
+        Err(err) => {
+            eprintln!("{}", err);
+            false
+        }
+    }
+}
+
+fn into_wallet(result: Result<Keypair, String>) -> *mut SolKeyPair {
+    match result {
+        Ok(keypair) => Box::into_raw(Box::new(SolKeyPair::from_existing(keypair))),
+        Err(err) => {
+            eprintln!("{}", err);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+// ==================== Keyring Functions ==================== //
+
+// Open (or create) a keyring directory. Pass a password to store new keys encrypted
+// and to unlock encrypted ones, or NULL for plain Solana CLI keypair files.
+#[no_mangle]
+pub extern "C" fn keyring_open(dir: *const c_char, password: *const c_char) -> *mut SolKeyring {
+    let dir = match unsafe { CStr::from_ptr(dir) }.to_str() {
+        Ok(str) => shellexpand::tilde(str).into_owned(),
+        Err(_) => return std::ptr::null_mut(),
+    };
+    let password = if password.is_null() {
+        None
+    } else {
+        match unsafe { CStr::from_ptr(password) }.to_str() {
+            Ok(str) => Some(str),
+            Err(_) => return std::ptr::null_mut(),
+        }
+    };
+
+    match SolKeyring::open(Path::new(&dir), password) {
+        Ok(keyring) => Box::into_raw(Box::new(keyring)),
+        Err(err) => {
+            eprintln!("{}", err);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+// Store an existing wallet under a label; `derivation_path` may be NULL
+#[no_mangle]
+pub extern "C" fn keyring_add_wallet(
+    keyring: *mut SolKeyring,
+    label: *const c_char,
+    wallet: *mut SolKeyPair,
+    derivation_path: *const c_char,
+) -> bool {
+    let keyring = unsafe {
+        assert!(!keyring.is_null());
+        &mut *keyring
+    };
+    let wallet = unsafe {
+        assert!(!wallet.is_null());
+        &*wallet
+    };
+    let label = match label_from_c(label) {
+        Some(label) => label,
+        None => return false,
+    };
+
+    report(keyring.add(label, &wallet.to_keypair(), label_from_c(derivation_path)))
+}
+
+// Generate a new wallet and store it under a label
+#[no_mangle]
+pub extern "C" fn keyring_create_wallet(
+    keyring: *mut SolKeyring,
+    label: *const c_char,
+) -> *mut SolKeyPair {
+    let keyring = unsafe {
+        assert!(!keyring.is_null());
+        &mut *keyring
+    };
+    let label = match label_from_c(label) {
+        Some(label) => label,
+        None => return std::ptr::null_mut(),
+    };
+
+    let keypair = Keypair::new();
+    into_wallet(keyring.add(label, &keypair, None).map(|_| keypair))
+}
+
+// Copy a loose Solana CLI keypair file into the keyring; the original file is left in place
+#[no_mangle]
+pub extern "C" fn keyring_import_file(
+    keyring: *mut SolKeyring,
+    label: *const c_char,
+    file_path: *const c_char,
+) -> bool {
+    let keyring = unsafe {
+        assert!(!keyring.is_null());
+        &mut *keyring
+    };
+    let (label, file_path) = match (label_from_c(label), label_from_c(file_path)) {
+        (Some(label), Some(file_path)) => (label, file_path),
+        _ => return false,
+    };
+
+    report(
+        load_wallet(file_path)
+            .map_err(|err| format!("Failed to load {}: {}", file_path, err))
+            .and_then(|keypair| keyring.add(label, &keypair, None)),
+    )
+}
+
+#[no_mangle]
+pub extern "C" fn keyring_get_wallet(
+    keyring: *const SolKeyring,
+    label: *const c_char,
+) -> *mut SolKeyPair {
+    let keyring = unsafe {
+        assert!(!keyring.is_null());
+        &*keyring
+    };
+    let label = match label_from_c(label) {
+        Some(label) => label,
+        None => return std::ptr::null_mut(),
+    };
+
+    into_wallet(keyring.keypair(label))
+}
+
+#[no_mangle]
+pub extern "C" fn keyring_get_default_wallet(keyring: *const SolKeyring) -> *mut SolKeyPair {
+    let keyring = unsafe {
+        assert!(!keyring.is_null());
+        &*keyring
+    };
+
+    into_wallet(keyring.default_keypair())
+}
+
+#[no_mangle]
+pub extern "C" fn keyring_set_default(keyring: *mut SolKeyring, label: *const c_char) -> bool {
+    let keyring = unsafe {
+        assert!(!keyring.is_null());
+        &mut *keyring
+    };
+    let label = match label_from_c(label) {
+        Some(label) => label,
+        None => return false,
+    };
+
+    report(keyring.set_default(label))
+}
+
+#[no_mangle]
+pub extern "C" fn keyring_rename(
+    keyring: *mut SolKeyring,
+    label: *const c_char,
+    new_label: *const c_char,
+) -> bool {
+    let keyring = unsafe {
+        assert!(!keyring.is_null());
+        &mut *keyring
+    };
+    let (label, new_label) = match (label_from_c(label), label_from_c(new_label)) {
+        (Some(label), Some(new_label)) => (label, new_label),
+        _ => return false,
+    };
+
+    report(keyring.rename(label, new_label))
+}
+
+// Remove a wallet from the keyring and delete its key file
+#[no_mangle]
+pub extern "C" fn keyring_remove(keyring: *mut SolKeyring, label: *const c_char) -> bool {
+    let keyring = unsafe {
+        assert!(!keyring.is_null());
+        &mut *keyring
+    };
+    let label = match label_from_c(label) {
+        Some(label) => label,
+        None => return false,
+    };
+
+    report(keyring.remove(label))
+}
+
+// List entries without touching any secret key material
+#[no_mangle]
+pub extern "C" fn keyring_list(keyring: *const SolKeyring) -> *mut SolKeyringList {
+    let keyring = unsafe {
+        assert!(!keyring.is_null());
+        &*keyring
+    };
+
+    let entries: Box<[SolKeyringEntry]> = keyring
+        .entries()
+        .iter()
+        .filter_map(|entry| {
+            let pubkey = entry.pubkey.parse().ok()?;
+            Some(SolKeyringEntry {
+                label: CString::new(entry.label.as_str()).ok()?.into_raw(),
+                pubkey: SolPublicKey::new(pubkey),
+                created_at: entry.created_at,
+                derivation_path: entry
+                    .derivation_path
+                    .as_ref()
+                    .and_then(|path| CString::new(path.as_str()).ok())
+                    .map_or(std::ptr::null(), |path| path.into_raw()),
+                is_default: keyring.is_default(&entry.label),
+                encrypted: entry.encrypted,
+            })
+        })
+        .collect();
+
+    let list = Box::new(SolKeyringList {
+        len: entries.len(),
+        data: Box::into_raw(entries) as *mut SolKeyringEntry, // Freed by `free_keyring_list`
+    });
+
+    Box::into_raw(list)
+}
+
+#[no_mangle]
+pub extern "C" fn keyring_list_get_data(list: *const SolKeyringList) -> *mut SolKeyringEntry {
+    if list.is_null() {
+        std::ptr::null_mut()
+    } else {
+        unsafe { (*list).data }
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn keyring_list_get_len(list: *const SolKeyringList) -> usize {
+    if list.is_null() {
+        0
+    } else {
+        unsafe { (*list).len }
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn free_keyring_list(list: *mut SolKeyringList) {
+    if list.is_null() {
+        return;
+    }
+
+    unsafe {
+        let list = Box::from_raw(list);
+        let entries = Box::from_raw(std::ptr::slice_from_raw_parts_mut(list.data, list.len));
+        for entry in entries.iter() {
+            drop(CString::from_raw(entry.label as *mut c_char));
+            if !entry.derivation_path.is_null() {
+                drop(CString::from_raw(entry.derivation_path as *mut c_char));
+            }
+        }
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn free_keyring(keyring: *mut SolKeyring) {
+    if keyring.is_null() {
+        return;
+    }
+    unsafe {
+        drop(Box::from_raw(keyring));
+    }
+}
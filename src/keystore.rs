@@ -0,0 +1,470 @@
+use aes_gcm::aead::{Aead, KeyInit, Payload};
+use aes_gcm::{Aes256Gcm, Nonce};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use rand::{rngs::OsRng, RngCore};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use solana_sdk::signature::{Keypair, Signer};
+use std::ffi::CStr;
+use std::fmt;
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufReader, Write};
+use std::os::raw::c_char;
+use zeroize::Zeroizing;
+
+use crate::wallet::{load_wallet, SolKeyPair};
+
+// ==================== Keystore Format ==================== //
+
+const KEYSTORE_VERSION: u32 = 1;
+const CIPHER_NAME: &str = "aes-256-gcm";
+const KDF_NAME: &str = "scrypt";
+
+// Default scrypt cost: N = 2^15, r = 8, p = 1 (~32 MiB, well under a second)
+const SCRYPT_LOG_N: u8 = 15;
+const SCRYPT_R: u32 = 8;
+const SCRYPT_P: u32 = 1;
+
+// scrypt needs 128·r·2^log_n bytes and p repeats that work. A file may ask for
+// at most 256 MiB of it in total (8x the default), so a damaged or hostile
+// keystore cannot exhaust memory or stall the caller.
+const MAX_SCRYPT_WORK: u64 = 256 * 1024 * 1024;
+
+const SALT_LEN: usize = 32;
+const NONCE_LEN: usize = 12;
+
+// Versioned JSON envelope written to disk
+#[derive(Serialize, Deserialize)]
+pub struct Keystore {
+    pub version: u32,
+    pub pubkey: String,
+    pub crypto: KeystoreCrypto,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct KeystoreCrypto {
+    pub cipher: String,
+    pub ciphertext: String,
+    pub nonce: String,
+    pub kdf: String,
+    pub kdfparams: ScryptParams,
+    // SHA-256 of the second half of the derived key, used to tell a wrong
+    // password apart from a damaged ciphertext
+    pub password_check: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ScryptParams {
+    pub log_n: u8,
+    pub r: u32,
+    pub p: u32,
+    pub salt: String,
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SolKeystoreStatus {
+    Ok,
+    IoError,
+    CorruptFile,
+    UnsupportedVersion,
+    WrongPassword,
+    InvalidArgument,
+}
+
+#[derive(Debug)]
+pub enum KeystoreError {
+    Io(std::io::Error),
+    Corrupt(String),
+    UnsupportedVersion(u32),
+    WrongPassword,
+}
+
+impl KeystoreError {
+    pub fn status(&self) -> SolKeystoreStatus {
+        match self {
+            KeystoreError::Io(_) => SolKeystoreStatus::IoError,
+            KeystoreError::Corrupt(_) => SolKeystoreStatus::CorruptFile,
+            KeystoreError::UnsupportedVersion(_) => SolKeystoreStatus::UnsupportedVersion,
+            KeystoreError::WrongPassword => SolKeystoreStatus::WrongPassword,
+        }
+    }
+}
+
+impl fmt::Display for KeystoreError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KeystoreError::Io(err) => write!(f, "Keystore I/O error: {}", err),
+            KeystoreError::Corrupt(reason) => write!(f, "Keystore file is corrupt: {}", reason),
+            KeystoreError::UnsupportedVersion(version) => {
+                write!(f, "Unsupported keystore version: {}", version)
+            }
+            KeystoreError::WrongPassword => write!(f, "Wrong keystore password"),
+        }
+    }
+}
+
+impl From<std::io::Error> for KeystoreError {
+    fn from(err: std::io::Error) -> Self {
+        KeystoreError::Io(err)
+    }
+}
+
+// ==================== Encryption ==================== //
+
+// 128·r·2^log_n·p, or None if it overflows
+fn scrypt_work(params: &ScryptParams) -> Option<u64> {
+    128u64
+        .checked_mul(u64::from(params.r))?
+        .checked_mul(1u64.checked_shl(u32::from(params.log_n))?)?
+        .checked_mul(u64::from(params.p))
+}
+
+// Derive 64 bytes: the first half is the AES key, the second half feeds the password check
+fn derive_key(password: &str, params: &ScryptParams) -> Result<Zeroizing<[u8; 64]>, KeystoreError> {
+    let salt = BASE64
+        .decode(&params.salt)
+        .map_err(|_| KeystoreError::Corrupt("invalid salt encoding".to_string()))?;
+    if scrypt_work(params).is_none_or(|work| work > MAX_SCRYPT_WORK) {
+        return Err(KeystoreError::Corrupt(format!(
+            "scrypt parameters out of range (log_n {}, r {}, p {})",
+            params.log_n, params.r, params.p
+        )));
+    }
+    let scrypt_params = scrypt::Params::new(params.log_n, params.r, params.p, 64)
+        .map_err(|_| KeystoreError::Corrupt("invalid scrypt parameters".to_string()))?;
+
+    let mut derived = Zeroizing::new([0u8; 64]);
+    scrypt::scrypt(password.as_bytes(), &salt, &scrypt_params, derived.as_mut())
+        .map_err(|_| KeystoreError::Corrupt("invalid scrypt output length".to_string()))?;
+    Ok(derived)
+}
+
+fn password_check(derived: &[u8; 64]) -> String {
+    BASE64.encode(Sha256::digest(&derived[32..]))
+}
+
+// Bind the header fields to the ciphertext so they cannot be swapped
+fn associated_data(version: u32, pubkey: &str) -> Vec<u8> {
+    format!("solana-c-sdk-keystore:v{}:{}", version, pubkey).into_bytes()
+}
+
+pub fn encrypt_keypair(keypair: &Keypair, password: &str) -> Result<Keystore, KeystoreError> {
+    let mut salt = [0u8; SALT_LEN];
+    let mut nonce = [0u8; NONCE_LEN];
+    OsRng.fill_bytes(&mut salt);
+    OsRng.fill_bytes(&mut nonce);
+
+    let kdfparams = ScryptParams {
+        log_n: SCRYPT_LOG_N,
+        r: SCRYPT_R,
+        p: SCRYPT_P,
+        salt: BASE64.encode(salt),
+    };
+    let derived = derive_key(password, &kdfparams)?;
+
+    let pubkey = keypair.pubkey().to_string();
+    let aad = associated_data(KEYSTORE_VERSION, &pubkey);
+    let secret_key = Zeroizing::new(keypair.to_bytes());
+
+    let cipher = Aes256Gcm::new_from_slice(&derived[..32])
+        .map_err(|_| KeystoreError::Corrupt("invalid key length".to_string()))?;
+    let ciphertext = cipher
+        .encrypt(
+            Nonce::from_slice(&nonce),
+            Payload {
+                msg: secret_key.as_ref(),
+                aad: &aad,
+            },
+        )
+        .map_err(|_| KeystoreError::Corrupt("encryption failed".to_string()))?;
+
+    Ok(Keystore {
+        version: KEYSTORE_VERSION,
+        pubkey,
+        crypto: KeystoreCrypto {
+            cipher: CIPHER_NAME.to_string(),
+            ciphertext: BASE64.encode(ciphertext),
+            nonce: BASE64.encode(nonce),
+            kdf: KDF_NAME.to_string(),
+            kdfparams,
+            password_check: password_check(&derived),
+        },
+    })
+}
+
+pub fn decrypt_keystore(keystore: &Keystore, password: &str) -> Result<Keypair, KeystoreError> {
+    if keystore.version != KEYSTORE_VERSION {
+        return Err(KeystoreError::UnsupportedVersion(keystore.version));
+    }
+    if keystore.crypto.cipher != CIPHER_NAME || keystore.crypto.kdf != KDF_NAME {
+        return Err(KeystoreError::Corrupt(format!(
+            "unknown cipher/kdf {}/{}",
+            keystore.crypto.cipher, keystore.crypto.kdf
+        )));
+    }
+
+    let derived = derive_key(password, &keystore.crypto.kdfparams)?;
+    if password_check(&derived) != keystore.crypto.password_check {
+        return Err(KeystoreError::WrongPassword);
+    }
+
+    let nonce = BASE64
+        .decode(&keystore.crypto.nonce)
+        .ok()
+        .filter(|nonce| nonce.len() == NONCE_LEN)
+        .ok_or_else(|| KeystoreError::Corrupt("invalid nonce".to_string()))?;
+    let ciphertext = BASE64
+        .decode(&keystore.crypto.ciphertext)
+        .map_err(|_| KeystoreError::Corrupt("invalid ciphertext encoding".to_string()))?;
+    let aad = associated_data(keystore.version, &keystore.pubkey);
+
+    let cipher = Aes256Gcm::new_from_slice(&derived[..32])
+        .map_err(|_| KeystoreError::Corrupt("invalid key length".to_string()))?;
+    // The password was verified above, so a failed tag means the file was damaged
+    let secret_key = Zeroizing::new(
+        cipher
+            .decrypt(
+                Nonce::from_slice(&nonce),
+                Payload {
+                    msg: &ciphertext,
+                    aad: &aad,
+                },
+            )
+            .map_err(|_| KeystoreError::Corrupt("authentication tag mismatch".to_string()))?,
+    );
+
+    let keypair = Keypair::from_bytes(&secret_key)
+        .map_err(|_| KeystoreError::Corrupt("invalid secret key".to_string()))?;
+    if keypair.pubkey().to_string() != keystore.pubkey {
+        return Err(KeystoreError::Corrupt(
+            "public key does not match secret key".to_string(),
+        ));
+    }
+    Ok(keypair)
+}
+
+// ==================== Keystore Files ==================== //
+
+pub fn read_keystore(file_path: &str) -> Result<Keystore, KeystoreError> {
+    let reader = BufReader::new(File::open(file_path)?);
+    serde_json::from_reader(reader).map_err(|err| KeystoreError::Corrupt(err.to_string()))
+}
+
+// Write through a temporary file so an interrupted save never truncates an existing keystore
+pub fn write_keystore(keystore: &Keystore, file_path: &str) -> Result<(), KeystoreError> {
+    let json_data = serde_json::to_string_pretty(keystore)
+        .map_err(|err| KeystoreError::Corrupt(err.to_string()))?;
+    let temp_path = format!("{}.tmp", file_path);
+
+    let mut options = OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+
+    let mut file = options.open(&temp_path)?;
+    file.write_all(json_data.as_bytes())?;
+    file.sync_all()?;
+    fs::rename(&temp_path, file_path)?;
+    Ok(())
+}
+
+pub fn save_encrypted_keypair(
+    keypair: &Keypair,
+    file_path: &str,
+    password: &str,
+) -> Result<(), KeystoreError> {
+    let keystore = encrypt_keypair(keypair, password)?;
+    write_keystore(&keystore, file_path)
+}
+
+pub fn load_encrypted_keypair(file_path: &str, password: &str) -> Result<Keypair, KeystoreError> {
+    let keystore = read_keystore(file_path)?;
+    decrypt_keystore(&keystore, password)
+}
+
+fn set_status(status_out: *mut SolKeystoreStatus, status: SolKeystoreStatus) {
+    if !status_out.is_null() {
+        unsafe { *status_out = status };
+    }
+}
+
+// ==================== Keystore Functions ==================== //
+
+#[no_mangle]
+pub extern "C" fn create_and_save_encrypted_wallet(
+    file_path: *const c_char,
+    password: *const c_char,
+) -> *mut SolKeyPair {
+    let file_path = match unsafe { CStr::from_ptr(file_path) }.to_str() {
+        Ok(str) => str,
+        Err(_) => return std::ptr::null_mut(),
+    };
+    let password = match unsafe { CStr::from_ptr(password) }.to_str() {
+        Ok(str) => str,
+        Err(_) => return std::ptr::null_mut(),
+    };
+
+    let keypair = Keypair::new();
+    match save_encrypted_keypair(&keypair, file_path, password) {
+        Ok(_) => Box::into_raw(Box::new(SolKeyPair::from_existing(keypair))),
+        Err(err) => {
+            eprintln!("{}", err);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+// Encrypt an existing wallet, e.g. to migrate a plaintext CLI keypair file
+#[no_mangle]
+pub extern "C" fn save_wallet_encrypted(
+    wallet: *mut SolKeyPair,
+    file_path: *const c_char,
+    password: *const c_char,
+) -> SolKeystoreStatus {
+    let wallet = unsafe {
+        assert!(!wallet.is_null());
+        &*wallet
+    };
+    let file_path = match unsafe { CStr::from_ptr(file_path) }.to_str() {
+        Ok(str) => str,
+        Err(_) => return SolKeystoreStatus::InvalidArgument,
+    };
+    let password = match unsafe { CStr::from_ptr(password) }.to_str() {
+        Ok(str) => str,
+        Err(_) => return SolKeystoreStatus::InvalidArgument,
+    };
+
+    match save_encrypted_keypair(&wallet.to_keypair(), file_path, password) {
+        Ok(_) => SolKeystoreStatus::Ok,
+        Err(err) => {
+            eprintln!("{}", err);
+            err.status()
+        }
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn load_encrypted_wallet(
+    file_path: *const c_char,
+    password: *const c_char,
+    status_out: *mut SolKeystoreStatus,
+) -> *mut SolKeyPair {
+    let file_path = match unsafe { CStr::from_ptr(file_path) }.to_str() {
+        Ok(str) => str,
+        Err(_) => {
+            set_status(status_out, SolKeystoreStatus::InvalidArgument);
+            return std::ptr::null_mut();
+        }
+    };
+    let password = match unsafe { CStr::from_ptr(password) }.to_str() {
+        Ok(str) => str,
+        Err(_) => {
+            set_status(status_out, SolKeystoreStatus::InvalidArgument);
+            return std::ptr::null_mut();
+        }
+    };
+
+    match load_encrypted_keypair(file_path, password) {
+        Ok(keypair) => {
+            set_status(status_out, SolKeystoreStatus::Ok);
+            Box::into_raw(Box::new(SolKeyPair::from_existing(keypair)))
+        }
+        Err(err) => {
+            eprintln!("{}", err);
+            set_status(status_out, err.status());
+            std::ptr::null_mut()
+        }
+    }
+}
+
+// Load either an encrypted keystore or a plaintext Solana CLI keypair file.
+// The password is ignored for CLI files and may be NULL.
+#[no_mangle]
+pub extern "C" fn load_wallet_any_format(
+    file_path: *const c_char,
+    password: *const c_char,
+    status_out: *mut SolKeystoreStatus,
+) -> *mut SolKeyPair {
+    let file_path_str = match unsafe { CStr::from_ptr(file_path) }.to_str() {
+        Ok(str) => str,
+        Err(_) => {
+            set_status(status_out, SolKeystoreStatus::InvalidArgument);
+            return std::ptr::null_mut();
+        }
+    };
+
+    let is_keystore = match fs::read(file_path_str) {
+        Ok(contents) => contents
+            .iter()
+            .find(|byte| !byte.is_ascii_whitespace())
+            .is_some_and(|byte| *byte == b'{'),
+        Err(err) => {
+            eprintln!("Failed to read wallet file {}: {}", file_path_str, err);
+            set_status(status_out, SolKeystoreStatus::IoError);
+            return std::ptr::null_mut();
+        }
+    };
+
+    if is_keystore {
+        if password.is_null() {
+            eprintln!("A password is required for keystore file {}", file_path_str);
+            set_status(status_out, SolKeystoreStatus::InvalidArgument);
+            return std::ptr::null_mut();
+        }
+        return load_encrypted_wallet(file_path, password, status_out);
+    }
+
+    match load_wallet(file_path_str) {
+        Ok(keypair) => {
+            set_status(status_out, SolKeystoreStatus::Ok);
+            Box::into_raw(Box::new(SolKeyPair::from_existing(keypair)))
+        }
+        Err(err) => {
+            eprintln!("Failed to load wallet {}: {}", file_path_str, err);
+            set_status(
+                status_out,
+                match err.kind() {
+                    std::io::ErrorKind::InvalidData => SolKeystoreStatus::CorruptFile,
+                    _ => SolKeystoreStatus::IoError,
+                },
+            );
+            std::ptr::null_mut()
+        }
+    }
+}
+
+// Re-encrypt a keystore under a new password with a fresh salt and nonce
+#[no_mangle]
+pub extern "C" fn change_keystore_password(
+    file_path: *const c_char,
+    old_password: *const c_char,
+    new_password: *const c_char,
+) -> SolKeystoreStatus {
+    let file_path = match unsafe { CStr::from_ptr(file_path) }.to_str() {
+        Ok(str) => str,
+        Err(_) => return SolKeystoreStatus::InvalidArgument,
+    };
+    let old_password = match unsafe { CStr::from_ptr(old_password) }.to_str() {
+        Ok(str) => str,
+        Err(_) => return SolKeystoreStatus::InvalidArgument,
+    };
+    let new_password = match unsafe { CStr::from_ptr(new_password) }.to_str() {
+        Ok(str) => str,
+        Err(_) => return SolKeystoreStatus::InvalidArgument,
+    };
+
+    let result = load_encrypted_keypair(file_path, old_password)
+        .and_then(|keypair| save_encrypted_keypair(&keypair, file_path, new_password));
+
+    match result {
+        Ok(_) => SolKeystoreStatus::Ok,
+        Err(err) => {
+            eprintln!("{}", err);
+            err.status()
+        }
+    }
+}
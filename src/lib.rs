@@ -1,4 +1,24 @@
 mod client;
+mod decoder;
+mod events;
+mod idl;
+mod idl_accounts;
+mod idl_codec;
+mod key_format;
+mod keyring;
+mod keystore;
+mod lookup_table;
+mod message;
+mod mnemonic;
+mod nonce;
+mod offline;
+mod pda;
+mod program_error;
+mod relayer;
+mod rpc_recorder;
+mod signer;
+mod siws;
 mod smart_contract;
 mod token;
+mod transaction;
 mod wallet;
@@ -0,0 +1,308 @@
+use solana_sdk::address_lookup_table::{
+    self, instruction, state::AddressLookupTable, AddressLookupTableAccount,
+};
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signer;
+
+use crate::client::SolClient;
+use crate::transaction::{send_instructions, SolTransactionResult};
+use crate::wallet::{SolKeyPair, SolPublicKey};
+
+// Addresses added per extend transaction, keeping it under the packet size limit
+const EXTEND_BATCH_SIZE: usize = 20;
+
+// Lookup table state; release with `free_lookup_table`
+#[repr(C)]
+pub struct SolLookupTable {
+    pub address: SolPublicKey,
+    pub authority: SolPublicKey, // All zeroes once the table is frozen
+    pub is_frozen: bool,
+    pub deactivation_slot: u64, // u64::MAX while the table is active
+    pub last_extended_slot: u64,
+    pub addresses: *mut SolPublicKey,
+    pub address_count: usize,
+}
+
+// ==================== Helpers ==================== //
+
+fn fetch_lookup_table_data(client: &SolClient, address: &Pubkey) -> Result<Vec<u8>, String> {
+    let account = client
+        .rpc_client
+        .get_account(address)
+        .map_err(|err| format!("Failed to fetch lookup table {}: {:?}", address, err))?;
+    if account.owner != address_lookup_table::program::id() {
+        return Err(format!(
+            "Account {} is not an address lookup table",
+            address
+        ));
+    }
+    Ok(account.data)
+}
+
+fn deserialize_lookup_table<'a>(
+    address: &Pubkey,
+    data: &'a [u8],
+) -> Result<AddressLookupTable<'a>, String> {
+    AddressLookupTable::deserialize(data)
+        .map_err(|err| format!("Invalid lookup table {}: {}", address, err))
+}
+
+// Resolve a lookup table into the form used when compiling v0 messages
+pub fn fetch_lookup_table(
+    client: &SolClient,
+    address: &Pubkey,
+) -> Result<AddressLookupTableAccount, String> {
+    let data = fetch_lookup_table_data(client, address)?;
+    let table = deserialize_lookup_table(address, &data)?;
+    Ok(AddressLookupTableAccount {
+        key: *address,
+        addresses: table.addresses.to_vec(),
+    })
+}
+
+// ==================== Lookup Table Functions ==================== //
+
+// Create a lookup table owned by `authority`; `payer` funds and signs.
+// The new table address is written to `lookup_table_out`.
+#[no_mangle]
+pub extern "C" fn create_lookup_table(
+    client: *mut SolClient,
+    authority: *const SolPublicKey,
+    payer: *mut SolKeyPair,
+    lookup_table_out: *mut SolPublicKey,
+) -> *mut SolTransactionResult {
+    let client = unsafe {
+        assert!(!client.is_null());
+        &*client
+    };
+    let authority = unsafe {
+        assert!(!authority.is_null());
+        &*authority
+    };
+    let payer = unsafe {
+        assert!(!payer.is_null());
+        &*payer
+    }
+    .to_keypair();
+
+    // The derivation slot must still be in the SlotHashes sysvar
+    let recent_slot = match client
+        .rpc_client
+        .get_slot_with_commitment(CommitmentConfig::finalized())
+    {
+        Ok(slot) => slot,
+        Err(err) => {
+            return SolTransactionResult::failed(format!("Failed to fetch slot: {:?}", err))
+                .into_raw()
+        }
+    };
+    let (create_instruction, lookup_table) =
+        instruction::create_lookup_table(authority.to_pubkey(), payer.pubkey(), recent_slot);
+
+    let result = send_instructions(client, &[create_instruction], &payer.pubkey(), &[&payer]);
+    if result.success && !lookup_table_out.is_null() {
+        unsafe { *lookup_table_out = SolPublicKey::new(lookup_table) };
+    }
+    result.into_raw()
+}
+
+// Append addresses to a table, sending one transaction per batch of 20.
+// `payer` funds the extra rent and may be the same wallet as `authority`.
+#[no_mangle]
+pub extern "C" fn extend_lookup_table(
+    client: *mut SolClient,
+    lookup_table: *const SolPublicKey,
+    authority: *mut SolKeyPair,
+    payer: *mut SolKeyPair,
+    addresses: *const SolPublicKey,
+    address_count: usize,
+) -> *mut SolTransactionResult {
+    let client = unsafe {
+        assert!(!client.is_null());
+        &*client
+    };
+    let lookup_table = unsafe {
+        assert!(!lookup_table.is_null());
+        &*lookup_table
+    }
+    .to_pubkey();
+    let authority = unsafe {
+        assert!(!authority.is_null());
+        &*authority
+    }
+    .to_keypair();
+    let payer = unsafe {
+        assert!(!payer.is_null());
+        &*payer
+    }
+    .to_keypair();
+    if addresses.is_null() || address_count == 0 {
+        return SolTransactionResult::failed("No addresses to add to the lookup table").into_raw();
+    }
+    let addresses: Vec<Pubkey> = unsafe { std::slice::from_raw_parts(addresses, address_count) }
+        .iter()
+        .map(SolPublicKey::to_pubkey)
+        .collect();
+
+    let mut result = SolTransactionResult::failed("No addresses to add to the lookup table");
+    for batch in addresses.chunks(EXTEND_BATCH_SIZE) {
+        let extend_instruction = instruction::extend_lookup_table(
+            lookup_table,
+            authority.pubkey(),
+            Some(payer.pubkey()),
+            batch.to_vec(),
+        );
+        result = send_instructions(
+            client,
+            &[extend_instruction],
+            &payer.pubkey(),
+            &[&payer, &authority],
+        );
+        if !result.success {
+            break;
+        }
+    }
+    result.into_raw()
+}
+
+// Start deactivation; the table can be closed once the slot is no longer recent
+#[no_mangle]
+pub extern "C" fn deactivate_lookup_table(
+    client: *mut SolClient,
+    lookup_table: *const SolPublicKey,
+    authority: *mut SolKeyPair,
+    fee_payer: *mut SolKeyPair, // NULL means the authority pays the fee
+) -> *mut SolTransactionResult {
+    let client = unsafe {
+        assert!(!client.is_null());
+        &*client
+    };
+    let lookup_table = unsafe {
+        assert!(!lookup_table.is_null());
+        &*lookup_table
+    };
+    let authority = unsafe {
+        assert!(!authority.is_null());
+        &*authority
+    }
+    .to_keypair();
+
+    let deactivate_instruction =
+        instruction::deactivate_lookup_table(lookup_table.to_pubkey(), authority.pubkey());
+    let fee_payer = unsafe { fee_payer.as_ref() }.map(SolKeyPair::to_keypair);
+    let fee_payer = fee_payer.as_ref().unwrap_or(&authority);
+    send_instructions(
+        client,
+        &[deactivate_instruction],
+        &fee_payer.pubkey(),
+        &[fee_payer, &authority],
+    )
+    .into_raw()
+}
+
+// Close a deactivated table and send its rent to `recipient`
+#[no_mangle]
+pub extern "C" fn close_lookup_table(
+    client: *mut SolClient,
+    lookup_table: *const SolPublicKey,
+    authority: *mut SolKeyPair,
+    recipient: *const SolPublicKey,
+    fee_payer: *mut SolKeyPair, // NULL means the authority pays the fee
+) -> *mut SolTransactionResult {
+    let client = unsafe {
+        assert!(!client.is_null());
+        &*client
+    };
+    let lookup_table = unsafe {
+        assert!(!lookup_table.is_null());
+        &*lookup_table
+    };
+    let authority = unsafe {
+        assert!(!authority.is_null());
+        &*authority
+    }
+    .to_keypair();
+    let recipient = unsafe {
+        assert!(!recipient.is_null());
+        &*recipient
+    };
+
+    let close_instruction = instruction::close_lookup_table(
+        lookup_table.to_pubkey(),
+        authority.pubkey(),
+        recipient.to_pubkey(),
+    );
+    let fee_payer = unsafe { fee_payer.as_ref() }.map(SolKeyPair::to_keypair);
+    let fee_payer = fee_payer.as_ref().unwrap_or(&authority);
+    send_instructions(
+        client,
+        &[close_instruction],
+        &fee_payer.pubkey(),
+        &[fee_payer, &authority],
+    )
+    .into_raw()
+}
+
+// Fetch a table and the addresses it holds
+#[no_mangle]
+pub extern "C" fn get_lookup_table(
+    client: *mut SolClient,
+    lookup_table: *const SolPublicKey,
+) -> *mut SolLookupTable {
+    let client = unsafe {
+        assert!(!client.is_null());
+        &*client
+    };
+    let address = unsafe {
+        assert!(!lookup_table.is_null());
+        &*lookup_table
+    }
+    .to_pubkey();
+
+    let data = match fetch_lookup_table_data(client, &address) {
+        Ok(data) => data,
+        Err(err) => {
+            eprintln!("{}", err);
+            return std::ptr::null_mut();
+        }
+    };
+    let table = match deserialize_lookup_table(&address, &data) {
+        Ok(table) => table,
+        Err(err) => {
+            eprintln!("{}", err);
+            return std::ptr::null_mut();
+        }
+    };
+
+    let addresses: Box<[SolPublicKey]> = table
+        .addresses
+        .iter()
+        .map(|address| SolPublicKey::new(*address))
+        .collect();
+    let address_count = addresses.len();
+
+    Box::into_raw(Box::new(SolLookupTable {
+        address: SolPublicKey::new(address),
+        authority: SolPublicKey::new(table.meta.authority.unwrap_or_default()),
+        is_frozen: table.meta.authority.is_none(),
+        deactivation_slot: table.meta.deactivation_slot,
+        last_extended_slot: table.meta.last_extended_slot,
+        addresses: Box::into_raw(addresses) as *mut SolPublicKey,
+        address_count,
+    }))
+}
+
+#[no_mangle]
+pub extern "C" fn free_lookup_table(lookup_table: *mut SolLookupTable) {
+    if lookup_table.is_null() {
+        return;
+    }
+    unsafe {
+        let lookup_table = Box::from_raw(lookup_table);
+        drop(Box::from_raw(std::ptr::slice_from_raw_parts_mut(
+            lookup_table.addresses,
+            lookup_table.address_count,
+        )));
+    }
+}
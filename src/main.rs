@@ -115,7 +115,7 @@ async fn main() {
     let program_id = "DsfPR2teuRS9ABmqGqq5NobD8Y9A9KvzMVNVzsjSP8Dy";
 
     // Create Solana client and payer
-    let (client, payer) = create_client_and_payer(rpc_url, &*shellexpand::tilde(payer_path));
+    let (client, payer) = create_client_and_payer(rpc_url, &shellexpand::tilde(payer_path));
 
     // Initialize a new account
     let account = Keypair::new();
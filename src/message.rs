@@ -0,0 +1,252 @@
+use solana_sdk::offchain_message::OffchainMessage;
+use solana_sdk::signature::{Signature, Signer};
+use std::ffi::{CStr, CString};
+use std::os::raw::c_char;
+use std::str::FromStr;
+
+use crate::signer::SolSigner;
+use crate::wallet::{SolKeyPair, SolPublicKey, SolSignature};
+
+// ==================== Helpers ==================== //
+
+fn message_from_c<'a>(message: *const u8, message_len: usize) -> &'a [u8] {
+    if message.is_null() || message_len == 0 {
+        &[]
+    } else {
+        unsafe { std::slice::from_raw_parts(message, message_len) }
+    }
+}
+
+fn write_signature(signature: Signature, signature_out: *mut SolSignature) -> bool {
+    if signature_out.is_null() {
+        return false;
+    }
+    unsafe { *signature_out = SolSignature::new(signature) };
+    true
+}
+
+// Build the off-chain envelope: signing domain, header version, format and length
+pub fn offchain_message_bytes(version: u8, message: &[u8]) -> Result<Vec<u8>, String> {
+    OffchainMessage::new(version, message)
+        .and_then(|offchain| offchain.serialize())
+        .map_err(|err| format!("Invalid off-chain message: {:?}", err))
+}
+
+pub fn sign_bytes(signer: &dyn Signer, message: &[u8]) -> Result<Signature, String> {
+    signer
+        .try_sign_message(message)
+        .map_err(|err| format!("Failed to sign message: {:?}", err))
+}
+
+// ==================== Raw Message Signing ==================== //
+
+// Sign arbitrary bytes, as done by wallet `signMessage` APIs
+#[no_mangle]
+pub extern "C" fn sign_message(
+    wallet: *mut SolKeyPair,
+    message: *const u8,
+    message_len: usize,
+    signature_out: *mut SolSignature,
+) -> bool {
+    let wallet = unsafe {
+        assert!(!wallet.is_null());
+        &*wallet
+    };
+
+    let message = message_from_c(message, message_len);
+    match sign_bytes(&wallet.to_keypair(), message) {
+        Ok(signature) => write_signature(signature, signature_out),
+        Err(err) => {
+            eprintln!("{}", err);
+            false
+        }
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn sign_message_with_signer(
+    signer: *mut SolSigner,
+    message: *const u8,
+    message_len: usize,
+    signature_out: *mut SolSignature,
+) -> bool {
+    let signer = unsafe {
+        assert!(!signer.is_null());
+        &*signer
+    };
+
+    let message = message_from_c(message, message_len);
+    match sign_bytes(signer, message) {
+        Ok(signature) => write_signature(signature, signature_out),
+        Err(err) => {
+            eprintln!("{}", err);
+            false
+        }
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn verify_signature(
+    pubkey: *const SolPublicKey,
+    message: *const u8,
+    message_len: usize,
+    signature: *const SolSignature,
+) -> bool {
+    let pubkey = unsafe {
+        assert!(!pubkey.is_null());
+        &*pubkey
+    };
+    let signature = unsafe {
+        assert!(!signature.is_null());
+        &*signature
+    };
+
+    let message = message_from_c(message, message_len);
+    signature
+        .to_signature()
+        .verify(pubkey.to_pubkey().as_ref(), message)
+}
+
+// ==================== Off-chain Message Signing ==================== //
+
+// Sign using the Solana off-chain message format ("\xffsolana offchain" domain)
+#[no_mangle]
+pub extern "C" fn sign_offchain_message(
+    wallet: *mut SolKeyPair,
+    version: u8,
+    message: *const u8,
+    message_len: usize,
+    signature_out: *mut SolSignature,
+) -> bool {
+    let wallet = unsafe {
+        assert!(!wallet.is_null());
+        &*wallet
+    };
+
+    let message = message_from_c(message, message_len);
+    match offchain_message_bytes(version, message)
+        .and_then(|bytes| sign_bytes(&wallet.to_keypair(), &bytes))
+    {
+        Ok(signature) => write_signature(signature, signature_out),
+        Err(err) => {
+            eprintln!("{}", err);
+            false
+        }
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn sign_offchain_message_with_signer(
+    signer: *mut SolSigner,
+    version: u8,
+    message: *const u8,
+    message_len: usize,
+    signature_out: *mut SolSignature,
+) -> bool {
+    let signer = unsafe {
+        assert!(!signer.is_null());
+        &*signer
+    };
+
+    let message = message_from_c(message, message_len);
+    match offchain_message_bytes(version, message).and_then(|bytes| sign_bytes(signer, &bytes)) {
+        Ok(signature) => write_signature(signature, signature_out),
+        Err(err) => {
+            eprintln!("{}", err);
+            false
+        }
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn verify_offchain_message(
+    pubkey: *const SolPublicKey,
+    version: u8,
+    message: *const u8,
+    message_len: usize,
+    signature: *const SolSignature,
+) -> bool {
+    let pubkey = unsafe {
+        assert!(!pubkey.is_null());
+        &*pubkey
+    };
+    let signature = unsafe {
+        assert!(!signature.is_null());
+        &*signature
+    };
+
+    let message = message_from_c(message, message_len);
+    match offchain_message_bytes(version, message) {
+        Ok(bytes) => signature
+            .to_signature()
+            .verify(pubkey.to_pubkey().as_ref(), &bytes),
+        Err(err) => {
+            eprintln!("{}", err);
+            false
+        }
+    }
+}
+
+// Write the exact bytes that get signed for an off-chain message.
+// Returns the serialized length (call with a NULL buffer to query it) or 0 on error.
+#[no_mangle]
+pub extern "C" fn serialize_offchain_message(
+    version: u8,
+    message: *const u8,
+    message_len: usize,
+    buffer_out: *mut u8,
+    buffer_out_len: usize,
+) -> usize {
+    let message = message_from_c(message, message_len);
+    let bytes = match offchain_message_bytes(version, message) {
+        Ok(bytes) => bytes,
+        Err(err) => {
+            eprintln!("{}", err);
+            return 0;
+        }
+    };
+
+    if !buffer_out.is_null() {
+        if buffer_out_len < bytes.len() {
+            eprintln!("Off-chain message buffer too small");
+            return 0;
+        }
+        unsafe { std::ptr::copy_nonoverlapping(bytes.as_ptr(), buffer_out, bytes.len()) };
+    }
+    bytes.len()
+}
+
+// ==================== Signature Encoding ==================== //
+
+#[no_mangle]
+pub extern "C" fn get_signature_string(signature: *const SolSignature) -> *mut c_char {
+    let signature = unsafe {
+        assert!(!signature.is_null());
+        &*signature
+    };
+
+    match CString::new(signature.to_signature().to_string()) {
+        Ok(c_str) => c_str.into_raw(),
+        Err(_) => std::ptr::null_mut(),
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn get_signature_from_string(
+    signature: *const c_char,
+    signature_out: *mut SolSignature,
+) -> bool {
+    let c_str = unsafe {
+        assert!(!signature.is_null());
+        CStr::from_ptr(signature)
+    };
+
+    match c_str
+        .to_str()
+        .ok()
+        .and_then(|s| Signature::from_str(s).ok())
+    {
+        Some(signature) => write_signature(signature, signature_out),
+        None => false,
+    }
+}
@@ -0,0 +1,275 @@
+use bip39::{ErrorKind, Language, Mnemonic, MnemonicType, Seed};
+use solana_sdk::derivation_path::DerivationPath;
+use solana_sdk::signature::{keypair_from_seed, keypair_from_seed_and_derivation_path, Keypair};
+use std::ffi::CStr;
+use std::os::raw::c_char;
+use zeroize::Zeroize;
+
+use crate::wallet::SolKeyPair;
+
+// Derivation path conventions used by common Solana wallets
+#[repr(C)]
+#[derive(Clone, Copy)]
+#[allow(dead_code)] // Variants are constructed on the C side
+pub enum SolDerivationScheme {
+    // `solana-keygen recover` default: first 32 bytes of the BIP39 seed, no derivation
+    SolanaCli,
+    // Phantom / Solflare: m/44'/501'/{account}'/0'
+    Phantom,
+    // Ledger live: m/44'/501'/{account}'
+    Ledger,
+    // Root BIP44 Solana path: m/44'/501'
+    Bip44Root,
+}
+
+#[repr(C)]
+#[derive(Debug, PartialEq, Eq)]
+pub enum SolMnemonicStatus {
+    Valid,
+    InvalidWordCount,
+    InvalidWord,
+    InvalidChecksum,
+    InvalidEncoding,
+}
+
+// ==================== Helpers ==================== //
+
+// Copy a string into a caller-owned, NUL-terminated buffer
+fn copy_to_c_buffer(value: &str, buffer: *mut c_char, buffer_len: usize) -> bool {
+    if buffer.is_null() || value.len() + 1 > buffer_len {
+        return false;
+    }
+
+    unsafe {
+        std::ptr::copy_nonoverlapping(value.as_ptr(), buffer as *mut u8, value.len());
+        *buffer.add(value.len()) = 0;
+    }
+    true
+}
+
+// Read an optional C string; NULL is treated as an empty passphrase
+fn passphrase_from_c<'a>(passphrase: *const c_char) -> Option<&'a str> {
+    if passphrase.is_null() {
+        return Some("");
+    }
+    unsafe { CStr::from_ptr(passphrase) }.to_str().ok()
+}
+
+fn mnemonic_status(phrase: &str) -> SolMnemonicStatus {
+    match Mnemonic::validate(phrase, Language::English) {
+        Ok(_) => SolMnemonicStatus::Valid,
+        Err(err) => match err.downcast_ref::<ErrorKind>() {
+            Some(ErrorKind::InvalidChecksum) => SolMnemonicStatus::InvalidChecksum,
+            Some(ErrorKind::InvalidWord) => SolMnemonicStatus::InvalidWord,
+            Some(ErrorKind::InvalidWordLength(_)) => SolMnemonicStatus::InvalidWordCount,
+            _ => SolMnemonicStatus::InvalidEncoding,
+        },
+    }
+}
+
+fn derivation_path_for(scheme: SolDerivationScheme, account_index: u32) -> Option<DerivationPath> {
+    match scheme {
+        SolDerivationScheme::SolanaCli => None,
+        SolDerivationScheme::Phantom => {
+            Some(DerivationPath::new_bip44(Some(account_index), Some(0)))
+        }
+        SolDerivationScheme::Ledger => Some(DerivationPath::new_bip44(Some(account_index), None)),
+        SolDerivationScheme::Bip44Root => Some(DerivationPath::new_bip44(None, None)),
+    }
+}
+
+// Derive a keypair from a mnemonic, passphrase and optional derivation path
+pub fn keypair_from_mnemonic(
+    phrase: &str,
+    passphrase: &str,
+    derivation_path: Option<DerivationPath>,
+) -> Result<Keypair, String> {
+    let mnemonic = Mnemonic::from_phrase(phrase, Language::English)
+        .map_err(|err| format!("Invalid mnemonic: {}", err))?;
+    let seed = Seed::new(&mnemonic, passphrase);
+
+    match derivation_path {
+        Some(path) => keypair_from_seed_and_derivation_path(seed.as_bytes(), Some(path)),
+        None => keypair_from_seed(seed.as_bytes()),
+    }
+    .map_err(|err| format!("Failed to derive keypair: {}", err))
+}
+
+// ==================== Mnemonic Functions ==================== //
+
+// Generate a new English mnemonic of 12, 15, 18, 21 or 24 words into the caller's buffer
+#[no_mangle]
+pub extern "C" fn generate_mnemonic(
+    word_count: u32,
+    phrase_out: *mut c_char,
+    phrase_out_len: usize,
+) -> bool {
+    let mnemonic_type = match MnemonicType::for_word_count(word_count as usize) {
+        Ok(mnemonic_type) => mnemonic_type,
+        Err(err) => {
+            eprintln!("Invalid mnemonic word count {}: {}", word_count, err);
+            return false;
+        }
+    };
+
+    let mnemonic = Mnemonic::new(mnemonic_type, Language::English);
+    if !copy_to_c_buffer(mnemonic.phrase(), phrase_out, phrase_out_len) {
+        eprintln!("Mnemonic buffer too small");
+        return false;
+    }
+    true
+}
+
+#[no_mangle]
+pub extern "C" fn validate_mnemonic(phrase: *const c_char) -> SolMnemonicStatus {
+    let c_str = unsafe {
+        assert!(!phrase.is_null());
+        CStr::from_ptr(phrase)
+    };
+
+    match c_str.to_str() {
+        Ok(phrase) => mnemonic_status(phrase),
+        Err(_) => SolMnemonicStatus::InvalidEncoding,
+    }
+}
+
+// Generate a mnemonic and return the wallet at the Phantom path m/44'/501'/0'/0'.
+// The phrase is written into the caller's buffer so the caller controls when it is wiped.
+#[no_mangle]
+pub extern "C" fn create_wallet_with_mnemonic(
+    word_count: u32,
+    passphrase: *const c_char,
+    phrase_out: *mut c_char,
+    phrase_out_len: usize,
+) -> *mut SolKeyPair {
+    let passphrase = match passphrase_from_c(passphrase) {
+        Some(passphrase) => passphrase,
+        None => return std::ptr::null_mut(),
+    };
+
+    let mnemonic_type = match MnemonicType::for_word_count(word_count as usize) {
+        Ok(mnemonic_type) => mnemonic_type,
+        Err(err) => {
+            eprintln!("Invalid mnemonic word count {}: {}", word_count, err);
+            return std::ptr::null_mut();
+        }
+    };
+    let mnemonic = Mnemonic::new(mnemonic_type, Language::English);
+
+    let derivation_path = derivation_path_for(SolDerivationScheme::Phantom, 0);
+    let keypair = match keypair_from_mnemonic(mnemonic.phrase(), passphrase, derivation_path) {
+        Ok(keypair) => keypair,
+        Err(err) => {
+            eprintln!("{}", err);
+            return std::ptr::null_mut();
+        }
+    };
+
+    if !copy_to_c_buffer(mnemonic.phrase(), phrase_out, phrase_out_len) {
+        eprintln!("Mnemonic buffer too small");
+        return std::ptr::null_mut();
+    }
+
+    Box::into_raw(Box::new(SolKeyPair::from_existing(keypair)))
+}
+
+// Restore a wallet at the BIP44 path m/44'/501'/{account_index}'/{change}'
+#[no_mangle]
+pub extern "C" fn wallet_from_mnemonic(
+    phrase: *const c_char,
+    passphrase: *const c_char,
+    account_index: u32,
+    change: u32,
+) -> *mut SolKeyPair {
+    let phrase = match unsafe { CStr::from_ptr(phrase) }.to_str() {
+        Ok(phrase) => phrase,
+        Err(_) => return std::ptr::null_mut(),
+    };
+    let passphrase = match passphrase_from_c(passphrase) {
+        Some(passphrase) => passphrase,
+        None => return std::ptr::null_mut(),
+    };
+
+    let derivation_path = DerivationPath::new_bip44(Some(account_index), Some(change));
+    match keypair_from_mnemonic(phrase, passphrase, Some(derivation_path)) {
+        Ok(keypair) => Box::into_raw(Box::new(SolKeyPair::from_existing(keypair))),
+        Err(err) => {
+            eprintln!("{}", err);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+// Restore a wallet using one of the derivation conventions of common wallets
+#[no_mangle]
+pub extern "C" fn wallet_from_mnemonic_with_scheme(
+    phrase: *const c_char,
+    passphrase: *const c_char,
+    scheme: SolDerivationScheme,
+    account_index: u32,
+) -> *mut SolKeyPair {
+    let phrase = match unsafe { CStr::from_ptr(phrase) }.to_str() {
+        Ok(phrase) => phrase,
+        Err(_) => return std::ptr::null_mut(),
+    };
+    let passphrase = match passphrase_from_c(passphrase) {
+        Some(passphrase) => passphrase,
+        None => return std::ptr::null_mut(),
+    };
+
+    let derivation_path = derivation_path_for(scheme, account_index);
+    match keypair_from_mnemonic(phrase, passphrase, derivation_path) {
+        Ok(keypair) => Box::into_raw(Box::new(SolKeyPair::from_existing(keypair))),
+        Err(err) => {
+            eprintln!("{}", err);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+// Restore a wallet from an explicit derivation path such as "m/44'/501'/3'/0'"
+#[no_mangle]
+pub extern "C" fn wallet_from_mnemonic_with_path(
+    phrase: *const c_char,
+    passphrase: *const c_char,
+    derivation_path: *const c_char,
+) -> *mut SolKeyPair {
+    let phrase = match unsafe { CStr::from_ptr(phrase) }.to_str() {
+        Ok(phrase) => phrase,
+        Err(_) => return std::ptr::null_mut(),
+    };
+    let passphrase = match passphrase_from_c(passphrase) {
+        Some(passphrase) => passphrase,
+        None => return std::ptr::null_mut(),
+    };
+    let derivation_path = match unsafe { CStr::from_ptr(derivation_path) }.to_str() {
+        Ok(path) => path,
+        Err(_) => return std::ptr::null_mut(),
+    };
+
+    let derivation_path = match DerivationPath::from_absolute_path_str(derivation_path) {
+        Ok(path) => path,
+        Err(err) => {
+            eprintln!("Invalid derivation path {}: {:?}", derivation_path, err);
+            return std::ptr::null_mut();
+        }
+    };
+
+    match keypair_from_mnemonic(phrase, passphrase, Some(derivation_path)) {
+        Ok(keypair) => Box::into_raw(Box::new(SolKeyPair::from_existing(keypair))),
+        Err(err) => {
+            eprintln!("{}", err);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+// Overwrite a phrase buffer previously filled by the SDK
+#[no_mangle]
+pub extern "C" fn wipe_mnemonic_buffer(phrase: *mut c_char, phrase_len: usize) {
+    if phrase.is_null() {
+        return;
+    }
+    let buffer = unsafe { std::slice::from_raw_parts_mut(phrase as *mut u8, phrase_len) };
+    buffer.zeroize();
+}
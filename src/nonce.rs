@@ -0,0 +1,277 @@
+use solana_sdk::nonce::state::{Data, State, Versions};
+use solana_sdk::nonce::State as NonceState;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signer;
+use solana_sdk::system_instruction;
+use solana_sdk::system_program;
+use std::ffi::CString;
+use std::os::raw::c_char;
+
+use crate::client::SolClient;
+use crate::transaction::{send_instructions, SolTransactionResult};
+use crate::wallet::{SolKeyPair, SolPublicKey};
+
+// Nonce account state; release with `free_nonce_account`
+#[repr(C)]
+pub struct SolNonceAccount {
+    pub address: SolPublicKey,
+    pub authority: SolPublicKey,
+    pub nonce: *mut c_char, // Stored blockhash, base58 encoded
+    pub lamports_per_signature: u64,
+    pub lamports: u64,
+}
+
+// ==================== Helpers ==================== //
+
+// Fetch an initialized nonce account and its balance
+pub fn fetch_nonce_account(client: &SolClient, address: &Pubkey) -> Result<(Data, u64), String> {
+    let account = client
+        .rpc_client
+        .get_account(address)
+        .map_err(|err| format!("Failed to fetch nonce account {}: {:?}", address, err))?;
+    if account.owner != system_program::ID {
+        return Err(format!("Account {} is not a nonce account", address));
+    }
+
+    let versions: Versions = bincode::deserialize(&account.data)
+        .map_err(|err| format!("Invalid nonce account {}: {}", address, err))?;
+    match versions.state() {
+        State::Initialized(data) => Ok((data.clone(), account.lamports)),
+        State::Uninitialized => Err(format!("Nonce account {} is not initialized", address)),
+    }
+}
+
+// ==================== Nonce Account Functions ==================== //
+
+// Create and initialize a nonce account controlled by `authority`. A `lamports`
+// of 0 funds the account with the rent-exempt minimum.
+#[no_mangle]
+pub extern "C" fn create_nonce_account(
+    client: *mut SolClient,
+    payer: *mut SolKeyPair,
+    nonce_account: *mut SolKeyPair,
+    authority: *const SolPublicKey,
+    lamports: u64,
+) -> *mut SolTransactionResult {
+    let client = unsafe {
+        assert!(!client.is_null());
+        &*client
+    };
+    let payer = unsafe {
+        assert!(!payer.is_null());
+        &*payer
+    }
+    .to_keypair();
+    let nonce_account = unsafe {
+        assert!(!nonce_account.is_null());
+        &*nonce_account
+    }
+    .to_keypair();
+    let authority = unsafe {
+        assert!(!authority.is_null());
+        &*authority
+    };
+
+    let lamports = if lamports == 0 {
+        match client
+            .rpc_client
+            .get_minimum_balance_for_rent_exemption(NonceState::size())
+        {
+            Ok(lamports) => lamports,
+            Err(err) => {
+                return SolTransactionResult::failed(format!(
+                    "Failed to get rent exemption: {:?}",
+                    err
+                ))
+                .into_raw()
+            }
+        }
+    } else {
+        lamports
+    };
+
+    let instructions = system_instruction::create_nonce_account(
+        &payer.pubkey(),
+        &nonce_account.pubkey(),
+        &authority.to_pubkey(),
+        lamports,
+    );
+    send_instructions(
+        client,
+        &instructions,
+        &payer.pubkey(),
+        &[&payer, &nonce_account],
+    )
+    .into_raw()
+}
+
+// Fetch the stored nonce, its authority and the account balance
+#[no_mangle]
+pub extern "C" fn get_nonce_account(
+    client: *mut SolClient,
+    nonce_account: *const SolPublicKey,
+) -> *mut SolNonceAccount {
+    let client = unsafe {
+        assert!(!client.is_null());
+        &*client
+    };
+    let address = unsafe {
+        assert!(!nonce_account.is_null());
+        &*nonce_account
+    }
+    .to_pubkey();
+
+    let (data, lamports) = match fetch_nonce_account(client, &address) {
+        Ok(account) => account,
+        Err(err) => {
+            eprintln!("{}", err);
+            return std::ptr::null_mut();
+        }
+    };
+
+    Box::into_raw(Box::new(SolNonceAccount {
+        address: SolPublicKey::new(address),
+        authority: SolPublicKey::new(data.authority),
+        nonce: CString::new(data.blockhash().to_string())
+            .unwrap()
+            .into_raw(),
+        lamports_per_signature: data.get_lamports_per_signature(),
+        lamports,
+    }))
+}
+
+// Replace the stored nonce; transactions signed with the old one can no longer land
+#[no_mangle]
+pub extern "C" fn advance_nonce_account(
+    client: *mut SolClient,
+    nonce_account: *const SolPublicKey,
+    authority: *mut SolKeyPair,
+    fee_payer: *mut SolKeyPair, // NULL means the authority pays the fee
+) -> *mut SolTransactionResult {
+    let client = unsafe {
+        assert!(!client.is_null());
+        &*client
+    };
+    let nonce_account = unsafe {
+        assert!(!nonce_account.is_null());
+        &*nonce_account
+    };
+    let authority = unsafe {
+        assert!(!authority.is_null());
+        &*authority
+    }
+    .to_keypair();
+
+    let instruction =
+        system_instruction::advance_nonce_account(&nonce_account.to_pubkey(), &authority.pubkey());
+    let fee_payer = unsafe { fee_payer.as_ref() }.map(SolKeyPair::to_keypair);
+    let fee_payer = fee_payer.as_ref().unwrap_or(&authority);
+    send_instructions(
+        client,
+        &[instruction],
+        &fee_payer.pubkey(),
+        &[fee_payer, &authority],
+    )
+    .into_raw()
+}
+
+// Withdraw lamports; withdrawing the whole balance closes the account
+#[no_mangle]
+pub extern "C" fn withdraw_nonce_account(
+    client: *mut SolClient,
+    nonce_account: *const SolPublicKey,
+    authority: *mut SolKeyPair,
+    recipient: *const SolPublicKey,
+    lamports: u64,
+    fee_payer: *mut SolKeyPair, // NULL means the authority pays the fee
+) -> *mut SolTransactionResult {
+    let client = unsafe {
+        assert!(!client.is_null());
+        &*client
+    };
+    let nonce_account = unsafe {
+        assert!(!nonce_account.is_null());
+        &*nonce_account
+    };
+    let authority = unsafe {
+        assert!(!authority.is_null());
+        &*authority
+    }
+    .to_keypair();
+    let recipient = unsafe {
+        assert!(!recipient.is_null());
+        &*recipient
+    };
+
+    let instruction = system_instruction::withdraw_nonce_account(
+        &nonce_account.to_pubkey(),
+        &authority.pubkey(),
+        &recipient.to_pubkey(),
+        lamports,
+    );
+    let fee_payer = unsafe { fee_payer.as_ref() }.map(SolKeyPair::to_keypair);
+    let fee_payer = fee_payer.as_ref().unwrap_or(&authority);
+    send_instructions(
+        client,
+        &[instruction],
+        &fee_payer.pubkey(),
+        &[fee_payer, &authority],
+    )
+    .into_raw()
+}
+
+// Hand control of the nonce account to `new_authority`
+#[no_mangle]
+pub extern "C" fn authorize_nonce_account(
+    client: *mut SolClient,
+    nonce_account: *const SolPublicKey,
+    authority: *mut SolKeyPair,
+    new_authority: *const SolPublicKey,
+    fee_payer: *mut SolKeyPair, // NULL means the authority pays the fee
+) -> *mut SolTransactionResult {
+    let client = unsafe {
+        assert!(!client.is_null());
+        &*client
+    };
+    let nonce_account = unsafe {
+        assert!(!nonce_account.is_null());
+        &*nonce_account
+    };
+    let authority = unsafe {
+        assert!(!authority.is_null());
+        &*authority
+    }
+    .to_keypair();
+    let new_authority = unsafe {
+        assert!(!new_authority.is_null());
+        &*new_authority
+    };
+
+    let instruction = system_instruction::authorize_nonce_account(
+        &nonce_account.to_pubkey(),
+        &authority.pubkey(),
+        &new_authority.to_pubkey(),
+    );
+    let fee_payer = unsafe { fee_payer.as_ref() }.map(SolKeyPair::to_keypair);
+    let fee_payer = fee_payer.as_ref().unwrap_or(&authority);
+    send_instructions(
+        client,
+        &[instruction],
+        &fee_payer.pubkey(),
+        &[fee_payer, &authority],
+    )
+    .into_raw()
+}
+
+#[no_mangle]
+pub extern "C" fn free_nonce_account(nonce_account: *mut SolNonceAccount) {
+    if nonce_account.is_null() {
+        return;
+    }
+    unsafe {
+        let nonce_account = Box::from_raw(nonce_account);
+        if !nonce_account.nonce.is_null() {
+            drop(CString::from_raw(nonce_account.nonce));
+        }
+    }
+}
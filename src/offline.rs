@@ -0,0 +1,409 @@
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use solana_sdk::bs58;
+use solana_sdk::hash::Hash;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use solana_sdk::transaction::VersionedTransaction;
+use std::ffi::{CStr, CString};
+use std::os::raw::c_char;
+use std::str::FromStr;
+
+use crate::client::SolClient;
+use crate::program_error::send_failed;
+use crate::signer::{partial_sign, required_signers, SolSigner};
+use crate::transaction::{SolTransactionBuilder, SolTransactionResult};
+use crate::wallet::{SolKeyPair, SolPublicKey};
+
+// A transaction that can be passed between parties and signed piece by piece
+pub struct SolTransaction {
+    pub transaction: VersionedTransaction,
+}
+
+impl SolTransaction {
+    pub fn into_raw(transaction: VersionedTransaction) -> *mut SolTransaction {
+        Box::into_raw(Box::new(SolTransaction { transaction }))
+    }
+
+    // Required signers whose signature slot is still empty
+    pub fn missing_signers(&self) -> Vec<Pubkey> {
+        required_signers(&self.transaction)
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| {
+                self.transaction
+                    .signatures
+                    .get(*index)
+                    .is_none_or(|signature| *signature == Signature::default())
+            })
+            .map(|(_, pubkey)| *pubkey)
+            .collect()
+    }
+
+    // Every signature present is valid for its signer
+    pub fn verify_signatures(&self) -> bool {
+        let message = self.transaction.message.serialize();
+        required_signers(&self.transaction)
+            .iter()
+            .zip(&self.transaction.signatures)
+            .filter(|(_, signature)| **signature != Signature::default())
+            .all(|(pubkey, signature)| signature.verify(pubkey.as_ref(), &message))
+    }
+}
+
+#[repr(C)]
+pub struct SolPublicKeyList {
+    data: *mut SolPublicKey,
+    len: usize,
+}
+
+// Decode a wire-format transaction, legacy or v0
+pub fn deserialize_transaction(bytes: &[u8]) -> Result<VersionedTransaction, String> {
+    let transaction: VersionedTransaction =
+        bincode::deserialize(bytes).map_err(|err| format!("Invalid transaction: {}", err))?;
+    transaction
+        .sanitize()
+        .map_err(|err| format!("Invalid transaction: {}", err))?;
+    Ok(transaction)
+}
+
+fn transaction_from_bytes(result: Result<Vec<u8>, String>) -> *mut SolTransaction {
+    match result.and_then(|bytes| deserialize_transaction(&bytes)) {
+        Ok(transaction) => SolTransaction::into_raw(transaction),
+        Err(err) => {
+            eprintln!("{}", err);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+fn serialize_transaction(transaction: &SolTransaction) -> Option<Vec<u8>> {
+    match bincode::serialize(&transaction.transaction) {
+        Ok(bytes) => Some(bytes),
+        Err(err) => {
+            eprintln!("Failed to serialize transaction: {}", err);
+            None
+        }
+    }
+}
+
+// ==================== Unsigned Transactions ==================== //
+
+// Build with a fresh blockhash (or the builder's durable nonce) without signing
+#[no_mangle]
+pub extern "C" fn sol_transaction_builder_build_unsigned(
+    builder: *const SolTransactionBuilder,
+    client: *mut SolClient,
+) -> *mut SolTransaction {
+    let builder = unsafe {
+        assert!(!builder.is_null());
+        &*builder
+    };
+    let client = unsafe {
+        assert!(!client.is_null());
+        &*client
+    };
+
+    match builder.build_unsigned_with_client(client) {
+        Ok(transaction) => SolTransaction::into_raw(transaction),
+        Err(err) => {
+            eprintln!("{}", err);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+// Build fully offline against a known blockhash or stored nonce (base58).
+// Builders with lookup tables need `sol_transaction_builder_build_unsigned`.
+#[no_mangle]
+pub extern "C" fn sol_transaction_builder_build_unsigned_with_blockhash(
+    builder: *const SolTransactionBuilder,
+    blockhash: *const c_char,
+) -> *mut SolTransaction {
+    let builder = unsafe {
+        assert!(!builder.is_null());
+        &*builder
+    };
+    let blockhash = match unsafe { CStr::from_ptr(blockhash) }.to_str() {
+        Ok(str) => str,
+        Err(_) => return std::ptr::null_mut(),
+    };
+    let blockhash = match Hash::from_str(blockhash) {
+        Ok(blockhash) => blockhash,
+        Err(err) => {
+            eprintln!("Invalid blockhash: {}", err);
+            return std::ptr::null_mut();
+        }
+    };
+    if builder.has_lookup_tables() {
+        eprintln!("Lookup tables can only be resolved with a client");
+        return std::ptr::null_mut();
+    }
+
+    match builder.build_unsigned(blockhash, &[]) {
+        Ok(transaction) => SolTransaction::into_raw(transaction),
+        Err(err) => {
+            eprintln!("{}", err);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+// ==================== Serialization ==================== //
+
+// Write the wire format. Returns the serialized length (call with a NULL
+// buffer to query it) or 0 on error.
+#[no_mangle]
+pub extern "C" fn sol_transaction_serialize(
+    transaction: *const SolTransaction,
+    buffer_out: *mut u8,
+    buffer_out_len: usize,
+) -> usize {
+    let transaction = unsafe {
+        assert!(!transaction.is_null());
+        &*transaction
+    };
+    let bytes = match serialize_transaction(transaction) {
+        Some(bytes) => bytes,
+        None => return 0,
+    };
+
+    if !buffer_out.is_null() {
+        if buffer_out_len < bytes.len() {
+            eprintln!("Transaction buffer too small");
+            return 0;
+        }
+        unsafe { std::ptr::copy_nonoverlapping(bytes.as_ptr(), buffer_out, bytes.len()) };
+    }
+    bytes.len()
+}
+
+#[no_mangle]
+pub extern "C" fn sol_transaction_to_base64(transaction: *const SolTransaction) -> *mut c_char {
+    let transaction = unsafe {
+        assert!(!transaction.is_null());
+        &*transaction
+    };
+
+    match serialize_transaction(transaction) {
+        Some(bytes) => CString::new(BASE64.encode(bytes)).unwrap().into_raw(),
+        None => std::ptr::null_mut(),
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn sol_transaction_to_base58(transaction: *const SolTransaction) -> *mut c_char {
+    let transaction = unsafe {
+        assert!(!transaction.is_null());
+        &*transaction
+    };
+
+    match serialize_transaction(transaction) {
+        Some(bytes) => CString::new(bs58::encode(bytes).into_string())
+            .unwrap()
+            .into_raw(),
+        None => std::ptr::null_mut(),
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn sol_transaction_from_bytes(
+    data: *const u8,
+    data_len: usize,
+) -> *mut SolTransaction {
+    if data.is_null() {
+        return std::ptr::null_mut();
+    }
+    let data = unsafe { std::slice::from_raw_parts(data, data_len) };
+
+    transaction_from_bytes(Ok(data.to_vec()))
+}
+
+#[no_mangle]
+pub extern "C" fn sol_transaction_from_base64(encoded: *const c_char) -> *mut SolTransaction {
+    let encoded = match unsafe { CStr::from_ptr(encoded) }.to_str() {
+        Ok(str) => str.trim(),
+        Err(_) => return std::ptr::null_mut(),
+    };
+
+    transaction_from_bytes(
+        BASE64
+            .decode(encoded)
+            .map_err(|err| format!("Invalid base64 transaction: {}", err)),
+    )
+}
+
+#[no_mangle]
+pub extern "C" fn sol_transaction_from_base58(encoded: *const c_char) -> *mut SolTransaction {
+    let encoded = match unsafe { CStr::from_ptr(encoded) }.to_str() {
+        Ok(str) => str.trim(),
+        Err(_) => return std::ptr::null_mut(),
+    };
+
+    transaction_from_bytes(
+        bs58::decode(encoded)
+            .into_vec()
+            .map_err(|err| format!("Invalid base58 transaction: {}", err)),
+    )
+}
+
+// ==================== Partial Signing ==================== //
+
+// Add the wallet's signature; the wallet must be a required signer
+#[no_mangle]
+pub extern "C" fn sol_transaction_partial_sign(
+    transaction: *mut SolTransaction,
+    wallet: *mut SolKeyPair,
+) -> bool {
+    let transaction = unsafe {
+        assert!(!transaction.is_null());
+        &mut *transaction
+    };
+    let wallet = unsafe {
+        assert!(!wallet.is_null());
+        &*wallet
+    };
+
+    match partial_sign(&mut transaction.transaction, &wallet.to_keypair()) {
+        Ok(()) => true,
+        Err(err) => {
+            eprintln!("{}", err);
+            false
+        }
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn sol_transaction_partial_sign_with_signer(
+    transaction: *mut SolTransaction,
+    signer: *const SolSigner,
+) -> bool {
+    let transaction = unsafe {
+        assert!(!transaction.is_null());
+        &mut *transaction
+    };
+    let signer = unsafe {
+        assert!(!signer.is_null());
+        &*signer
+    };
+
+    match partial_sign(&mut transaction.transaction, signer) {
+        Ok(()) => true,
+        Err(err) => {
+            eprintln!("{}", err);
+            false
+        }
+    }
+}
+
+// Required signers that have not signed yet, in signature order
+#[no_mangle]
+pub extern "C" fn sol_transaction_missing_signers(
+    transaction: *const SolTransaction,
+) -> *mut SolPublicKeyList {
+    let transaction = unsafe {
+        assert!(!transaction.is_null());
+        &*transaction
+    };
+
+    let signers: Box<[SolPublicKey]> = transaction
+        .missing_signers()
+        .into_iter()
+        .map(SolPublicKey::new)
+        .collect();
+    let len = signers.len();
+    Box::into_raw(Box::new(SolPublicKeyList {
+        data: Box::into_raw(signers) as *mut SolPublicKey,
+        len,
+    }))
+}
+
+// True when every signature present is valid; missing signatures are not errors
+#[no_mangle]
+pub extern "C" fn sol_transaction_verify_signatures(transaction: *const SolTransaction) -> bool {
+    let transaction = unsafe {
+        assert!(!transaction.is_null());
+        &*transaction
+    };
+
+    transaction.verify_signatures()
+}
+
+// Send a fully signed transaction and wait for confirmation
+#[no_mangle]
+pub extern "C" fn sol_transaction_send(
+    transaction: *const SolTransaction,
+    client: *mut SolClient,
+) -> *mut SolTransactionResult {
+    let transaction = unsafe {
+        assert!(!transaction.is_null());
+        &*transaction
+    };
+    let client = unsafe {
+        assert!(!client.is_null());
+        &*client
+    };
+
+    let missing = transaction.missing_signers();
+    if !missing.is_empty() {
+        let missing: Vec<String> = missing.iter().map(Pubkey::to_string).collect();
+        return SolTransactionResult::failed(format!(
+            "Transaction is missing signatures from {}",
+            missing.join(", ")
+        ))
+        .into_raw();
+    }
+    if !transaction.verify_signatures() {
+        return SolTransactionResult::failed("Transaction has an invalid signature").into_raw();
+    }
+
+    match client
+        .rpc_client
+        .send_and_confirm_transaction(&transaction.transaction)
+    {
+        Ok(signature) => SolTransactionResult::sent(&signature).into_raw(),
+        Err(err) => send_failed(client, &transaction.transaction, &err).into_raw(),
+    }
+}
+
+// ==================== Public Key Lists ==================== //
+
+#[no_mangle]
+pub extern "C" fn public_key_list_get_data(list: *const SolPublicKeyList) -> *mut SolPublicKey {
+    if list.is_null() {
+        std::ptr::null_mut()
+    } else {
+        unsafe { (*list).data }
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn public_key_list_get_len(list: *const SolPublicKeyList) -> usize {
+    if list.is_null() {
+        0
+    } else {
+        unsafe { (*list).len }
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn free_public_key_list(list: *mut SolPublicKeyList) {
+    if list.is_null() {
+        return;
+    }
+    unsafe {
+        let list = Box::from_raw(list);
+        drop(Box::from_raw(std::ptr::slice_from_raw_parts_mut(
+            list.data, list.len,
+        )));
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn free_sol_transaction(transaction: *mut SolTransaction) {
+    if transaction.is_null() {
+        return;
+    }
+    unsafe {
+        drop(Box::from_raw(transaction));
+    }
+}
@@ -0,0 +1,174 @@
+use solana_program::pubkey::{Pubkey, MAX_SEEDS, MAX_SEED_LEN};
+use std::ffi::CStr;
+use std::os::raw::c_char;
+
+use crate::wallet::SolPublicKey;
+
+// ==================== Helpers ==================== //
+
+// Collect `seed_count` (pointer, length) pairs into seed slices
+pub fn seeds_from_c<'a>(
+    seeds: *const *const u8,
+    seed_lens: *const usize,
+    seed_count: usize,
+) -> Result<Vec<&'a [u8]>, String> {
+    if seed_count == 0 {
+        return Ok(Vec::new());
+    }
+    if seeds.is_null() || seed_lens.is_null() {
+        return Err("Seeds and seed lengths must not be NULL".to_string());
+    }
+    if seed_count > MAX_SEEDS {
+        return Err(format!(
+            "Too many seeds: {} (max {})",
+            seed_count, MAX_SEEDS
+        ));
+    }
+
+    let seeds = unsafe { std::slice::from_raw_parts(seeds, seed_count) };
+    let seed_lens = unsafe { std::slice::from_raw_parts(seed_lens, seed_count) };
+
+    seeds
+        .iter()
+        .zip(seed_lens)
+        .map(|(&seed, &len)| {
+            if len > MAX_SEED_LEN {
+                Err(format!(
+                    "Seed too long: {} bytes (max {})",
+                    len, MAX_SEED_LEN
+                ))
+            } else if len == 0 {
+                Ok(&[][..])
+            } else if seed.is_null() {
+                Err("Seed must not be NULL".to_string())
+            } else {
+                Ok(unsafe { std::slice::from_raw_parts(seed, len) })
+            }
+        })
+        .collect()
+}
+
+fn write_pubkey(pubkey: Pubkey, pubkey_out: *mut SolPublicKey) -> bool {
+    if pubkey_out.is_null() {
+        return false;
+    }
+    unsafe { *pubkey_out = SolPublicKey::new(pubkey) };
+    true
+}
+
+// ==================== Address Derivation ==================== //
+
+// Find the canonical program derived address and its bump seed
+#[no_mangle]
+pub extern "C" fn find_program_address_c(
+    seeds: *const *const u8,
+    seed_lens: *const usize,
+    seed_count: usize,
+    program_id: *const SolPublicKey,
+    pubkey_out: *mut SolPublicKey,
+    bump_out: *mut u8,
+) -> bool {
+    let program_id = unsafe {
+        assert!(!program_id.is_null());
+        &*program_id
+    };
+
+    let seeds = match seeds_from_c(seeds, seed_lens, seed_count) {
+        Ok(seeds) => seeds,
+        Err(err) => {
+            eprintln!("{}", err);
+            return false;
+        }
+    };
+    // Leave room for the bump seed appended during the search
+    if seeds.len() >= MAX_SEEDS {
+        eprintln!("Too many seeds: {} (max {})", seeds.len(), MAX_SEEDS - 1);
+        return false;
+    }
+
+    match Pubkey::try_find_program_address(&seeds, &program_id.to_pubkey()) {
+        Some((pubkey, bump)) => {
+            if !bump_out.is_null() {
+                unsafe { *bump_out = bump };
+            }
+            write_pubkey(pubkey, pubkey_out)
+        }
+        None => {
+            eprintln!("Unable to find a viable program address bump seed");
+            false
+        }
+    }
+}
+
+// Derive a program address from seeds that already include the bump
+#[no_mangle]
+pub extern "C" fn create_program_address_c(
+    seeds: *const *const u8,
+    seed_lens: *const usize,
+    seed_count: usize,
+    program_id: *const SolPublicKey,
+    pubkey_out: *mut SolPublicKey,
+) -> bool {
+    let program_id = unsafe {
+        assert!(!program_id.is_null());
+        &*program_id
+    };
+
+    let seeds = match seeds_from_c(seeds, seed_lens, seed_count) {
+        Ok(seeds) => seeds,
+        Err(err) => {
+            eprintln!("{}", err);
+            return false;
+        }
+    };
+
+    match Pubkey::create_program_address(&seeds, &program_id.to_pubkey()) {
+        Ok(pubkey) => write_pubkey(pubkey, pubkey_out),
+        Err(err) => {
+            eprintln!("Failed to create program address: {}", err);
+            false
+        }
+    }
+}
+
+// Derive an address from a base key, a string seed and an owner program
+// (as used by `SystemInstruction::CreateAccountWithSeed`)
+#[no_mangle]
+pub extern "C" fn create_with_seed_c(
+    base: *const SolPublicKey,
+    seed: *const c_char,
+    owner: *const SolPublicKey,
+    pubkey_out: *mut SolPublicKey,
+) -> bool {
+    let base = unsafe {
+        assert!(!base.is_null());
+        &*base
+    };
+    let owner = unsafe {
+        assert!(!owner.is_null());
+        &*owner
+    };
+    let seed = match unsafe { CStr::from_ptr(seed) }.to_str() {
+        Ok(str) => str,
+        Err(_) => return false,
+    };
+
+    match Pubkey::create_with_seed(&base.to_pubkey(), seed, &owner.to_pubkey()) {
+        Ok(pubkey) => write_pubkey(pubkey, pubkey_out),
+        Err(err) => {
+            eprintln!("Failed to create address with seed: {}", err);
+            false
+        }
+    }
+}
+
+// Program derived addresses are off the ed25519 curve, wallet addresses are on it
+#[no_mangle]
+pub extern "C" fn is_on_curve(pubkey: *const SolPublicKey) -> bool {
+    let pubkey = unsafe {
+        assert!(!pubkey.is_null());
+        &*pubkey
+    };
+
+    pubkey.to_pubkey().is_on_curve()
+}
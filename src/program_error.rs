@@ -0,0 +1,210 @@
+use solana_client::client_error::{ClientError, ClientErrorKind};
+use solana_client::rpc_request::{RpcError, RpcResponseErrorData};
+use solana_sdk::instruction::InstructionError;
+use solana_sdk::message::VersionedMessage;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::transaction::{TransactionError, VersionedTransaction};
+use std::fmt;
+use std::str::FromStr;
+
+use crate::client::SolClient;
+use crate::transaction::SolTransactionResult;
+
+// Error codes Anchor itself returns, below the 6000 where program errors start
+const ANCHOR_ERRORS: &[(u32, &str, &str)] = &[
+    (100, "InstructionMissing", "8 byte instruction identifier not provided"),
+    (101, "InstructionFallbackNotFound", "Fallback functions are not supported"),
+    (102, "InstructionDidNotDeserialize", "The program could not deserialize the given instruction"),
+    (103, "InstructionDidNotSerialize", "The program could not serialize the given instruction"),
+    (1000, "IdlInstructionStub", "The program was compiled without idl instructions"),
+    (1001, "IdlInstructionInvalidProgram", "Invalid program given to the IDL instruction"),
+    (1002, "IdlAccountNotEmpty", "IDL account must be empty in order to resize, try closing first"),
+    (1500, "EventInstructionStub", "The program was compiled without `event-cpi` feature"),
+    (2000, "ConstraintMut", "A mut constraint was violated"),
+    (2001, "ConstraintHasOne", "A has one constraint was violated"),
+    (2002, "ConstraintSigner", "A signer constraint was violated"),
+    (2003, "ConstraintRaw", "A raw constraint was violated"),
+    (2004, "ConstraintOwner", "An owner constraint was violated"),
+    (2005, "ConstraintRentExempt", "A rent exemption constraint was violated"),
+    (2006, "ConstraintSeeds", "A seeds constraint was violated"),
+    (2007, "ConstraintExecutable", "An executable constraint was violated"),
+    (2008, "ConstraintState", "Deprecated Error, feel free to replace with something else"),
+    (2009, "ConstraintAssociated", "An associated constraint was violated"),
+    (2010, "ConstraintAssociatedInit", "An associated init constraint was violated"),
+    (2011, "ConstraintClose", "A close constraint was violated"),
+    (2012, "ConstraintAddress", "An address constraint was violated"),
+    (2013, "ConstraintZero", "Expected zero account discriminant"),
+    (2014, "ConstraintTokenMint", "A token mint constraint was violated"),
+    (2015, "ConstraintTokenOwner", "A token owner constraint was violated"),
+    (2016, "ConstraintMintMintAuthority", "A mint mint authority constraint was violated"),
+    (2017, "ConstraintMintFreezeAuthority", "A mint freeze authority constraint was violated"),
+    (2018, "ConstraintMintDecimals", "A mint decimals constraint was violated"),
+    (2019, "ConstraintSpace", "A space constraint was violated"),
+    (2020, "ConstraintAccountIsNone", "A required account for the constraint is None"),
+    (2021, "ConstraintTokenTokenProgram", "A token account token program constraint was violated"),
+    (2022, "ConstraintMintTokenProgram", "A mint token program constraint was violated"),
+    (2023, "ConstraintAssociatedTokenTokenProgram", "An associated token account token program constraint was violated"),
+    (2500, "RequireViolated", "A require expression was violated"),
+    (2501, "RequireEqViolated", "A require_eq expression was violated"),
+    (2502, "RequireKeysEqViolated", "A require_keys_eq expression was violated"),
+    (2503, "RequireNeqViolated", "A require_neq expression was violated"),
+    (2504, "RequireKeysNeqViolated", "A require_keys_neq expression was violated"),
+    (2505, "RequireGtViolated", "A require_gt expression was violated"),
+    (2506, "RequireGteViolated", "A require_gte expression was violated"),
+    (3000, "AccountDiscriminatorAlreadySet", "The account discriminator was already set on this account"),
+    (3001, "AccountDiscriminatorNotFound", "No 8 byte discriminator was found on the account"),
+    (3002, "AccountDiscriminatorMismatch", "8 byte discriminator did not match what was expected"),
+    (3003, "AccountDidNotDeserialize", "Failed to deserialize the account"),
+    (3004, "AccountDidNotSerialize", "Failed to serialize the account"),
+    (3005, "AccountNotEnoughKeys", "Not enough account keys given to the instruction"),
+    (3006, "AccountNotMutable", "The given account is not mutable"),
+    (3007, "AccountOwnedByWrongProgram", "The given account is owned by a different program than expected"),
+    (3008, "InvalidProgramId", "Program ID was not as expected"),
+    (3009, "InvalidProgramExecutable", "Program account is not executable"),
+    (3010, "AccountNotSigner", "The given account did not sign"),
+    (3011, "AccountNotSystemOwned", "The given account is not owned by the system program"),
+    (3012, "AccountNotInitialized", "The program expected this account to be already initialized"),
+    (3013, "AccountNotProgramData", "The given account is not a program data account"),
+    (3014, "AccountNotAssociatedTokenAccount", "The given account is not the associated token account"),
+    (3015, "AccountSysvarMismatch", "The given public key does not match the required sysvar"),
+    (3016, "AccountReallocExceedsLimit", "The account reallocation exceeds the MAX_PERMITTED_DATA_INCREASE limit"),
+    (3017, "AccountDuplicateReallocs", "The account was duplicated for more than one reallocation"),
+    (4100, "DeclaredProgramIdMismatch", "The declared program id does not match the actual program id"),
+    (4101, "TryingToInitPayerAsProgramAccount", "You cannot/should not initialize the payer account as a program account"),
+    (4102, "InvalidNumericConversion", "The program could not perform the numeric conversion, out of range integral type conversion attempted"),
+    (5000, "Deprecated", "The API being used is deprecated and should no longer be used"),
+];
+
+// A custom error code returned by a program, with its name when known
+pub struct ProgramError {
+    pub instruction_index: u8,
+    pub program_id: Pubkey,
+    pub code: u32,
+    pub name: Option<String>,
+    pub message: Option<String>,
+}
+
+impl fmt::Display for ProgramError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Program {} returned error {}",
+            self.program_id, self.code
+        )?;
+        match (&self.name, &self.message) {
+            (Some(name), Some(message)) => write!(f, " ({}: {})", name, message)?,
+            (Some(name), None) => write!(f, " ({})", name)?,
+            (None, Some(message)) => write!(f, " ({})", message)?,
+            (None, None) => {}
+        }
+        write!(f, " in instruction {}", self.instruction_index)
+    }
+}
+
+// ==================== Decoding ==================== //
+
+// Program logs carried by a failed preflight simulation
+pub fn client_error_logs(error: &ClientError) -> Vec<String> {
+    match error.kind() {
+        ClientErrorKind::RpcError(RpcError::RpcResponseError {
+            data: RpcResponseErrorData::SendTransactionPreflightFailure(simulation),
+            ..
+        }) => simulation.logs.clone().unwrap_or_default(),
+        _ => Vec::new(),
+    }
+}
+
+// The program whose failure produced `code`. After a CPI every caller logs the
+// same failure on the way out, so the first line names the innermost program.
+fn failing_program(message: &VersionedMessage, index: u8, code: u32, logs: &[String]) -> Pubkey {
+    let failure = format!(" failed: custom program error: {:#x}", code);
+    logs.iter()
+        .filter_map(|line| line.strip_prefix("Program ")?.strip_suffix(&failure))
+        .find_map(|program| Pubkey::from_str(program).ok())
+        .or_else(|| {
+            let instruction = message.instructions().get(usize::from(index))?;
+            message
+                .static_account_keys()
+                .get(usize::from(instruction.program_id_index))
+                .copied()
+        })
+        .unwrap_or_default()
+}
+
+// Name and message from the `AnchorError occurred` log line Anchor writes,
+// taken from the innermost program like `failing_program`
+fn anchor_log_error(code: u32, logs: &[String]) -> Option<(String, String)> {
+    let number = format!(". Error Number: {}. Error Message: ", code);
+    logs.iter().find_map(|line| {
+        let (_, rest) = line.split_once("Error Code: ")?;
+        let (name, message) = rest.split_once(&number)?;
+        Some((name.to_string(), message.trim_end_matches('.').to_string()))
+    })
+}
+
+// Decode `InstructionError::Custom` using the IDLs registered on the client,
+// Anchor's built-in errors and Anchor's error logs
+pub fn decode_program_error(
+    client: &SolClient,
+    message: &VersionedMessage,
+    error: &TransactionError,
+    logs: &[String],
+) -> Option<ProgramError> {
+    let (index, code) = match error {
+        TransactionError::InstructionError(index, InstructionError::Custom(code)) => {
+            (*index, *code)
+        }
+        _ => return None,
+    };
+    let program_id = failing_program(message, index, code, logs);
+    let idl = client.idl(&program_id);
+
+    let mut name = None;
+    let mut text = None;
+    if let Some(error) = idl.and_then(|idl| idl.error(code)) {
+        name = Some(error.name.clone());
+        text = error.msg.clone();
+    } else if let Some((log_name, log_message)) = anchor_log_error(code, logs) {
+        name = Some(log_name);
+        text = Some(log_message);
+    } else if idl.is_some() {
+        // Built-in codes are only meaningful for programs known to use Anchor
+        if let Some((_, error_name, error_message)) = ANCHOR_ERRORS
+            .iter()
+            .find(|(anchor_code, _, _)| *anchor_code == code)
+        {
+            name = Some(error_name.to_string());
+            text = Some(error_message.to_string());
+        }
+    }
+
+    Some(ProgramError {
+        instruction_index: index,
+        program_id,
+        code,
+        name,
+        message: text,
+    })
+}
+
+// Result for a failed send, with the program error decoded when there is one
+pub fn send_failed(
+    client: &SolClient,
+    transaction: &VersionedTransaction,
+    error: &ClientError,
+) -> SolTransactionResult {
+    let decoded = error.get_transaction_error().and_then(|transaction_error| {
+        decode_program_error(
+            client,
+            &transaction.message,
+            &transaction_error,
+            &client_error_logs(error),
+        )
+    });
+    match decoded {
+        Some(program_error) => {
+            SolTransactionResult::program_failed("Transaction failed", &program_error)
+        }
+        None => SolTransactionResult::failed(format!("Transaction failed: {:?}", error)),
+    }
+}
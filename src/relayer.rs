@@ -0,0 +1,216 @@
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use solana_account_decoder::UiAccountEncoding;
+use solana_client::rpc_config::{
+    RpcSimulateTransactionAccountsConfig, RpcSimulateTransactionConfig,
+};
+use solana_sdk::message::VersionedMessage;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::transaction::VersionedTransaction;
+use std::ffi::CStr;
+use std::os::raw::c_char;
+
+use crate::client::SolClient;
+use crate::offline::{deserialize_transaction, SolTransaction};
+use crate::program_error::send_failed;
+use crate::signer::partial_sign;
+use crate::transaction::SolTransactionResult;
+use crate::wallet::{SolKeyPair, SolPublicKey};
+
+// Limits a sponsor applies before paying for someone else's transaction
+#[repr(C)]
+pub struct SolRelayerPolicy {
+    pub allowed_programs: *const SolPublicKey,
+    pub allowed_program_count: usize, // 0 allows any program
+    pub max_fee: u64,                 // Lamports; 0 means no limit
+    pub max_payer_outflow: u64,       // Lamports the fee payer may lose besides the fee
+}
+
+impl SolRelayerPolicy {
+    fn allowed_programs(&self) -> &[SolPublicKey] {
+        if self.allowed_programs.is_null() || self.allowed_program_count == 0 {
+            &[]
+        } else {
+            unsafe { std::slice::from_raw_parts(self.allowed_programs, self.allowed_program_count) }
+        }
+    }
+}
+
+// ==================== Helpers ==================== //
+
+// Checks that need no signature from the fee payer; returns the fee
+fn check_transaction(
+    client: &SolClient,
+    transaction: &SolTransaction,
+    fee_payer: &Pubkey,
+    policy: &SolRelayerPolicy,
+) -> Result<u64, String> {
+    let message = &transaction.transaction.message;
+    let account_keys = message.static_account_keys();
+    if account_keys.first() != Some(fee_payer) {
+        return Err(format!("Fee payer must be {}", fee_payer));
+    }
+
+    // The fee payer signs for the fee only. An instruction that lists it could
+    // use it as an authority, e.g. to move its tokens, so none may.
+    if let Some(index) = message
+        .instructions()
+        .iter()
+        .position(|instruction| instruction.accounts.contains(&0))
+    {
+        return Err(format!("Instruction {} uses the fee payer account", index));
+    }
+
+    let missing = transaction.missing_signers();
+    if let Some(signer) = missing.iter().find(|signer| *signer != fee_payer) {
+        return Err(format!(
+            "Transaction is missing a signature from {}",
+            signer
+        ));
+    }
+    if !transaction.verify_signatures() {
+        return Err("Transaction has an invalid signature".to_string());
+    }
+
+    let allowed_programs = policy.allowed_programs();
+    if !allowed_programs.is_empty() {
+        for instruction in message.instructions() {
+            let program_id = account_keys[usize::from(instruction.program_id_index)];
+            if !allowed_programs
+                .iter()
+                .any(|allowed| allowed.to_pubkey() == program_id)
+            {
+                return Err(format!("Program {} is not allowed", program_id));
+            }
+        }
+    }
+
+    let fee = match message {
+        VersionedMessage::Legacy(message) => client.rpc_client.get_fee_for_message(message),
+        VersionedMessage::V0(message) => client.rpc_client.get_fee_for_message(message),
+    }
+    .map_err(|err| format!("Failed to get fee: {:?}", err))?;
+    if policy.max_fee != 0 && fee > policy.max_fee {
+        return Err(format!(
+            "Fee of {} lamports exceeds the limit of {}",
+            fee, policy.max_fee
+        ));
+    }
+    Ok(fee)
+}
+
+// Simulate the co-signed transaction and measure what the fee payer loses
+fn check_payer_outflow(
+    client: &SolClient,
+    transaction: &VersionedTransaction,
+    fee_payer: &Pubkey,
+    fee: u64,
+    policy: &SolRelayerPolicy,
+) -> Result<(), String> {
+    let balance = client
+        .rpc_client
+        .get_balance(fee_payer)
+        .map_err(|err| format!("Failed to get fee payer balance: {:?}", err))?;
+
+    let config = RpcSimulateTransactionConfig {
+        sig_verify: true,
+        accounts: Some(RpcSimulateTransactionAccountsConfig {
+            encoding: Some(UiAccountEncoding::Base64),
+            addresses: vec![fee_payer.to_string()],
+        }),
+        ..RpcSimulateTransactionConfig::default()
+    };
+    let simulation = client
+        .rpc_client
+        .simulate_transaction_with_config(transaction, config)
+        .map_err(|err| format!("Simulation failed: {:?}", err))?
+        .value;
+    if let Some(err) = simulation.err {
+        return Err(format!("Simulation failed: {:?}", err));
+    }
+
+    let balance_after = simulation
+        .accounts
+        .and_then(|accounts| accounts.into_iter().next().flatten())
+        .map_or(0, |account| account.lamports);
+    let outflow = balance.saturating_sub(balance_after).saturating_sub(fee);
+    if outflow > policy.max_payer_outflow {
+        return Err(format!(
+            "Transaction takes {} lamports from the fee payer (limit {})",
+            outflow, policy.max_payer_outflow
+        ));
+    }
+    Ok(())
+}
+
+fn relay(
+    client: &SolClient,
+    fee_payer: &Keypair,
+    mut transaction: SolTransaction,
+    policy: &SolRelayerPolicy,
+) -> Result<SolTransactionResult, String> {
+    let fee_payer_pubkey = fee_payer.pubkey();
+    let fee = check_transaction(client, &transaction, &fee_payer_pubkey, policy)?;
+    partial_sign(&mut transaction.transaction, fee_payer)?;
+    check_payer_outflow(
+        client,
+        &transaction.transaction,
+        &fee_payer_pubkey,
+        fee,
+        policy,
+    )?;
+
+    Ok(
+        match client
+            .rpc_client
+            .send_and_confirm_transaction(&transaction.transaction)
+        {
+            Ok(signature) => SolTransactionResult::sent(&signature),
+            Err(err) => send_failed(client, &transaction.transaction, &err),
+        },
+    )
+}
+
+// ==================== Relayer Functions ==================== //
+
+// Accept a base64 transaction signed by everyone except the fee payer, check it
+// against `policy`, co-sign as fee payer and submit it
+#[no_mangle]
+pub extern "C" fn relayer_sign_and_send(
+    client: *mut SolClient,
+    fee_payer: *mut SolKeyPair,
+    transaction: *const c_char,
+    policy: *const SolRelayerPolicy,
+) -> *mut SolTransactionResult {
+    let client = unsafe {
+        assert!(!client.is_null());
+        &*client
+    };
+    let fee_payer = unsafe {
+        assert!(!fee_payer.is_null());
+        &*fee_payer
+    }
+    .to_keypair();
+    let policy = unsafe {
+        assert!(!policy.is_null());
+        &*policy
+    };
+    let encoded = match unsafe { CStr::from_ptr(transaction) }.to_str() {
+        Ok(str) => str.trim(),
+        Err(_) => return SolTransactionResult::failed("Invalid transaction string").into_raw(),
+    };
+
+    let transaction = match BASE64
+        .decode(encoded)
+        .map_err(|err| format!("Invalid base64 transaction: {}", err))
+        .and_then(|bytes| deserialize_transaction(&bytes))
+    {
+        Ok(transaction) => SolTransaction { transaction },
+        Err(err) => return SolTransactionResult::failed(err).into_raw(),
+    };
+
+    match relay(client, &fee_payer, transaction, policy) {
+        Ok(result) => result.into_raw(),
+        Err(err) => SolTransactionResult::failed(format!("Rejected: {}", err)).into_raw(),
+    }
+}
@@ -0,0 +1,277 @@
+use std::collections::VecDeque;
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::sync::Mutex;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use solana_client::{
+    client_error::{ClientError, ClientErrorKind, Result as ClientResult},
+    rpc_request::{RpcError, RpcRequest, RpcResponseErrorData},
+    rpc_response::RpcSimulateTransactionResult,
+    rpc_sender::{RpcSender, RpcTransportStats},
+};
+use solana_rpc_client::http_sender::HttpSender;
+
+// ==================== Recorded Session Format ==================== //
+
+// One line of a recording: the request and whatever the node answered
+#[derive(Serialize, Deserialize)]
+struct RecordedExchange {
+    method: String,
+    params: Value,
+    #[serde(flatten)]
+    outcome: RecordedOutcome,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+enum RecordedOutcome {
+    Result(Value),
+    Error(RecordedError),
+}
+
+// Mirrors the error kinds callers match on (e.g. `ClientErrorKind::RpcError`)
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+enum RecordedError {
+    RpcResponse {
+        code: i64,
+        message: String,
+        data: RecordedErrorData,
+    },
+    RpcRequest {
+        message: String,
+    },
+    Parse {
+        message: String,
+    },
+    ForUser {
+        message: String,
+    },
+    Transport {
+        message: String,
+    },
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+enum RecordedErrorData {
+    Empty,
+    SendTransactionPreflightFailure(Box<RpcSimulateTransactionResult>),
+    NodeUnhealthy { num_slots_behind: Option<u64> },
+}
+
+impl RecordedError {
+    fn from_client_error(err: &ClientError) -> Self {
+        match err.kind() {
+            ClientErrorKind::RpcError(RpcError::RpcResponseError {
+                code,
+                message,
+                data,
+            }) => RecordedError::RpcResponse {
+                code: *code,
+                message: message.clone(),
+                data: match data {
+                    RpcResponseErrorData::Empty => RecordedErrorData::Empty,
+                    RpcResponseErrorData::SendTransactionPreflightFailure(result) => {
+                        RecordedErrorData::SendTransactionPreflightFailure(Box::new(result.clone()))
+                    }
+                    RpcResponseErrorData::NodeUnhealthy { num_slots_behind } => {
+                        RecordedErrorData::NodeUnhealthy {
+                            num_slots_behind: *num_slots_behind,
+                        }
+                    }
+                },
+            },
+            ClientErrorKind::RpcError(RpcError::RpcRequestError(message)) => {
+                RecordedError::RpcRequest {
+                    message: message.clone(),
+                }
+            }
+            ClientErrorKind::RpcError(RpcError::ParseError(message)) => RecordedError::Parse {
+                message: message.clone(),
+            },
+            ClientErrorKind::RpcError(RpcError::ForUser(message)) => RecordedError::ForUser {
+                message: message.clone(),
+            },
+            kind => RecordedError::Transport {
+                message: kind.to_string(),
+            },
+        }
+    }
+
+    fn into_client_error(self, request: RpcRequest) -> ClientError {
+        let kind = match self {
+            RecordedError::RpcResponse {
+                code,
+                message,
+                data,
+            } => ClientErrorKind::RpcError(RpcError::RpcResponseError {
+                code,
+                message,
+                data: match data {
+                    RecordedErrorData::Empty => RpcResponseErrorData::Empty,
+                    RecordedErrorData::SendTransactionPreflightFailure(result) => {
+                        RpcResponseErrorData::SendTransactionPreflightFailure(*result)
+                    }
+                    RecordedErrorData::NodeUnhealthy { num_slots_behind } => {
+                        RpcResponseErrorData::NodeUnhealthy { num_slots_behind }
+                    }
+                },
+            }),
+            RecordedError::RpcRequest { message } => {
+                ClientErrorKind::RpcError(RpcError::RpcRequestError(message))
+            }
+            RecordedError::Parse { message } => {
+                ClientErrorKind::RpcError(RpcError::ParseError(message))
+            }
+            RecordedError::ForUser { message } => {
+                ClientErrorKind::RpcError(RpcError::ForUser(message))
+            }
+            RecordedError::Transport { message } => ClientErrorKind::Custom(message),
+        };
+        ClientError::new_with_request(kind, request)
+    }
+}
+
+// ==================== Recording Sender ==================== //
+
+// Forwards every request to the node over HTTP and appends the exchange to a JSONL file
+pub struct RecordingSender {
+    inner: HttpSender,
+    writer: Mutex<BufWriter<File>>,
+}
+
+impl RecordingSender {
+    pub fn new(url: &str, record_path: &str) -> std::io::Result<Self> {
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(record_path)?;
+
+        Ok(RecordingSender {
+            inner: HttpSender::new(url),
+            writer: Mutex::new(BufWriter::new(file)),
+        })
+    }
+
+    fn record(&self, exchange: &RecordedExchange) {
+        let line = match serde_json::to_string(exchange) {
+            Ok(line) => line,
+            Err(err) => {
+                eprintln!("Failed to serialize RPC exchange: {:?}", err);
+                return;
+            }
+        };
+
+        let mut writer = self.writer.lock().unwrap();
+        if let Err(err) = writeln!(writer, "{}", line).and_then(|_| writer.flush()) {
+            eprintln!("Failed to write RPC recording: {:?}", err);
+        }
+    }
+}
+
+#[async_trait]
+impl RpcSender for RecordingSender {
+    async fn send(&self, request: RpcRequest, params: Value) -> ClientResult<Value> {
+        let result = self.inner.send(request, params.clone()).await;
+
+        let outcome = match &result {
+            Ok(value) => RecordedOutcome::Result(value.clone()),
+            Err(err) => RecordedOutcome::Error(RecordedError::from_client_error(err)),
+        };
+        self.record(&RecordedExchange {
+            method: request.to_string(),
+            params,
+            outcome,
+        });
+
+        result
+    }
+
+    fn get_transport_stats(&self) -> RpcTransportStats {
+        self.inner.get_transport_stats()
+    }
+
+    fn url(&self) -> String {
+        self.inner.url()
+    }
+}
+
+// ==================== Replay Sender ==================== //
+
+// Serves responses from a recording instead of talking to a node.
+// Exchanges are consumed in order; a request is answered by the next
+// unconsumed exchange with the same method, since params such as freshly
+// generated keypairs or signatures may legitimately differ between runs.
+pub struct ReplaySender {
+    path: String,
+    exchanges: Mutex<VecDeque<RecordedExchange>>,
+    stats: Mutex<RpcTransportStats>,
+}
+
+impl ReplaySender {
+    pub fn new(replay_path: &str) -> std::io::Result<Self> {
+        let reader = BufReader::new(File::open(replay_path)?);
+
+        let mut exchanges = VecDeque::new();
+        for line in reader.lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let exchange: RecordedExchange = serde_json::from_str(&line)?;
+            exchanges.push_back(exchange);
+        }
+
+        Ok(ReplaySender {
+            path: replay_path.to_string(),
+            exchanges: Mutex::new(exchanges),
+            stats: Mutex::new(RpcTransportStats::default()),
+        })
+    }
+}
+
+#[async_trait]
+impl RpcSender for ReplaySender {
+    async fn send(&self, request: RpcRequest, _params: Value) -> ClientResult<Value> {
+        self.stats.lock().unwrap().request_count += 1;
+
+        let method = request.to_string();
+        let exchange = {
+            let mut exchanges = self.exchanges.lock().unwrap();
+            exchanges
+                .iter()
+                .position(|exchange| exchange.method == method)
+                .and_then(|index| exchanges.remove(index))
+        };
+
+        match exchange {
+            Some(RecordedExchange {
+                outcome: RecordedOutcome::Result(value),
+                ..
+            }) => Ok(value),
+            Some(RecordedExchange {
+                outcome: RecordedOutcome::Error(err),
+                ..
+            }) => Err(err.into_client_error(request)),
+            None => Err(ClientError::new_with_request(
+                ClientErrorKind::Custom(format!(
+                    "No recorded response left for '{}' in {}",
+                    method, self.path
+                )),
+                request,
+            )),
+        }
+    }
+
+    fn get_transport_stats(&self) -> RpcTransportStats {
+        self.stats.lock().unwrap().clone()
+    }
+
+    fn url(&self) -> String {
+        format!("replay:{}", self.path)
+    }
+}
@@ -0,0 +1,437 @@
+use solana_sdk::address_lookup_table::AddressLookupTableAccount;
+use solana_sdk::hash::Hash;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::message::{v0, VersionedMessage};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signature, Signer, SignerError};
+use solana_sdk::transaction::VersionedTransaction;
+use std::alloc::{self, Layout};
+use std::ffi::{c_void, CStr};
+use std::ops::Deref;
+use std::os::raw::c_char;
+use std::ptr::NonNull;
+
+use crate::keystore::{load_encrypted_keypair, SolKeystoreStatus};
+use crate::wallet::{load_wallet, SolKeyPair, SolPublicKey, SolSecretKey, SolSignature};
+
+// ==================== Locked Key Storage ==================== //
+
+fn page_size() -> usize {
+    #[cfg(unix)]
+    {
+        let size = unsafe { libc::sysconf(libc::_SC_PAGESIZE) };
+        if size > 0 {
+            return size as usize;
+        }
+    }
+    4096
+}
+
+// Keeps a keypair on its own page(s) so it can be mlock'd without affecting
+// unrelated allocations; the secret is wiped by `ed25519_dalek` on drop
+struct LockedKeypair {
+    ptr: NonNull<Keypair>,
+    layout: Layout,
+    locked: bool,
+}
+
+// The pointer is uniquely owned and `Keypair` is itself Send + Sync
+unsafe impl Send for LockedKeypair {}
+unsafe impl Sync for LockedKeypair {}
+
+impl LockedKeypair {
+    fn new(keypair: Keypair) -> Self {
+        let page = page_size();
+        let size = std::mem::size_of::<Keypair>().div_ceil(page) * page;
+        let layout = Layout::from_size_align(size, page).expect("Invalid keypair layout");
+
+        let ptr = unsafe { alloc::alloc_zeroed(layout) } as *mut Keypair;
+        let ptr = NonNull::new(ptr).unwrap_or_else(|| alloc::handle_alloc_error(layout));
+        unsafe { ptr.as_ptr().write(keypair) };
+
+        #[cfg(unix)]
+        let locked = unsafe { libc::mlock(ptr.as_ptr() as *const libc::c_void, size) == 0 };
+        #[cfg(not(unix))]
+        let locked = false;
+
+        LockedKeypair {
+            ptr,
+            layout,
+            locked,
+        }
+    }
+}
+
+impl Deref for LockedKeypair {
+    type Target = Keypair;
+
+    fn deref(&self) -> &Keypair {
+        unsafe { self.ptr.as_ref() }
+    }
+}
+
+impl Drop for LockedKeypair {
+    fn drop(&mut self) {
+        unsafe {
+            std::ptr::drop_in_place(self.ptr.as_ptr());
+            #[cfg(unix)]
+            if self.locked {
+                libc::munlock(self.ptr.as_ptr() as *const libc::c_void, self.layout.size());
+            }
+            alloc::dealloc(self.ptr.as_ptr() as *mut u8, self.layout);
+        }
+    }
+}
+
+// ==================== Signer Handle ==================== //
+
+// Called to sign `message` with the key for the registered public key. Write the
+// 64-byte signature to `signature_out` and return true, or return false to refuse.
+pub type SolSignCallback = Option<
+    extern "C" fn(
+        user_data: *mut c_void,
+        message: *const u8,
+        message_len: usize,
+        signature_out: *mut SolSignature,
+    ) -> bool,
+>;
+
+enum SignerKind {
+    Local(LockedKeypair),
+    // Signing is delegated to the caller, e.g. a separate process, HSM or wallet service
+    Callback {
+        pubkey: Pubkey,
+        callback: SolSignCallback,
+        user_data: *mut c_void,
+    },
+}
+
+// Opaque signing handle: the secret key never leaves Rust unless explicitly exported,
+// or never enters it at all for callback signers
+pub struct SolSigner {
+    kind: SignerKind,
+}
+
+impl SolSigner {
+    pub fn new_local(keypair: Keypair) -> Self {
+        SolSigner {
+            kind: SignerKind::Local(LockedKeypair::new(keypair)),
+        }
+    }
+
+    pub fn new_callback(pubkey: Pubkey, callback: SolSignCallback, user_data: *mut c_void) -> Self {
+        SolSigner {
+            kind: SignerKind::Callback {
+                pubkey,
+                callback,
+                user_data,
+            },
+        }
+    }
+
+    // The local keypair, if this signer holds one
+    fn keypair(&self) -> Option<&Keypair> {
+        match &self.kind {
+            SignerKind::Local(keypair) => Some(keypair),
+            SignerKind::Callback { .. } => None,
+        }
+    }
+
+    pub fn is_memory_locked(&self) -> bool {
+        match &self.kind {
+            SignerKind::Local(keypair) => keypair.locked,
+            SignerKind::Callback { .. } => false,
+        }
+    }
+}
+
+impl Signer for SolSigner {
+    fn try_pubkey(&self) -> Result<Pubkey, SignerError> {
+        match &self.kind {
+            SignerKind::Local(keypair) => keypair.try_pubkey(),
+            SignerKind::Callback { pubkey, .. } => Ok(*pubkey),
+        }
+    }
+
+    fn try_sign_message(&self, message: &[u8]) -> Result<Signature, SignerError> {
+        match &self.kind {
+            SignerKind::Local(keypair) => keypair.try_sign_message(message),
+            SignerKind::Callback {
+                pubkey,
+                callback,
+                user_data,
+            } => {
+                let callback = callback.ok_or(SignerError::Custom(
+                    "External signer has no callback".to_string(),
+                ))?;
+                let mut signature = SolSignature { data: [0; 64] };
+                if !callback(*user_data, message.as_ptr(), message.len(), &mut signature) {
+                    return Err(SignerError::Custom(
+                        "External signer refused to sign".to_string(),
+                    ));
+                }
+
+                // Never hand an unverified signature to the cluster
+                let signature = signature.to_signature();
+                if !signature.verify(pubkey.as_ref(), message) {
+                    return Err(SignerError::Custom(format!(
+                        "External signer returned an invalid signature for {}",
+                        pubkey
+                    )));
+                }
+                Ok(signature)
+            }
+        }
+    }
+
+    fn is_interactive(&self) -> bool {
+        matches!(self.kind, SignerKind::Callback { .. })
+    }
+}
+
+// Build and sign a v0 transaction, reporting signer failures instead of panicking
+pub fn sign_transaction(
+    instructions: &[Instruction],
+    payer: &Pubkey,
+    signers: &[&dyn Signer],
+    recent_blockhash: Hash,
+) -> Result<VersionedTransaction, String> {
+    sign_transaction_with_lookup_tables(instructions, payer, signers, recent_blockhash, &[])
+}
+
+// Accounts found in `lookup_tables` are referenced by index instead of by key
+pub fn sign_transaction_with_lookup_tables(
+    instructions: &[Instruction],
+    payer: &Pubkey,
+    signers: &[&dyn Signer],
+    recent_blockhash: Hash,
+    lookup_tables: &[AddressLookupTableAccount],
+) -> Result<VersionedTransaction, String> {
+    let message = compile_message(instructions, payer, recent_blockhash, lookup_tables)?;
+
+    // A wallet passed more than once (e.g. as payer and owner) signs once
+    let mut unique_signers: Vec<&dyn Signer> = Vec::with_capacity(signers.len());
+    for signer in signers {
+        if !unique_signers
+            .iter()
+            .any(|unique| unique.pubkey() == signer.pubkey())
+        {
+            unique_signers.push(*signer);
+        }
+    }
+
+    VersionedTransaction::try_new(message, unique_signers.as_slice())
+        .map_err(|err| format!("Failed to sign transaction: {}", err))
+}
+
+pub fn compile_message(
+    instructions: &[Instruction],
+    payer: &Pubkey,
+    recent_blockhash: Hash,
+    lookup_tables: &[AddressLookupTableAccount],
+) -> Result<VersionedMessage, String> {
+    v0::Message::try_compile(payer, instructions, lookup_tables, recent_blockhash)
+        .map(VersionedMessage::V0)
+        .map_err(|err| format!("Failed to compile transaction message: {}", err))
+}
+
+// Signers the message requires, in signature order
+pub fn required_signers(transaction: &VersionedTransaction) -> &[Pubkey] {
+    let keys = transaction.message.static_account_keys();
+    let count = usize::from(transaction.message.header().num_required_signatures);
+    &keys[..count.min(keys.len())]
+}
+
+// Add one signature to a transaction that other parties also sign
+pub fn partial_sign(
+    transaction: &mut VersionedTransaction,
+    signer: &dyn Signer,
+) -> Result<(), String> {
+    let pubkey = signer.pubkey();
+    let index = required_signers(transaction)
+        .iter()
+        .position(|key| *key == pubkey)
+        .ok_or_else(|| format!("{} is not a required signer of this transaction", pubkey))?;
+    let signature = signer
+        .try_sign_message(&transaction.message.serialize())
+        .map_err(|err| format!("Failed to sign transaction: {}", err))?;
+
+    transaction
+        .signatures
+        .resize(required_signers(transaction).len(), Signature::default());
+    transaction.signatures[index] = signature;
+    Ok(())
+}
+
+// ==================== Signer Functions ==================== //
+
+#[no_mangle]
+pub extern "C" fn sol_signer_new() -> *mut SolSigner {
+    Box::into_raw(Box::new(SolSigner::new_local(Keypair::new())))
+}
+
+// Copy a wallet into a signer handle; the wallet can then be released with `free_keypair`
+#[no_mangle]
+pub extern "C" fn sol_signer_from_keypair(wallet: *mut SolKeyPair) -> *mut SolSigner {
+    let wallet = unsafe {
+        assert!(!wallet.is_null());
+        &*wallet
+    };
+
+    Box::into_raw(Box::new(SolSigner::new_local(wallet.to_keypair())))
+}
+
+// Load a Solana CLI keypair file straight into a signer handle
+#[no_mangle]
+pub extern "C" fn sol_signer_from_file(file_path: *const c_char) -> *mut SolSigner {
+    let file_path = match unsafe { CStr::from_ptr(file_path) }.to_str() {
+        Ok(str) => str,
+        Err(_) => return std::ptr::null_mut(),
+    };
+
+    match load_wallet(file_path) {
+        Ok(keypair) => Box::into_raw(Box::new(SolSigner::new_local(keypair))),
+        Err(err) => {
+            eprintln!("Failed to load wallet {}: {}", file_path, err);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+// Decrypt a keystore straight into a signer handle
+#[no_mangle]
+pub extern "C" fn sol_signer_from_encrypted_file(
+    file_path: *const c_char,
+    password: *const c_char,
+    status_out: *mut SolKeystoreStatus,
+) -> *mut SolSigner {
+    let set_status = |status| {
+        if !status_out.is_null() {
+            unsafe { *status_out = status };
+        }
+    };
+
+    let file_path = match unsafe { CStr::from_ptr(file_path) }.to_str() {
+        Ok(str) => str,
+        Err(_) => {
+            set_status(SolKeystoreStatus::InvalidArgument);
+            return std::ptr::null_mut();
+        }
+    };
+    let password = match unsafe { CStr::from_ptr(password) }.to_str() {
+        Ok(str) => str,
+        Err(_) => {
+            set_status(SolKeystoreStatus::InvalidArgument);
+            return std::ptr::null_mut();
+        }
+    };
+
+    match load_encrypted_keypair(file_path, password) {
+        Ok(keypair) => {
+            set_status(SolKeystoreStatus::Ok);
+            Box::into_raw(Box::new(SolSigner::new_local(keypair)))
+        }
+        Err(err) => {
+            eprintln!("{}", err);
+            set_status(err.status());
+            std::ptr::null_mut()
+        }
+    }
+}
+
+// Create a signer that delegates signing to `callback`; `user_data` is passed through
+// untouched and must stay valid for the lifetime of the signer
+#[no_mangle]
+pub extern "C" fn sol_signer_from_callback(
+    pubkey: *const SolPublicKey,
+    callback: SolSignCallback,
+    user_data: *mut c_void,
+) -> *mut SolSigner {
+    let pubkey = unsafe {
+        assert!(!pubkey.is_null());
+        &*pubkey
+    };
+    if callback.is_none() {
+        return std::ptr::null_mut();
+    }
+
+    Box::into_raw(Box::new(SolSigner::new_callback(
+        pubkey.to_pubkey(),
+        callback,
+        user_data,
+    )))
+}
+
+#[no_mangle]
+pub extern "C" fn sol_signer_pubkey(signer: *const SolSigner) -> SolPublicKey {
+    let signer = unsafe {
+        assert!(!signer.is_null());
+        &*signer
+    };
+
+    SolPublicKey::new(signer.pubkey())
+}
+
+#[no_mangle]
+pub extern "C" fn sol_signer_is_memory_locked(signer: *const SolSigner) -> bool {
+    let signer = unsafe {
+        assert!(!signer.is_null());
+        &*signer
+    };
+
+    signer.is_memory_locked()
+}
+
+// Explicitly copy the 64-byte secret key out to a caller-owned buffer (local signers only)
+#[no_mangle]
+pub extern "C" fn sol_signer_export_secret_key(
+    signer: *const SolSigner,
+    secret_out: *mut SolSecretKey,
+) -> bool {
+    let signer = unsafe {
+        assert!(!signer.is_null());
+        &*signer
+    };
+    let keypair = match signer.keypair() {
+        Some(keypair) => keypair,
+        None => {
+            eprintln!("External signers have no secret key to export");
+            return false;
+        }
+    };
+    if secret_out.is_null() {
+        return false;
+    }
+
+    unsafe { (*secret_out).data = keypair.to_bytes() };
+    true
+}
+
+// Explicitly export the signer as a `SolKeyPair` for APIs that still require one
+#[no_mangle]
+pub extern "C" fn sol_signer_export_keypair(signer: *const SolSigner) -> *mut SolKeyPair {
+    let signer = unsafe {
+        assert!(!signer.is_null());
+        &*signer
+    };
+
+    match signer.keypair() {
+        Some(keypair) => Box::into_raw(Box::new(SolKeyPair::from_existing(
+            keypair.insecure_clone(),
+        ))),
+        None => {
+            eprintln!("External signers have no secret key to export");
+            std::ptr::null_mut()
+        }
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn free_sol_signer(signer: *mut SolSigner) {
+    if signer.is_null() {
+        return;
+    }
+    unsafe {
+        drop(Box::from_raw(signer));
+    }
+}
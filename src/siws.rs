@@ -0,0 +1,522 @@
+use chrono::{DateTime, SecondsFormat, Utc};
+use solana_program::pubkey::Pubkey;
+use std::ffi::{CStr, CString};
+use std::os::raw::c_char;
+use std::str::FromStr;
+
+use crate::message::sign_bytes;
+use crate::wallet::{SolKeyPair, SolPublicKey, SolSignature};
+
+const SIWS_HEADER_SUFFIX: &str = " wants you to sign in with your Solana account:";
+
+// Result of verifying a Sign-In With Solana message
+#[repr(C)]
+#[derive(Debug, PartialEq, Eq)]
+pub enum SolSiwsStatus {
+    Ok,
+    InvalidMessage,
+    InvalidSignature,
+    DomainMismatch,
+    NonceMismatch,
+    Expired,
+    NotYetValid,
+    InvalidArgument,
+}
+
+// Parsed SIWS message handed to C; optional text fields are NULL and
+// optional timestamps are 0 when absent. Release with `free_siws_message`.
+#[repr(C)]
+pub struct SolSiwsMessage {
+    pub domain: *mut c_char,
+    pub address: SolPublicKey,
+    pub statement: *mut c_char,
+    pub uri: *mut c_char,
+    pub version: *mut c_char,
+    pub chain_id: *mut c_char,
+    pub nonce: *mut c_char,
+    pub issued_at: i64,
+    pub expiration_time: i64,
+    pub not_before: i64,
+    pub request_id: *mut c_char,
+    pub resources: *mut *mut c_char, // NULL when there are no resources
+    pub resource_count: usize,
+}
+
+// ==================== Message Model ==================== //
+
+// CAIP-122 style sign-in message as shown to the user by wallets
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignInMessage {
+    pub domain: String,
+    pub address: Pubkey,
+    pub statement: Option<String>,
+    pub uri: Option<String>,
+    pub version: String,
+    pub chain_id: Option<String>,
+    pub nonce: Option<String>,
+    pub issued_at: Option<i64>,
+    pub expiration_time: Option<i64>,
+    pub not_before: Option<i64>,
+    pub request_id: Option<String>,
+    pub resources: Vec<String>,
+}
+
+fn format_timestamp(timestamp: i64) -> Result<String, String> {
+    DateTime::<Utc>::from_timestamp(timestamp, 0)
+        .map(|time| time.to_rfc3339_opts(SecondsFormat::Secs, true))
+        .ok_or_else(|| format!("Invalid timestamp: {}", timestamp))
+}
+
+fn parse_timestamp(value: &str) -> Result<i64, String> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|time| time.timestamp())
+        .map_err(|err| format!("Invalid timestamp {}: {}", value, err))
+}
+
+impl SignInMessage {
+    // Render the message text that gets signed
+    pub fn to_message_string(&self) -> Result<String, String> {
+        let mut message = format!("{}{}\n{}", self.domain, SIWS_HEADER_SUFFIX, self.address);
+
+        if let Some(statement) = &self.statement {
+            message.push_str(&format!("\n\n{}", statement));
+        }
+
+        let mut fields = Vec::new();
+        if let Some(uri) = &self.uri {
+            fields.push(format!("URI: {}", uri));
+        }
+        fields.push(format!("Version: {}", self.version));
+        if let Some(chain_id) = &self.chain_id {
+            fields.push(format!("Chain ID: {}", chain_id));
+        }
+        if let Some(nonce) = &self.nonce {
+            fields.push(format!("Nonce: {}", nonce));
+        }
+        if let Some(issued_at) = self.issued_at {
+            fields.push(format!("Issued At: {}", format_timestamp(issued_at)?));
+        }
+        if let Some(expiration_time) = self.expiration_time {
+            fields.push(format!(
+                "Expiration Time: {}",
+                format_timestamp(expiration_time)?
+            ));
+        }
+        if let Some(not_before) = self.not_before {
+            fields.push(format!("Not Before: {}", format_timestamp(not_before)?));
+        }
+        if let Some(request_id) = &self.request_id {
+            fields.push(format!("Request ID: {}", request_id));
+        }
+        if !self.resources.is_empty() {
+            fields.push("Resources:".to_string());
+            fields.extend(
+                self.resources
+                    .iter()
+                    .map(|resource| format!("- {}", resource)),
+            );
+        }
+
+        message.push_str("\n\n");
+        message.push_str(&fields.join("\n"));
+        Ok(message)
+    }
+
+    // Parse message text produced by `to_message_string` or a compatible wallet
+    pub fn parse(message: &str) -> Result<Self, String> {
+        let mut lines = message.lines();
+
+        let header = lines.next().ok_or("Empty sign-in message")?;
+        let domain = header
+            .strip_suffix(SIWS_HEADER_SUFFIX)
+            .filter(|domain| !domain.is_empty())
+            .ok_or("Missing sign-in header")?
+            .to_string();
+
+        let address = lines.next().ok_or("Missing address")?;
+        let address =
+            Pubkey::from_str(address).map_err(|_| format!("Invalid address: {}", address))?;
+
+        let mut parsed = SignInMessage {
+            domain,
+            address,
+            statement: None,
+            uri: None,
+            version: String::new(),
+            chain_id: None,
+            nonce: None,
+            issued_at: None,
+            expiration_time: None,
+            not_before: None,
+            request_id: None,
+            resources: Vec::new(),
+        };
+
+        // The statement is the only free-form block: it sits between two blank lines
+        let mut rest: Vec<&str> = lines.collect();
+        if rest.first() == Some(&"") {
+            rest.remove(0);
+        }
+        let is_field = |line: &str| {
+            [
+                "URI: ",
+                "Version: ",
+                "Chain ID: ",
+                "Nonce: ",
+                "Issued At: ",
+                "Expiration Time: ",
+                "Not Before: ",
+                "Request ID: ",
+                "Resources:",
+            ]
+            .iter()
+            .any(|prefix| line.starts_with(prefix))
+        };
+        if let Some(first) = rest.first() {
+            if !is_field(first) {
+                parsed.statement = Some(first.to_string());
+                rest.remove(0);
+                if rest.first() == Some(&"") {
+                    rest.remove(0);
+                }
+            }
+        }
+
+        // "Resources:" is followed by one "- <uri>" line per resource
+        let mut in_resources = false;
+        for line in rest {
+            if line == "Resources:" {
+                in_resources = true;
+                continue;
+            }
+            if in_resources {
+                if let Some(resource) = line.strip_prefix("- ") {
+                    parsed.resources.push(resource.to_string());
+                    continue;
+                }
+                in_resources = false;
+            }
+            let (key, value) = line
+                .split_once(": ")
+                .ok_or_else(|| format!("Invalid field line: {}", line))?;
+            let value = value.to_string();
+            match key {
+                "URI" => parsed.uri = Some(value),
+                "Version" => parsed.version = value,
+                "Chain ID" => parsed.chain_id = Some(value),
+                "Nonce" => parsed.nonce = Some(value),
+                "Issued At" => parsed.issued_at = Some(parse_timestamp(&value)?),
+                "Expiration Time" => parsed.expiration_time = Some(parse_timestamp(&value)?),
+                "Not Before" => parsed.not_before = Some(parse_timestamp(&value)?),
+                "Request ID" => parsed.request_id = Some(value),
+                _ => return Err(format!("Unknown field: {}", key)),
+            }
+        }
+
+        if parsed.version.is_empty() {
+            return Err("Missing version".to_string());
+        }
+        Ok(parsed)
+    }
+
+    // Check the signature against the embedded address, then the field constraints
+    pub fn verify(
+        &self,
+        message: &str,
+        signature: &SolSignature,
+        expected_domain: Option<&str>,
+        expected_nonce: Option<&str>,
+        now: i64,
+    ) -> SolSiwsStatus {
+        if !signature
+            .to_signature()
+            .verify(self.address.as_ref(), message.as_bytes())
+        {
+            return SolSiwsStatus::InvalidSignature;
+        }
+        if let Some(domain) = expected_domain {
+            if self.domain != domain {
+                return SolSiwsStatus::DomainMismatch;
+            }
+        }
+        if let Some(nonce) = expected_nonce {
+            if self.nonce.as_deref() != Some(nonce) {
+                return SolSiwsStatus::NonceMismatch;
+            }
+        }
+        if let Some(expiration_time) = self.expiration_time {
+            if now >= expiration_time {
+                return SolSiwsStatus::Expired;
+            }
+        }
+        if let Some(issued_at) = self.issued_at {
+            if now < issued_at {
+                return SolSiwsStatus::NotYetValid;
+            }
+        }
+        if let Some(not_before) = self.not_before {
+            if now < not_before {
+                return SolSiwsStatus::NotYetValid;
+            }
+        }
+        SolSiwsStatus::Ok
+    }
+}
+
+// ==================== Helpers ==================== //
+
+// Read an optional C string; NULL means absent
+fn optional_str(value: *const c_char) -> Result<Option<String>, ()> {
+    if value.is_null() {
+        return Ok(None);
+    }
+    match unsafe { CStr::from_ptr(value) }.to_str() {
+        Ok(str) => Ok(Some(str.to_string())),
+        Err(_) => Err(()),
+    }
+}
+
+// Read an optional message field; NULL or "" means the field is omitted
+fn optional_field(value: *const c_char) -> Result<Option<String>, ()> {
+    optional_str(value).map(|value| value.filter(|value| !value.is_empty()))
+}
+
+fn optional_c_string(value: Option<String>) -> *mut c_char {
+    value
+        .and_then(|value| CString::new(value).ok())
+        .map_or(std::ptr::null_mut(), CString::into_raw)
+}
+
+fn free_c_string(value: *mut c_char) {
+    if !value.is_null() {
+        unsafe { drop(CString::from_raw(value)) };
+    }
+}
+
+// Strings with an interior NUL are left out of the array
+fn c_string_array(values: Vec<String>) -> *mut *mut c_char {
+    if values.is_empty() {
+        return std::ptr::null_mut();
+    }
+    let values: Box<[*mut c_char]> = values
+        .into_iter()
+        .map(|value| optional_c_string(Some(value)))
+        .collect();
+    Box::into_raw(values) as *mut *mut c_char
+}
+
+// ==================== SIWS Functions ==================== //
+
+// Build the sign-in message text. `issued_at` of 0 means "now", `expiration_time`
+// of 0 means no expiry; NULL or empty strings omit the optional fields.
+#[no_mangle]
+pub extern "C" fn siws_create_message(
+    domain: *const c_char,
+    address: *const SolPublicKey,
+    statement: *const c_char,
+    uri: *const c_char,
+    chain_id: *const c_char,
+    nonce: *const c_char,
+    issued_at: i64,
+    expiration_time: i64,
+) -> *mut c_char {
+    let address = unsafe {
+        assert!(!address.is_null());
+        &*address
+    };
+    let domain = match unsafe { CStr::from_ptr(domain) }.to_str() {
+        Ok(str) => str.to_string(),
+        Err(_) => return std::ptr::null_mut(),
+    };
+    let (statement, uri, chain_id, nonce) = match (
+        optional_field(statement),
+        optional_field(uri),
+        optional_field(chain_id),
+        optional_field(nonce),
+    ) {
+        (Ok(statement), Ok(uri), Ok(chain_id), Ok(nonce)) => (statement, uri, chain_id, nonce),
+        _ => return std::ptr::null_mut(),
+    };
+    if statement.as_deref().is_some_and(|s| s.contains('\n')) {
+        eprintln!("Sign-in statement must be a single line");
+        return std::ptr::null_mut();
+    }
+
+    let message = SignInMessage {
+        domain,
+        address: address.to_pubkey(),
+        statement,
+        uri,
+        version: "1".to_string(),
+        chain_id,
+        nonce,
+        issued_at: Some(if issued_at == 0 {
+            Utc::now().timestamp()
+        } else {
+            issued_at
+        }),
+        expiration_time: (expiration_time != 0).then_some(expiration_time),
+        not_before: None,
+        request_id: None,
+        resources: Vec::new(),
+    };
+
+    match message.to_message_string().map(CString::new) {
+        Ok(Ok(c_str)) => c_str.into_raw(),
+        Ok(Err(_)) => std::ptr::null_mut(),
+        Err(err) => {
+            eprintln!("{}", err);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+// Sign a sign-in message; only the wallet whose address is in the message may sign it
+#[no_mangle]
+pub extern "C" fn siws_sign_message(
+    wallet: *mut SolKeyPair,
+    message: *const c_char,
+    signature_out: *mut SolSignature,
+) -> bool {
+    let wallet = unsafe {
+        assert!(!wallet.is_null());
+        &*wallet
+    };
+    let message = match unsafe { CStr::from_ptr(message) }.to_str() {
+        Ok(str) => str,
+        Err(_) => return false,
+    };
+    if signature_out.is_null() {
+        return false;
+    }
+
+    match SignInMessage::parse(message) {
+        Ok(parsed) if parsed.address == wallet.get_pubkey() => {}
+        Ok(_) => {
+            eprintln!("Sign-in message address does not match the wallet");
+            return false;
+        }
+        Err(err) => {
+            eprintln!("{}", err);
+            return false;
+        }
+    }
+
+    match sign_bytes(&wallet.to_keypair(), message.as_bytes()) {
+        Ok(signature) => {
+            unsafe { *signature_out = SolSignature::new(signature) };
+            true
+        }
+        Err(err) => {
+            eprintln!("{}", err);
+            false
+        }
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn siws_parse_message(message: *const c_char) -> *mut SolSiwsMessage {
+    let message = match unsafe { CStr::from_ptr(message) }.to_str() {
+        Ok(str) => str,
+        Err(_) => return std::ptr::null_mut(),
+    };
+
+    match SignInMessage::parse(message) {
+        Ok(parsed) => Box::into_raw(Box::new(SolSiwsMessage {
+            domain: optional_c_string(Some(parsed.domain)),
+            address: SolPublicKey::new(parsed.address),
+            statement: optional_c_string(parsed.statement),
+            uri: optional_c_string(parsed.uri),
+            version: optional_c_string(Some(parsed.version)),
+            chain_id: optional_c_string(parsed.chain_id),
+            nonce: optional_c_string(parsed.nonce),
+            issued_at: parsed.issued_at.unwrap_or(0),
+            expiration_time: parsed.expiration_time.unwrap_or(0),
+            not_before: parsed.not_before.unwrap_or(0),
+            request_id: optional_c_string(parsed.request_id),
+            resource_count: parsed.resources.len(),
+            resources: c_string_array(parsed.resources),
+        })),
+        Err(err) => {
+            eprintln!("{}", err);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+// Verify the signature and the expected domain and nonce (NULL skips a check,
+// "" is rejected). `now` is a unix timestamp used for the validity window; 0
+// means the current time.
+#[no_mangle]
+pub extern "C" fn siws_verify(
+    message: *const c_char,
+    signature: *const SolSignature,
+    expected_domain: *const c_char,
+    expected_nonce: *const c_char,
+    now: i64,
+) -> SolSiwsStatus {
+    let signature = unsafe {
+        assert!(!signature.is_null());
+        &*signature
+    };
+    let message = match unsafe { CStr::from_ptr(message) }.to_str() {
+        Ok(str) => str,
+        Err(_) => return SolSiwsStatus::InvalidArgument,
+    };
+    let (expected_domain, expected_nonce) =
+        match (optional_str(expected_domain), optional_str(expected_nonce)) {
+            (Ok(domain), Ok(nonce)) => (domain, nonce),
+            _ => return SolSiwsStatus::InvalidArgument,
+        };
+    // An empty expectation is a caller bug, not a request to skip the check
+    if expected_domain.as_deref() == Some("") || expected_nonce.as_deref() == Some("") {
+        return SolSiwsStatus::InvalidArgument;
+    }
+
+    let parsed = match SignInMessage::parse(message) {
+        Ok(parsed) => parsed,
+        Err(err) => {
+            eprintln!("{}", err);
+            return SolSiwsStatus::InvalidMessage;
+        }
+    };
+
+    let now = if now == 0 {
+        Utc::now().timestamp()
+    } else {
+        now
+    };
+    parsed.verify(
+        message,
+        signature,
+        expected_domain.as_deref(),
+        expected_nonce.as_deref(),
+        now,
+    )
+}
+
+#[no_mangle]
+pub extern "C" fn free_siws_message(message: *mut SolSiwsMessage) {
+    if message.is_null() {
+        return;
+    }
+    let message = unsafe { Box::from_raw(message) };
+    free_c_string(message.domain);
+    free_c_string(message.statement);
+    free_c_string(message.uri);
+    free_c_string(message.version);
+    free_c_string(message.chain_id);
+    free_c_string(message.nonce);
+    free_c_string(message.request_id);
+    if !message.resources.is_null() {
+        let resources = unsafe {
+            Box::from_raw(std::ptr::slice_from_raw_parts_mut(
+                message.resources,
+                message.resource_count,
+            ))
+        };
+        resources
+            .iter()
+            .for_each(|resource| free_c_string(*resource));
+    }
+}
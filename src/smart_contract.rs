@@ -1,21 +1,21 @@
-use borsh::{BorshDeserialize, BorshSerialize};
 use sha2::{Digest, Sha256};
-use solana_client::rpc_client::RpcClient;
 use solana_program::pubkey::Pubkey;
 use solana_sdk::{
     instruction::{AccountMeta, Instruction},
     signature::{Keypair, Signer},
-    signer::EncodableKey,
     system_program,
-    transaction::Transaction,
 };
-use spl_associated_token_account::tools::account;
-use std::ffi::{CStr, CString};
+use std::ffi::CStr;
 use std::os::raw::c_char;
 use std::str::FromStr;
 
 use crate::{
     client::SolClient,
+    signer::{sign_transaction, SolSigner},
+    transaction::{
+        account_metas_from_c, free_transaction_result, send_instructions, SolAccountMeta,
+        SolTransactionResult,
+    },
     wallet::{SolKeyPair, SolPublicKey},
 };
 
@@ -70,24 +70,73 @@ pub extern "C" fn get_account_data_c(
 
 // Compute Discriminator
 fn get_discriminator(method_name: &str) -> Vec<u8> {
+    anchor_discriminator("global", method_name).to_vec()
+}
+
+// First 8 bytes of sha256("<namespace>:<name>"), e.g. "global:increment" or "account:Counter"
+pub fn anchor_discriminator(namespace: &str, name: &str) -> [u8; 8] {
     let mut hasher = Sha256::new();
-    hasher.update(format!("global:{}", method_name).as_bytes());
-    hasher.finalize()[..8].to_vec()
+    hasher.update(format!("{}:{}", namespace, name).as_bytes());
+    let mut discriminator = [0u8; 8];
+    discriminator.copy_from_slice(&hasher.finalize()[..8]);
+    discriminator
+}
+
+// How the instruction data is prefixed before the arguments
+#[repr(C)]
+#[allow(dead_code)] // Variants are constructed by C callers
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SolDiscriminatorKind {
+    AnchorGlobal, // sha256("global:<method_name>")[..8]
+    Custom,       // Caller supplied bytes, e.g. the "discriminator" array of an Anchor IDL
+    EnumTag,      // A single byte instruction index (native, SPL and Shank programs)
+    None,         // The instruction data is used as is
+}
+
+pub fn instruction_prefix(
+    kind: SolDiscriminatorKind,
+    method_name: Option<&str>,
+    discriminator: &[u8],
+) -> Result<Vec<u8>, String> {
+    match kind {
+        SolDiscriminatorKind::AnchorGlobal => method_name
+            .map(get_discriminator)
+            .ok_or_else(|| "An Anchor discriminator needs a method name".to_string()),
+        SolDiscriminatorKind::Custom if discriminator.is_empty() => {
+            Err("A custom discriminator needs at least one byte".to_string())
+        }
+        SolDiscriminatorKind::Custom => Ok(discriminator.to_vec()),
+        SolDiscriminatorKind::EnumTag if discriminator.len() != 1 => Err(format!(
+            "An enum tag is a single byte, got {} bytes",
+            discriminator.len()
+        )),
+        SolDiscriminatorKind::EnumTag => Ok(discriminator.to_vec()),
+        SolDiscriminatorKind::None => Ok(Vec::new()),
+    }
 }
 
-// Create Instruction for Anchor Methods
+// Create Instruction with the given discriminator in front of the arguments
 fn create_instruction(
-    program_id: &str,
-    method_name: &str,
+    program_id: &Pubkey,
+    discriminator: &[u8],
     accounts: Vec<AccountMeta>,
     data: Vec<u8>,
 ) -> Instruction {
-    let discriminator = get_discriminator(method_name);
-    let mut instruction_data = discriminator;
+    let mut instruction_data = discriminator.to_vec();
     instruction_data.extend(data);
-    let program_id = Pubkey::from_str(program_id).expect("Invalid program ID");
 
-    Instruction::new_with_bytes(program_id, &instruction_data, accounts)
+    Instruction::new_with_bytes(*program_id, &instruction_data, accounts)
+}
+
+// Parse a program id passed from C as a base58 string
+pub(crate) fn program_pubkey_from_c(program_id: *const c_char) -> Result<Pubkey, String> {
+    if program_id.is_null() {
+        return Err("A program id is required".to_string());
+    }
+    let program_id = unsafe { CStr::from_ptr(program_id) }
+        .to_str()
+        .map_err(|_| "Invalid program id string".to_string())?;
+    Pubkey::from_str(program_id).map_err(|err| format!("Invalid program id: {}", err))
 }
 
 // ==================== Transaction Functions ==================== //
@@ -106,80 +155,453 @@ pub extern "C" fn send_generic_transaction_c(
 ) -> *mut c_char {
     let client = unsafe { &mut *client };
 
-    let program_id = unsafe { CStr::from_ptr(program_id).to_str().unwrap() };
+    let program_id = match program_pubkey_from_c(program_id) {
+        Ok(program_id) => program_id,
+        Err(err) => {
+            eprintln!("{}", err);
+            return std::ptr::null_mut();
+        }
+    };
     let method_name = unsafe { CStr::from_ptr(method_name).to_str().unwrap() };
+    let account_pubkeys = unsafe { std::slice::from_raw_parts(account_pubkeys, account_count) };
 
-    // Deserialize account pubkeys
-    let mut accounts = unsafe {
-        std::slice::from_raw_parts(account_pubkeys, account_count)
-            .iter()
-            .map(|a| AccountMeta::new(a.to_pubkey(), false)) // Default signer = false
-            .collect::<Vec<AccountMeta>>()
+    // Convert signers to Keypair list
+    let signer_keypairs = keypairs_from_c(signers, signer_count);
+    let signer_refs: Vec<&dyn Signer> = signer_keypairs.iter().map(|s| s as &dyn Signer).collect();
+
+    let accounts = legacy_account_metas(account_pubkeys, &signer_refs);
+    let data = data_from_c(data_ptr, data_len);
+    _send_generic_transaction(
+        client,
+        &program_id,
+        &get_discriminator(method_name),
+        accounts,
+        None,
+        &signer_refs,
+        data,
+    )
+}
+
+// Same as `send_generic_transaction_c`, signing through `SolSigner` handles
+#[no_mangle]
+pub extern "C" fn send_generic_transaction_with_signers(
+    client: *mut SolClient,
+    program_id: *const c_char,
+    method_name: *const c_char,
+    account_pubkeys: *const SolPublicKey,
+    account_count: usize,
+    signers: *const *mut SolSigner, // List of signers, the first one pays the fees
+    signer_count: usize,
+    data_ptr: *const u8,
+    data_len: usize,
+    fee_payer: *const SolSigner, // Pays the fees instead of the first signer; may be NULL
+) -> *mut c_char {
+    let client = unsafe { &mut *client };
+
+    let program_id = match program_pubkey_from_c(program_id) {
+        Ok(program_id) => program_id,
+        Err(err) => {
+            eprintln!("{}", err);
+            return std::ptr::null_mut();
+        }
+    };
+    let method_name = unsafe { CStr::from_ptr(method_name).to_str().unwrap() };
+    let account_pubkeys = unsafe { std::slice::from_raw_parts(account_pubkeys, account_count) };
+
+    let signer_refs: Vec<&dyn Signer> = if signer_count == 0 {
+        Vec::new()
+    } else {
+        unsafe {
+            std::slice::from_raw_parts(signers, signer_count)
+                .iter()
+                .map(|s| &**s as &dyn Signer)
+                .collect()
+        }
+    };
+    let fee_payer = unsafe { fee_payer.as_ref() }.map(|s| s as &dyn Signer);
+
+    let accounts = legacy_account_metas(account_pubkeys, &signer_refs);
+    let data = data_from_c(data_ptr, data_len);
+    _send_generic_transaction(
+        client,
+        &program_id,
+        &get_discriminator(method_name),
+        accounts,
+        fee_payer,
+        &signer_refs,
+        data,
+    )
+}
+
+// Send an Anchor instruction with explicit per-account signer and writable flags
+#[no_mangle]
+pub extern "C" fn send_generic_transaction_with_metas(
+    client: *mut SolClient,
+    program_id: *const c_char,
+    method_name: *const c_char,
+    accounts: *const SolAccountMeta,
+    account_count: usize,
+    signers: *const *mut SolKeyPair, // List of signers, the first one pays the fees
+    signer_count: usize,
+    data_ptr: *const u8,
+    data_len: usize,
+) -> *mut c_char {
+    let client = unsafe { &mut *client };
+
+    let program_id = match program_pubkey_from_c(program_id) {
+        Ok(program_id) => program_id,
+        Err(err) => {
+            eprintln!("{}", err);
+            return std::ptr::null_mut();
+        }
+    };
+    let method_name = unsafe { CStr::from_ptr(method_name).to_str().unwrap() };
+    let accounts = account_metas_from_c(accounts, account_count);
+
+    let signer_keypairs = keypairs_from_c(signers, signer_count);
+    let signer_refs: Vec<&dyn Signer> = signer_keypairs.iter().map(|s| s as &dyn Signer).collect();
+
+    let data = data_from_c(data_ptr, data_len);
+    _send_generic_transaction(
+        client,
+        &program_id,
+        &get_discriminator(method_name),
+        accounts,
+        None,
+        &signer_refs,
+        data,
+    )
+}
+
+// Same as `send_generic_transaction_with_metas`, with fees paid by `fee_payer`
+// instead of the first signer. `signers` may be empty if only the fee payer signs.
+#[no_mangle]
+pub extern "C" fn send_generic_transaction_with_fee_payer(
+    client: *mut SolClient,
+    program_id: *const c_char,
+    method_name: *const c_char,
+    accounts: *const SolAccountMeta,
+    account_count: usize,
+    signers: *const *mut SolKeyPair,
+    signer_count: usize,
+    data_ptr: *const u8,
+    data_len: usize,
+    fee_payer: *mut SolKeyPair, // NULL means the first signer pays
+) -> *mut c_char {
+    let client = unsafe { &mut *client };
+    let fee_payer = unsafe { fee_payer.as_ref() }.map(SolKeyPair::to_keypair);
+
+    let program_id = match program_pubkey_from_c(program_id) {
+        Ok(program_id) => program_id,
+        Err(err) => {
+            eprintln!("{}", err);
+            return std::ptr::null_mut();
+        }
+    };
+    let method_name = unsafe { CStr::from_ptr(method_name).to_str().unwrap() };
+    let accounts = account_metas_from_c(accounts, account_count);
+
+    let signer_keypairs = keypairs_from_c(signers, signer_count);
+    let signer_refs: Vec<&dyn Signer> = signer_keypairs.iter().map(|s| s as &dyn Signer).collect();
+
+    let data = data_from_c(data_ptr, data_len);
+    _send_generic_transaction(
+        client,
+        &program_id,
+        &get_discriminator(method_name),
+        accounts,
+        fee_payer.as_ref().map(|s| s as &dyn Signer),
+        &signer_refs,
+        data,
+    )
+}
+
+// Send an instruction whose data is passed through unchanged, for native, SPL
+// and other non-Anchor programs
+#[no_mangle]
+pub extern "C" fn send_raw_instruction_c(
+    client: *mut SolClient,
+    program_id: *const c_char,
+    accounts: *const SolAccountMeta,
+    account_count: usize,
+    signers: *const *mut SolKeyPair, // List of signers, the first one pays the fees
+    signer_count: usize,
+    data_ptr: *const u8,
+    data_len: usize,
+    fee_payer: *mut SolKeyPair, // Pays the fees instead of the first signer; may be NULL
+) -> *mut c_char {
+    send_generic_transaction_with_discriminator(
+        client,
+        program_id,
+        std::ptr::null(),
+        SolDiscriminatorKind::None,
+        std::ptr::null(),
+        0,
+        accounts,
+        account_count,
+        signers,
+        signer_count,
+        data_ptr,
+        data_len,
+        fee_payer,
+    )
+}
+
+// Send an instruction prefixed according to `discriminator_kind`. `method_name` is
+// only read for `AnchorGlobal`; `discriminator` holds the bytes for `Custom` (any
+// length) and `EnumTag` (one byte).
+#[no_mangle]
+pub extern "C" fn send_generic_transaction_with_discriminator(
+    client: *mut SolClient,
+    program_id: *const c_char,
+    method_name: *const c_char,
+    discriminator_kind: SolDiscriminatorKind,
+    discriminator: *const u8,
+    discriminator_len: usize,
+    accounts: *const SolAccountMeta,
+    account_count: usize,
+    signers: *const *mut SolKeyPair, // List of signers, the first one pays the fees
+    signer_count: usize,
+    data_ptr: *const u8,
+    data_len: usize,
+    fee_payer: *mut SolKeyPair, // Pays the fees instead of the first signer; may be NULL
+) -> *mut c_char {
+    let client = unsafe { &mut *client };
+
+    let program_id = match program_pubkey_from_c(program_id) {
+        Ok(program_id) => program_id,
+        Err(err) => {
+            eprintln!("{}", err);
+            return std::ptr::null_mut();
+        }
+    };
+    let prefix = match prefix_from_c(
+        discriminator_kind,
+        method_name,
+        discriminator,
+        discriminator_len,
+    ) {
+        Ok(prefix) => prefix,
+        Err(err) => {
+            eprintln!("{}", err);
+            return std::ptr::null_mut();
+        }
+    };
+    let accounts = account_metas_from_c(accounts, account_count);
+
+    let signer_keypairs = keypairs_from_c(signers, signer_count);
+    let signer_refs: Vec<&dyn Signer> = signer_keypairs.iter().map(|s| s as &dyn Signer).collect();
+    let fee_payer = unsafe { fee_payer.as_ref() }.map(SolKeyPair::to_keypair);
+
+    let data = data_from_c(data_ptr, data_len);
+    _send_generic_transaction(
+        client,
+        &program_id,
+        &prefix,
+        accounts,
+        fee_payer.as_ref().map(|s| s as &dyn Signer),
+        &signer_refs,
+        data,
+    )
+}
+
+// Same as `send_generic_transaction_with_discriminator`, returning the full
+// result so a custom program error can be read as `error_code` and `error_name`
+#[no_mangle]
+pub extern "C" fn send_generic_transaction_with_result(
+    client: *mut SolClient,
+    program_id: *const c_char,
+    method_name: *const c_char,
+    discriminator_kind: SolDiscriminatorKind,
+    discriminator: *const u8,
+    discriminator_len: usize,
+    accounts: *const SolAccountMeta,
+    account_count: usize,
+    signers: *const *mut SolKeyPair, // List of signers, the first one pays the fees
+    signer_count: usize,
+    data_ptr: *const u8,
+    data_len: usize,
+    fee_payer: *mut SolKeyPair, // Pays the fees instead of the first signer; may be NULL
+) -> *mut SolTransactionResult {
+    let client = unsafe { &mut *client };
+
+    let program_id = match program_pubkey_from_c(program_id) {
+        Ok(program_id) => program_id,
+        Err(err) => return SolTransactionResult::failed(err).into_raw(),
+    };
+    let prefix = match prefix_from_c(
+        discriminator_kind,
+        method_name,
+        discriminator,
+        discriminator_len,
+    ) {
+        Ok(prefix) => prefix,
+        Err(err) => return SolTransactionResult::failed(err).into_raw(),
+    };
+    let accounts = account_metas_from_c(accounts, account_count);
+
+    let signer_keypairs = keypairs_from_c(signers, signer_count);
+    let signer_refs: Vec<&dyn Signer> = signer_keypairs.iter().map(|s| s as &dyn Signer).collect();
+    let fee_payer = unsafe { fee_payer.as_ref() }.map(SolKeyPair::to_keypair);
+
+    let data = data_from_c(data_ptr, data_len);
+    send_generic_instruction(
+        client,
+        &program_id,
+        &prefix,
+        accounts,
+        fee_payer.as_ref().map(|s| s as &dyn Signer),
+        &signer_refs,
+        data,
+    )
+    .into_raw()
+}
+
+// The instruction prefix for a discriminator passed from C
+fn prefix_from_c(
+    kind: SolDiscriminatorKind,
+    method_name: *const c_char,
+    discriminator: *const u8,
+    discriminator_len: usize,
+) -> Result<Vec<u8>, String> {
+    let method_name = if method_name.is_null() {
+        None
+    } else {
+        Some(
+            unsafe { CStr::from_ptr(method_name) }
+                .to_str()
+                .map_err(|_| "Invalid method name string".to_string())?,
+        )
     };
+    let discriminator = data_from_c(discriminator, discriminator_len);
+    instruction_prefix(kind, method_name, &discriminator)
+}
 
-    // Process signers
-    let signer_refs = unsafe {
+// Copy signer keypairs from C; NULL or a zero count means no signers
+pub(crate) fn keypairs_from_c(
+    signers: *const *mut SolKeyPair,
+    signer_count: usize,
+) -> Vec<Keypair> {
+    if signers.is_null() || signer_count == 0 {
+        return Vec::new();
+    }
+    unsafe {
         std::slice::from_raw_parts(signers, signer_count)
             .iter()
-            .map(|s| unsafe { &**s }) // Dereference raw pointers to SolKeyPair
-            .collect::<Vec<&SolKeyPair>>()
+            .map(|s| (**s).to_keypair())
+            .collect()
+    }
+}
+
+// Write the Anchor discriminator for `<namespace>:<name>` into an 8-byte buffer
+#[no_mangle]
+pub extern "C" fn get_anchor_discriminator(
+    namespace: *const c_char,
+    name: *const c_char,
+    discriminator_out: *mut u8,
+) -> bool {
+    let namespace = match unsafe { CStr::from_ptr(namespace) }.to_str() {
+        Ok(str) => str,
+        Err(_) => return false,
+    };
+    let name = match unsafe { CStr::from_ptr(name) }.to_str() {
+        Ok(str) => str,
+        Err(_) => return false,
     };
+    if discriminator_out.is_null() {
+        return false;
+    }
 
-    let payer = signer_refs
-        .first()
-        .expect("At least one signer (payer) required"); // Ensure the first signer is the payer
+    let discriminator = anchor_discriminator(namespace, name);
+    unsafe { std::ptr::copy_nonoverlapping(discriminator.as_ptr(), discriminator_out, 8) };
+    true
+}
+
+// Every account is writable; an account is a signer only if a matching signer was given
+fn legacy_account_metas(
+    account_pubkeys: &[SolPublicKey],
+    signers: &[&dyn Signer],
+) -> Vec<AccountMeta> {
+    // Deserialize account pubkeys
+    let mut accounts = account_pubkeys
+        .iter()
+        .map(|a| AccountMeta::new(a.to_pubkey(), false)) // Default signer = false
+        .collect::<Vec<AccountMeta>>();
 
     // Mark signer accounts as signers
-    for signer in &signer_refs {
+    for signer in signers {
         if let Some(account) = accounts
             .iter_mut()
-            .find(|acc| acc.pubkey == signer.get_pubkey())
+            .find(|acc| acc.pubkey == signer.pubkey())
         {
             account.is_signer = true;
         }
     }
+    accounts
+}
 
-    // Deserialize additional data if provided
-    let data = if data_ptr.is_null() {
-        vec![]
+// Copy instruction data from C; NULL or zero length means no data
+pub(crate) fn data_from_c(data_ptr: *const u8, data_len: usize) -> Vec<u8> {
+    if data_ptr.is_null() || data_len == 0 {
+        Vec::new()
     } else {
         unsafe { std::slice::from_raw_parts(data_ptr, data_len).to_vec() }
-    };
+    }
+}
 
-    // Create the transaction instruction
-    let instruction = create_instruction(program_id, method_name, accounts, data);
+pub fn _send_generic_transaction(
+    client: &SolClient,
+    program_id: &Pubkey,
+    discriminator: &[u8],
+    accounts: Vec<AccountMeta>,
+    fee_payer: Option<&dyn Signer>,
+    signers: &[&dyn Signer],
+    data: Vec<u8>,
+) -> *mut c_char {
+    result_text(send_generic_instruction(
+        client,
+        program_id,
+        discriminator,
+        accounts,
+        fee_payer,
+        signers,
+        data,
+    ))
+}
 
-    // Get latest blockhash
-    let blockhash = match client.rpc_client.get_latest_blockhash() {
-        Ok(bh) => bh,
-        Err(e) => {
-            eprintln!("Failed to get blockhash: {:?}", e);
-            return CString::new("Failed to fetch blockhash")
-                .unwrap()
-                .into_raw();
-        }
+// Send one instruction and wait for confirmation. Without a separate fee payer
+// the first signer pays.
+pub fn send_generic_instruction(
+    client: &SolClient,
+    program_id: &Pubkey,
+    discriminator: &[u8],
+    accounts: Vec<AccountMeta>,
+    fee_payer: Option<&dyn Signer>,
+    signers: &[&dyn Signer],
+    data: Vec<u8>,
+) -> SolTransactionResult {
+    let payer = match fee_payer.or_else(|| signers.first().copied()) {
+        Some(payer) => payer,
+        None => return SolTransactionResult::failed("At least one signer (payer) required"),
     };
+    let signers: Vec<&dyn Signer> = fee_payer
+        .into_iter()
+        .chain(signers.iter().copied())
+        .collect();
 
-    // Convert signers to Keypair list
-    let signer_keypairs: Vec<Keypair> = signer_refs.iter().map(|s| s.to_keypair()).collect();
-
-    let signer_refs: Vec<&Keypair> = signer_keypairs.iter().collect();
-
-    let transaction = Transaction::new_signed_with_payer(
-        &[instruction],
-        Some(&payer.get_pubkey()), // Payer must be the first signer
-        &signer_refs,
-        blockhash,
-    );
-
-    let result = client.rpc_client.send_and_confirm_transaction(&transaction);
+    let instruction = create_instruction(program_id, discriminator, accounts, data);
+    send_instructions(client, &[instruction], &payer.pubkey(), &signers)
+}
 
-    match result {
-        Ok(sig) => CString::new(sig.to_string()).unwrap().into_raw(),
-        Err(err) => CString::new(format!("Transaction failed: {:?}", err))
-            .unwrap()
-            .into_raw(),
-    }
+// The signature on success, otherwise the error text; the rest is freed
+pub(crate) fn result_text(mut result: SolTransactionResult) -> *mut c_char {
+    let text = if result.success {
+        std::mem::replace(&mut result.signature, std::ptr::null_mut())
+    } else {
+        std::mem::replace(&mut result.error, std::ptr::null_mut())
+    };
+    free_transaction_result(result.into_raw());
+    text
 }
 
 // Initialize Account
@@ -195,13 +617,40 @@ pub extern "C" fn initialize_account_c(
 
     let program_id = unsafe { CStr::from_ptr(program_id).to_str().unwrap() };
     let account = &unsafe { &mut *account }.to_keypair();
+    let payer = &payer.to_keypair();
+
+    _initialize_account(client, payer, account, program_id);
+}
+
+#[no_mangle]
+pub extern "C" fn initialize_account_with_signer(
+    client: *mut SolClient,
+    payer: *mut SolSigner,
+    account: *mut SolSigner,
+    program_id: *const c_char,
+) {
+    let client = unsafe { &mut *client };
+    let payer = unsafe { &*payer };
+    let account = unsafe { &*account };
+
+    let program_id = unsafe { CStr::from_ptr(program_id).to_str().unwrap() };
 
+    _initialize_account(client, payer, account, program_id);
+}
+
+fn _initialize_account(
+    client: &SolClient,
+    payer: &dyn Signer,
+    account: &dyn Signer,
+    program_id: &str,
+) {
+    let program_id = Pubkey::from_str(program_id).expect("Invalid program ID");
     let instruction = create_instruction(
-        program_id,
-        "initialize",
+        &program_id,
+        &get_discriminator("initialize"),
         vec![
             AccountMeta::new(account.pubkey(), true),
-            AccountMeta::new(payer.to_keypair().pubkey(), true),
+            AccountMeta::new(payer.pubkey(), true),
             AccountMeta::new_readonly(system_program::ID, false),
         ],
         vec![],
@@ -215,12 +664,18 @@ pub extern "C" fn initialize_account_c(
         }
     };
 
-    let transaction = Transaction::new_signed_with_payer(
+    let transaction = match sign_transaction(
         &[instruction],
-        Some(&payer.to_keypair().pubkey()),
-        &[&payer.to_keypair(), &account],
+        &payer.pubkey(),
+        &[payer, account],
         blockhash,
-    );
+    ) {
+        Ok(transaction) => transaction,
+        Err(err) => {
+            eprintln!("❌ {}", err);
+            return;
+        }
+    };
 
     match client.rpc_client.send_and_confirm_transaction(&transaction) {
         Ok(sig) => {
@@ -240,7 +695,7 @@ pub extern "C" fn free_client(client: *mut SolClient) {
         return;
     }
     unsafe {
-        Box::from_raw(client);
+        drop(Box::from_raw(client));
     }
 }
 
@@ -250,6 +705,6 @@ pub extern "C" fn free_payer(payer: *mut SolKeyPair) {
         return;
     }
     unsafe {
-        Box::from_raw(payer);
+        drop(Box::from_raw(payer));
     }
 }
@@ -2,13 +2,10 @@ use std::ffi::{c_char, CString};
 
 use solana_account_decoder::UiAccountData;
 use solana_client::rpc_request::TokenAccountsFilter;
-use solana_sdk::{
-    instruction::Instruction, program_pack::Pack, pubkey::Pubkey, signer::Signer,
-    transaction::Transaction,
-};
-use spl_associated_token_account;
+use solana_sdk::{instruction::Instruction, program_pack::Pack, pubkey::Pubkey, signer::Signer};
 use spl_token::state::Mint; // Add this line to import the module
 
+use crate::signer::{sign_transaction, SolSigner};
 use crate::wallet::SolKeyPair;
 use crate::{client::SolClient, wallet::SolPublicKey};
 
@@ -135,11 +132,11 @@ pub extern "C" fn free_token_list(list: *mut TokenList) {
         let list = Box::from_raw(list);
         for i in 0..list.len {
             let token_info = &mut *list.data.add(i);
-            CString::from_raw(token_info.mint as *mut c_char);
-            CString::from_raw(token_info.balance as *mut c_char);
-            CString::from_raw(token_info.owner as *mut c_char);
+            drop(CString::from_raw(token_info.mint as *mut c_char));
+            drop(CString::from_raw(token_info.balance as *mut c_char));
+            drop(CString::from_raw(token_info.owner as *mut c_char));
         }
-        Vec::from_raw_parts(list.data, list.len, list.len);
+        drop(Vec::from_raw_parts(list.data, list.len, list.len));
     }
 }
 
@@ -166,7 +163,87 @@ pub extern "C" fn transfer_sol(
         &*recipient
     };
 
-    let sender_pubkey = sender.get_pubkey();
+    let sender = sender.to_keypair();
+    _transfer_sol(client, &sender, &sender, recipient, lamports)
+}
+
+#[no_mangle]
+pub extern "C" fn transfer_sol_with_signer(
+    client: *mut SolClient,
+    sender: *mut SolSigner,
+    recipient: *mut SolPublicKey,
+    lamports: u64,
+    fee_payer: *const SolSigner, // NULL means the sender pays
+) -> bool {
+    // Safety: Ensure pointers are not null
+    let client = unsafe {
+        assert!(!client.is_null());
+        &*client
+    };
+
+    let sender = unsafe {
+        assert!(!sender.is_null());
+        &*sender
+    };
+
+    let recipient = unsafe {
+        assert!(!recipient.is_null());
+        &*recipient
+    };
+
+    let fee_payer: &dyn Signer = match unsafe { fee_payer.as_ref() } {
+        Some(fee_payer) => fee_payer,
+        None => sender,
+    };
+    _transfer_sol(client, sender, fee_payer, recipient, lamports)
+}
+
+// Same as `transfer_sol`, with fees paid by `fee_payer` (NULL means the sender pays)
+#[no_mangle]
+pub extern "C" fn transfer_sol_with_fee_payer(
+    client: *mut SolClient,
+    sender: *mut SolKeyPair,
+    recipient: *mut SolPublicKey,
+    lamports: u64,
+    fee_payer: *mut SolKeyPair,
+) -> bool {
+    // Safety: Ensure pointers are not null
+    let client = unsafe {
+        assert!(!client.is_null());
+        &*client
+    };
+
+    let sender = unsafe {
+        assert!(!sender.is_null());
+        &*sender
+    }
+    .to_keypair();
+
+    let recipient = unsafe {
+        assert!(!recipient.is_null());
+        &*recipient
+    };
+
+    match unsafe { fee_payer.as_ref() } {
+        Some(fee_payer) => _transfer_sol(
+            client,
+            &sender,
+            &fee_payer.to_keypair(),
+            recipient,
+            lamports,
+        ),
+        None => _transfer_sol(client, &sender, &sender, recipient, lamports),
+    }
+}
+
+fn _transfer_sol(
+    client: &SolClient,
+    sender: &dyn Signer,
+    fee_payer: &dyn Signer,
+    recipient: &SolPublicKey,
+    lamports: u64,
+) -> bool {
+    let sender_pubkey = sender.pubkey();
     let recipient_pubkey = Pubkey::new_from_array(recipient.data);
 
     // Verify that the sender's account exists
@@ -200,12 +277,18 @@ pub extern "C" fn transfer_sol(
     };
 
     // Step 3: Create and sign the transaction
-    let mut transaction = Transaction::new_signed_with_payer(
+    let transaction = match sign_transaction(
         &[transfer_instruction],
-        Some(&sender.to_keypair().pubkey()), // Fee payer
-        &[&sender.to_keypair()],             // Required signer
+        &fee_payer.pubkey(),
+        &[fee_payer, sender], // Required signers
         recent_blockhash,
-    );
+    ) {
+        Ok(transaction) => transaction,
+        Err(err) => {
+            eprintln!("{}", err);
+            return false;
+        }
+    };
 
     // Step 4: Send and confirm the transaction
     match client.rpc_client.send_transaction(&transaction) {
@@ -252,14 +335,110 @@ pub extern "C" fn transfer_spl(
         &*mint
     };
 
-    let sender_pubkey = sender.to_keypair().pubkey();
+    let sender = sender.to_keypair();
+    _transfer_spl(client, &sender, &sender, recipient, mint, amount)
+}
+
+#[no_mangle]
+pub extern "C" fn transfer_spl_with_signer(
+    client: *mut SolClient,
+    sender: *mut SolSigner,
+    recipient: *mut SolPublicKey,
+    mint: *mut SolPublicKey,
+    amount: u64,
+    fee_payer: *const SolSigner, // NULL means the sender pays
+) -> bool {
+    // Safety: Ensure pointers are not null
+    let client = unsafe {
+        assert!(!client.is_null());
+        &*client
+    };
+
+    let sender = unsafe {
+        assert!(!sender.is_null());
+        &*sender
+    };
+
+    let recipient = unsafe {
+        assert!(!recipient.is_null());
+        &*recipient
+    };
+
+    let mint = unsafe {
+        assert!(!mint.is_null());
+        &*mint
+    };
+
+    let fee_payer: &dyn Signer = match unsafe { fee_payer.as_ref() } {
+        Some(fee_payer) => fee_payer,
+        None => sender,
+    };
+    _transfer_spl(client, sender, fee_payer, recipient, mint, amount)
+}
+
+// Same as `transfer_spl`, with fees and any recipient token account rent paid
+// by `fee_payer` (NULL means the sender pays)
+#[no_mangle]
+pub extern "C" fn transfer_spl_with_fee_payer(
+    client: *mut SolClient,
+    sender: *mut SolKeyPair,
+    recipient: *mut SolPublicKey,
+    mint: *mut SolPublicKey,
+    amount: u64,
+    fee_payer: *mut SolKeyPair,
+) -> bool {
+    // Safety: Ensure pointers are not null
+    let client = unsafe {
+        assert!(!client.is_null());
+        &*client
+    };
+
+    let sender = unsafe {
+        assert!(!sender.is_null());
+        &*sender
+    }
+    .to_keypair();
+
+    let recipient = unsafe {
+        assert!(!recipient.is_null());
+        &*recipient
+    };
+
+    let mint = unsafe {
+        assert!(!mint.is_null());
+        &*mint
+    };
+
+    match unsafe { fee_payer.as_ref() } {
+        Some(fee_payer) => _transfer_spl(
+            client,
+            &sender,
+            &fee_payer.to_keypair(),
+            recipient,
+            mint,
+            amount,
+        ),
+        None => _transfer_spl(client, &sender, &sender, recipient, mint, amount),
+    }
+}
+
+fn _transfer_spl(
+    client: &SolClient,
+    sender: &dyn Signer,
+    fee_payer: &dyn Signer,
+    recipient: &SolPublicKey,
+    mint: &SolPublicKey,
+    amount: u64,
+) -> bool {
+    let sender_pubkey = sender.pubkey();
     let recipient_pubkey = Pubkey::new_from_array(recipient.data);
     let mint_pubkey = mint.to_pubkey();
 
     // Step 1: Get or create recipient's associated token account
     let recipient_assoc = match _get_or_create_associated_token_account(
         client,
-        sender,
+        fee_payer,
+        fee_payer,
         &recipient_pubkey,
         &mint_pubkey,
     ) {
@@ -282,8 +461,8 @@ pub extern "C" fn transfer_spl(
         &spl_token::id(),
         &sender_assoc,
         &recipient_assoc,
-        &sender.to_keypair().pubkey(),
-        &[&sender.to_keypair().pubkey()],
+        &sender_pubkey,
+        &[&sender_pubkey],
         amount,
     ) {
         Ok(instruction) => instruction,
@@ -303,12 +482,18 @@ pub extern "C" fn transfer_spl(
     };
 
     // Step 5: Create and sign the transaction
-    let mut transaction = Transaction::new_signed_with_payer(
+    let transaction = match sign_transaction(
         &[transfer_instruction],
-        Some(&sender.to_keypair().pubkey()), // Fee payer
-        &[&sender.to_keypair()],             // Required signers
+        &fee_payer.pubkey(),
+        &[fee_payer, sender], // Required signers
         recent_blockhash,
-    );
+    ) {
+        Ok(transaction) => transaction,
+        Err(err) => {
+            eprintln!("{}", err);
+            return false;
+        }
+    };
 
     // Step 6: Send and confirm the transaction
     match client.rpc_client.send_transaction(&transaction) {
@@ -348,6 +533,77 @@ pub extern "C" fn create_spl_token(
         &*mint
     };
 
+    let payer = payer.to_keypair();
+    _create_spl_token(client, &payer, &mint.to_keypair(), &payer)
+}
+
+// Same as `create_spl_token`, with the transaction fee paid by `fee_payer`
+// while `payer` funds the mint account (NULL means `payer` pays both)
+#[no_mangle]
+pub extern "C" fn create_spl_token_with_fee_payer(
+    client: *mut SolClient,
+    payer: *mut SolKeyPair,
+    mint: *mut SolKeyPair,
+    fee_payer: *mut SolKeyPair,
+) -> bool {
+    let client = unsafe {
+        assert!(!client.is_null());
+        &*client
+    };
+    let payer = unsafe {
+        assert!(!payer.is_null());
+        &*payer
+    }
+    .to_keypair();
+    let mint = unsafe {
+        assert!(!mint.is_null());
+        &*mint
+    }
+    .to_keypair();
+
+    let fee_payer = unsafe { fee_payer.as_ref() }.map(SolKeyPair::to_keypair);
+    _create_spl_token(client, &payer, &mint, fee_payer.as_ref().unwrap_or(&payer))
+}
+
+#[no_mangle]
+pub extern "C" fn create_spl_token_with_signer(
+    client: *mut SolClient,
+    payer: *mut SolSigner,
+    mint: *mut SolSigner,
+    fee_payer: *const SolSigner, // NULL means `payer` pays the fee
+) -> bool {
+    // Safety: Ensure the client pointer is not null
+    let client = unsafe {
+        assert!(!client.is_null());
+        &*client
+    };
+
+    let payer = unsafe {
+        assert!(!payer.is_null());
+        &*payer
+    };
+
+    let mint = unsafe {
+        assert!(!mint.is_null());
+        &*mint
+    };
+
+    let fee_payer: &dyn Signer = match unsafe { fee_payer.as_ref() } {
+        Some(fee_payer) => fee_payer,
+        None => payer,
+    };
+    _create_spl_token(client, payer, mint, fee_payer)
+}
+
+fn _create_spl_token(
+    client: &SolClient,
+    payer: &dyn Signer,
+    mint: &dyn Signer,
+    fee_payer: &dyn Signer,
+) -> bool {
+    let payer_pubkey = payer.pubkey();
+    let mint_pubkey = mint.pubkey();
+
     let minimum_balance_for_rent_exemption = match client
         .rpc_client
         .get_minimum_balance_for_rent_exemption(Mint::LEN)
@@ -363,8 +619,8 @@ pub extern "C" fn create_spl_token(
     };
 
     let create_account_instruction: Instruction = solana_sdk::system_instruction::create_account(
-        &&payer.to_keypair().pubkey(),
-        &mint.to_keypair().pubkey(),
+        &payer_pubkey,
+        &mint_pubkey,
         minimum_balance_for_rent_exemption,
         Mint::LEN as u64,
         &spl_token::ID,
@@ -373,8 +629,8 @@ pub extern "C" fn create_spl_token(
     // Create the mint instruction
     let mint_instruction = spl_token::instruction::initialize_mint(
         &spl_token::id(),
-        &mint.to_keypair().pubkey(),
-        &mint.to_keypair().pubkey(),
+        &mint_pubkey,
+        &mint_pubkey,
         None,
         9, // Decimals
     );
@@ -396,19 +652,25 @@ pub extern "C" fn create_spl_token(
     };
 
     // Create and sign the transaction
-    let mut transaction = Transaction::new_signed_with_payer(
+    let transaction = match sign_transaction(
         &[create_account_instruction, mint_instruction],
-        Some(&payer.to_keypair().pubkey()),
-        &[&mint.to_keypair(), &payer.to_keypair()],
+        &fee_payer.pubkey(),
+        &[fee_payer, mint, payer],
         recent_blockhash,
-    );
+    ) {
+        Ok(transaction) => transaction,
+        Err(err) => {
+            eprintln!("{}", err);
+            return false;
+        }
+    };
 
     // Send the transaction
     match client.rpc_client.send_transaction(&transaction) {
         Ok(_) => true,
         Err(err) => {
             eprintln!("Error sending and confirming transaction: {:?}", err);
-            return false;
+            false
         }
     }
 }
@@ -488,10 +750,17 @@ pub extern "C" fn get_or_create_associated_token_account(
 
     // Extract public keys
     let owner_pubkey = Pubkey::new_from_array(owner.data);
-    let mint_pubkey = mint.to_keypair().pubkey();
+    let mint_pubkey = mint.get_pubkey();
 
     // Call the helper function to get or create the associated token account
-    match _get_or_create_associated_token_account(client, payer, &owner_pubkey, &mint_pubkey) {
+    let payer = payer.to_keypair();
+    match _get_or_create_associated_token_account(
+        client,
+        &payer,
+        &payer,
+        &owner_pubkey,
+        &mint_pubkey,
+    ) {
         Ok(assoc) => Box::into_raw(Box::new(SolPublicKey {
             data: assoc.to_bytes(),
         })),
@@ -502,9 +771,101 @@ pub extern "C" fn get_or_create_associated_token_account(
     }
 }
 
+// Same as `get_or_create_associated_token_account`, with the transaction fee
+// paid by `fee_payer` while `payer` funds the account (NULL means `payer` pays both)
+#[no_mangle]
+pub extern "C" fn get_or_create_associated_token_account_with_fee_payer(
+    client: *mut SolClient,
+    payer: *mut SolKeyPair,
+    owner: *mut SolPublicKey,
+    mint: *mut SolKeyPair,
+    fee_payer: *mut SolKeyPair,
+) -> *mut SolPublicKey {
+    let client = unsafe {
+        assert!(!client.is_null());
+        &*client
+    };
+    let payer = unsafe {
+        assert!(!payer.is_null());
+        &*payer
+    }
+    .to_keypair();
+    let owner = unsafe {
+        assert!(!owner.is_null());
+        &*owner
+    };
+    let mint = unsafe {
+        assert!(!mint.is_null());
+        &*mint
+    };
+
+    let fee_payer = unsafe { fee_payer.as_ref() }.map(SolKeyPair::to_keypair);
+    match _get_or_create_associated_token_account(
+        client,
+        &payer,
+        fee_payer.as_ref().unwrap_or(&payer),
+        &owner.to_pubkey(),
+        &mint.get_pubkey(),
+    ) {
+        Ok(assoc) => Box::into_raw(Box::new(SolPublicKey::new(assoc))),
+        Err(err) => {
+            eprintln!("Error managing associated token account: {}", err);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn get_or_create_associated_token_account_with_signer(
+    client: *mut SolClient,
+    payer: *mut SolSigner,
+    owner: *mut SolPublicKey,
+    mint: *mut SolPublicKey,
+    fee_payer: *const SolSigner, // NULL means `payer` pays the fee
+) -> *mut SolPublicKey {
+    // Safety: Ensure pointers are not null
+    let client = unsafe {
+        assert!(!client.is_null());
+        &*client
+    };
+    let payer = unsafe {
+        assert!(!payer.is_null());
+        &*payer
+    };
+    let owner = unsafe {
+        assert!(!owner.is_null());
+        &*owner
+    };
+    let mint = unsafe {
+        assert!(!mint.is_null());
+        &*mint
+    };
+
+    let fee_payer: &dyn Signer = match unsafe { fee_payer.as_ref() } {
+        Some(fee_payer) => fee_payer,
+        None => payer,
+    };
+    match _get_or_create_associated_token_account(
+        client,
+        payer,
+        fee_payer,
+        &owner.to_pubkey(),
+        &mint.to_pubkey(),
+    ) {
+        Ok(assoc) => Box::into_raw(Box::new(SolPublicKey::new(assoc))),
+        Err(err) => {
+            eprintln!("Error managing associated token account: {}", err);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+// `payer` funds a new account and `fee_payer` pays the transaction fee; they
+// may be the same wallet
 pub fn _get_or_create_associated_token_account(
     client: &SolClient,
-    payer: &SolKeyPair,
+    payer: &dyn Signer,
+    fee_payer: &dyn Signer,
     recipient_pubkey: &Pubkey,
     mint_pubkey: &Pubkey,
 ) -> Result<Pubkey, String> {
@@ -512,7 +873,7 @@ pub fn _get_or_create_associated_token_account(
         spl_associated_token_account::get_associated_token_address(recipient_pubkey, mint_pubkey);
 
     match client.rpc_client.get_account(&assoc) {
-        Ok(account) => {
+        Ok(_) => {
             // Associated token account exists
             println!("Associated token account already exists at: {}", assoc);
             Ok(assoc)
@@ -525,9 +886,10 @@ pub fn _get_or_create_associated_token_account(
         {
             // Create the associated token account
             println!("Associated token account does not exist. Proceeding to create...");
+            let payer_pubkey = payer.pubkey();
             let assoc_instruction =
                 spl_associated_token_account::instruction::create_associated_token_account(
-                    &payer.to_keypair().pubkey(),
+                    &payer_pubkey,
                     recipient_pubkey,
                     mint_pubkey,
                     &spl_token::id(),
@@ -538,12 +900,12 @@ pub fn _get_or_create_associated_token_account(
                 .get_latest_blockhash()
                 .map_err(|err| format!("Error fetching latest blockhash: {:?}", err))?;
 
-            let mut assoc_transaction = Transaction::new_signed_with_payer(
+            let assoc_transaction = sign_transaction(
                 &[assoc_instruction],
-                Some(&payer.to_keypair().pubkey()),
-                &[&payer.to_keypair()],
+                &fee_payer.pubkey(),
+                &[fee_payer, payer],
                 recent_blockhash,
-            );
+            )?;
 
             client
                 .rpc_client
@@ -589,13 +951,108 @@ pub extern "C" fn mint_spl(
         &*recipient
     };
 
-    let mint_authority_pubkey = mint_authority.to_keypair().pubkey();
+    let payer = payer.to_keypair();
+    _mint_spl(
+        client,
+        &payer,
+        &payer,
+        &mint_authority.to_keypair(),
+        recipient,
+        amount,
+    )
+}
+
+// Same as `mint_spl`, with the transaction fees paid by `fee_payer` while
+// `payer` funds the recipient's token account (NULL means `payer` pays both)
+#[no_mangle]
+pub extern "C" fn mint_spl_with_fee_payer(
+    client: *mut SolClient,
+    payer: *mut SolKeyPair,
+    mint_authority: *mut SolKeyPair,
+    recipient: *mut SolPublicKey,
+    amount: u64,
+    fee_payer: *mut SolKeyPair,
+) -> bool {
+    let client = unsafe {
+        assert!(!client.is_null());
+        &*client
+    };
+    let payer = unsafe {
+        assert!(!payer.is_null());
+        &*payer
+    }
+    .to_keypair();
+    let mint_authority = unsafe {
+        assert!(!mint_authority.is_null());
+        &*mint_authority
+    }
+    .to_keypair();
+    let recipient = unsafe {
+        assert!(!recipient.is_null());
+        &*recipient
+    };
+
+    let fee_payer = unsafe { fee_payer.as_ref() }.map(SolKeyPair::to_keypair);
+    _mint_spl(
+        client,
+        &payer,
+        fee_payer.as_ref().unwrap_or(&payer),
+        &mint_authority,
+        recipient,
+        amount,
+    )
+}
+
+#[no_mangle]
+pub extern "C" fn mint_spl_with_signer(
+    client: *mut SolClient,
+    payer: *mut SolSigner,
+    mint_authority: *mut SolSigner,
+    recipient: *mut SolPublicKey,
+    amount: u64,
+    fee_payer: *const SolSigner, // NULL means `payer` pays the fees
+) -> bool {
+    // Safety: Ensure pointers are not null
+    let client = unsafe {
+        assert!(!client.is_null());
+        &*client
+    };
+    let payer = unsafe {
+        assert!(!payer.is_null());
+        &*payer
+    };
+    let mint_authority = unsafe {
+        assert!(!mint_authority.is_null());
+        &*mint_authority
+    };
+    let recipient = unsafe {
+        assert!(!recipient.is_null());
+        &*recipient
+    };
+
+    let fee_payer: &dyn Signer = match unsafe { fee_payer.as_ref() } {
+        Some(fee_payer) => fee_payer,
+        None => payer,
+    };
+    _mint_spl(client, payer, fee_payer, mint_authority, recipient, amount)
+}
+
+fn _mint_spl(
+    client: &SolClient,
+    payer: &dyn Signer,
+    fee_payer: &dyn Signer,
+    mint_authority: &dyn Signer,
+    recipient: &SolPublicKey,
+    amount: u64,
+) -> bool {
+    let mint_authority_pubkey = mint_authority.pubkey();
     let recipient_pubkey = Pubkey::new_from_array(recipient.data);
 
     // Get or create associated token account
     let assoc = match _get_or_create_associated_token_account(
         client,
         payer,
+        fee_payer,
         &recipient_pubkey,
         &mint_authority_pubkey,
     ) {
@@ -611,8 +1068,8 @@ pub extern "C" fn mint_spl(
         &spl_token::id(),
         &mint_authority_pubkey,
         &assoc,
-        &mint_authority.to_keypair().pubkey(),
-        &[&mint_authority.to_keypair().pubkey()],
+        &mint_authority_pubkey,
+        &[&mint_authority_pubkey],
         amount,
     ) {
         Ok(instruction) => instruction,
@@ -632,12 +1089,18 @@ pub extern "C" fn mint_spl(
     };
 
     // Step 5: Create and sign the mint transaction
-    let mut transaction = Transaction::new_signed_with_payer(
+    let transaction = match sign_transaction(
         &[mint_instruction],
-        Some(&payer.to_keypair().pubkey()), // Fee payer
-        &[&mint_authority.to_keypair(), &payer.to_keypair()], // Required signers
+        &fee_payer.pubkey(),          // Fee payer
+        &[mint_authority, fee_payer], // Required signers
         recent_blockhash,
-    );
+    ) {
+        Ok(transaction) => transaction,
+        Err(err) => {
+            eprintln!("{}", err);
+            return false;
+        }
+    };
 
     // Step 6: Send and confirm the mint transaction
     match client.rpc_client.send_transaction(&transaction) {
@@ -0,0 +1,566 @@
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use solana_sdk::address_lookup_table::AddressLookupTableAccount;
+use solana_sdk::hash::Hash;
+use solana_sdk::instruction::{AccountMeta, Instruction};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signature, Signer};
+use solana_sdk::system_instruction;
+use solana_sdk::transaction::VersionedTransaction;
+use std::ffi::CString;
+use std::os::raw::c_char;
+
+use crate::client::SolClient;
+use crate::lookup_table::fetch_lookup_table;
+use crate::nonce::fetch_nonce_account;
+use crate::program_error::{decode_program_error, send_failed, ProgramError};
+use crate::signer::{
+    compile_message, sign_transaction, sign_transaction_with_lookup_tables, SolSigner,
+};
+use crate::smart_contract::data_from_c;
+use crate::wallet::{SolKeyPair, SolPublicKey};
+
+// Account passed to an instruction, with explicit signer and writable flags
+#[repr(C)]
+pub struct SolAccountMeta {
+    pub pubkey: SolPublicKey,
+    pub is_signer: bool,
+    pub is_writable: bool,
+}
+
+impl SolAccountMeta {
+    pub fn to_account_meta(&self) -> AccountMeta {
+        if self.is_writable {
+            AccountMeta::new(self.pubkey.to_pubkey(), self.is_signer)
+        } else {
+            AccountMeta::new_readonly(self.pubkey.to_pubkey(), self.is_signer)
+        }
+    }
+}
+
+// Outcome of sending or simulating a transaction. Text fields are NULL when
+// absent; release with `free_transaction_result`.
+#[repr(C)]
+pub struct SolTransactionResult {
+    pub success: bool,
+    pub signature: *mut c_char,
+    pub error: *mut c_char,
+    pub logs: *mut c_char, // Program logs joined by newlines
+    pub units_consumed: u64,
+    // Set when an instruction failed with a program's custom error code
+    pub has_program_error: bool,
+    pub instruction_index: u8,
+    pub program_id: SolPublicKey,
+    pub error_code: u32,
+    pub error_name: *mut c_char, // From the program's IDL or Anchor's built-in errors
+    pub error_message: *mut c_char, // From the program's IDL or Anchor's built-in errors
+}
+
+impl SolTransactionResult {
+    pub fn sent(signature: &Signature) -> Self {
+        SolTransactionResult {
+            success: true,
+            signature: to_c_string(signature.to_string()),
+            error: std::ptr::null_mut(),
+            logs: std::ptr::null_mut(),
+            units_consumed: 0,
+            has_program_error: false,
+            instruction_index: 0,
+            program_id: SolPublicKey::new(Pubkey::default()),
+            error_code: 0,
+            error_name: std::ptr::null_mut(),
+            error_message: std::ptr::null_mut(),
+        }
+    }
+
+    pub fn failed(error: impl Into<String>) -> Self {
+        let error = error.into();
+        eprintln!("{}", error);
+        SolTransactionResult {
+            success: false,
+            signature: std::ptr::null_mut(),
+            error: to_c_string(error),
+            logs: std::ptr::null_mut(),
+            units_consumed: 0,
+            has_program_error: false,
+            instruction_index: 0,
+            program_id: SolPublicKey::new(Pubkey::default()),
+            error_code: 0,
+            error_name: std::ptr::null_mut(),
+            error_message: std::ptr::null_mut(),
+        }
+    }
+
+    // A failure with the decoded custom error of the failing instruction
+    pub fn program_failed(context: &str, error: &ProgramError) -> Self {
+        let mut result = SolTransactionResult::failed(format!("{}: {}", context, error));
+        result.has_program_error = true;
+        result.instruction_index = error.instruction_index;
+        result.program_id = SolPublicKey::new(error.program_id);
+        result.error_code = error.code;
+        result.error_name = error.name.clone().map_or(std::ptr::null_mut(), to_c_string);
+        result.error_message = error
+            .message
+            .clone()
+            .map_or(std::ptr::null_mut(), to_c_string);
+        result
+    }
+
+    pub fn into_raw(self) -> *mut SolTransactionResult {
+        Box::into_raw(Box::new(self))
+    }
+}
+
+fn to_c_string(value: String) -> *mut c_char {
+    CString::new(value).map_or(std::ptr::null_mut(), CString::into_raw)
+}
+
+// Sign `instructions` with a fresh blockhash, send them and wait for confirmation
+pub fn send_instructions(
+    client: &SolClient,
+    instructions: &[Instruction],
+    payer: &Pubkey,
+    signers: &[&dyn Signer],
+) -> SolTransactionResult {
+    let recent_blockhash = match client.rpc_client.get_latest_blockhash() {
+        Ok(blockhash) => blockhash,
+        Err(err) => {
+            return SolTransactionResult::failed(format!("Failed to fetch blockhash: {:?}", err))
+        }
+    };
+    let transaction = match sign_transaction(instructions, payer, signers, recent_blockhash) {
+        Ok(transaction) => transaction,
+        Err(err) => return SolTransactionResult::failed(err),
+    };
+
+    match client.rpc_client.send_and_confirm_transaction(&transaction) {
+        Ok(signature) => SolTransactionResult::sent(&signature),
+        Err(err) => send_failed(client, &transaction, &err),
+    }
+}
+
+// ==================== Transaction Builder ==================== //
+
+enum BuilderSigner {
+    Keypair(Box<Keypair>),
+    // Borrowed handle; the caller keeps it alive until the builder is freed
+    Handle(*const SolSigner),
+}
+
+impl BuilderSigner {
+    fn as_signer(&self) -> &dyn Signer {
+        match self {
+            BuilderSigner::Keypair(keypair) => keypair,
+            BuilderSigner::Handle(signer) => unsafe { &**signer },
+        }
+    }
+}
+
+// Collects instructions and signers so several instructions land atomically
+// in one transaction
+#[derive(Default)]
+pub struct SolTransactionBuilder {
+    instructions: Vec<Instruction>,
+    fee_payer: Option<Pubkey>,
+    signers: Vec<BuilderSigner>,
+    lookup_tables: Vec<Pubkey>,
+    // Durable nonce account and its authority
+    nonce: Option<(Pubkey, Pubkey)>,
+}
+
+impl SolTransactionBuilder {
+    pub fn add_instruction(&mut self, instruction: Instruction) {
+        self.instructions.push(instruction);
+    }
+
+    // The explicit fee payer, falling back to the first signer
+    pub fn fee_payer(&self) -> Option<Pubkey> {
+        self.fee_payer.or_else(|| {
+            self.signers
+                .first()
+                .map(|signer| signer.as_signer().pubkey())
+        })
+    }
+
+    pub fn signers(&self) -> Vec<&dyn Signer> {
+        self.signers.iter().map(BuilderSigner::as_signer).collect()
+    }
+
+    // Check the builder before any network round trip
+    fn validate(&self) -> Result<Pubkey, String> {
+        if self.instructions.is_empty() {
+            return Err("Transaction has no instructions".to_string());
+        }
+        self.fee_payer()
+            .ok_or_else(|| "Transaction has no fee payer or signers".to_string())
+    }
+
+    // With a durable nonce the transaction must start by advancing it
+    fn instructions(&self) -> Vec<Instruction> {
+        let mut instructions = Vec::with_capacity(self.instructions.len() + 1);
+        if let Some((nonce_account, nonce_authority)) = &self.nonce {
+            instructions.push(system_instruction::advance_nonce_account(
+                nonce_account,
+                nonce_authority,
+            ));
+        }
+        instructions.extend(self.instructions.iter().cloned());
+        instructions
+    }
+
+    // `recent_blockhash` is the stored nonce when a nonce account is set
+    pub fn build(
+        &self,
+        recent_blockhash: Hash,
+        lookup_tables: &[AddressLookupTableAccount],
+    ) -> Result<VersionedTransaction, String> {
+        let fee_payer = self.validate()?;
+        sign_transaction_with_lookup_tables(
+            &self.instructions(),
+            &fee_payer,
+            &self.signers(),
+            recent_blockhash,
+            lookup_tables,
+        )
+    }
+
+    // Compile without signing, for transactions signed later or by other parties
+    pub fn build_unsigned(
+        &self,
+        recent_blockhash: Hash,
+        lookup_tables: &[AddressLookupTableAccount],
+    ) -> Result<VersionedTransaction, String> {
+        let fee_payer = self.validate()?;
+        let message = compile_message(
+            &self.instructions(),
+            &fee_payer,
+            recent_blockhash,
+            lookup_tables,
+        )?;
+        Ok(VersionedTransaction {
+            signatures: vec![
+                Signature::default();
+                usize::from(message.header().num_required_signatures)
+            ],
+            message,
+        })
+    }
+
+    // Fetch the lookup tables and a recent blockhash (or the stored nonce)
+    fn fetch_recent_state(
+        &self,
+        client: &SolClient,
+    ) -> Result<(Hash, Vec<AddressLookupTableAccount>), String> {
+        self.validate()?;
+        let lookup_tables = self
+            .lookup_tables
+            .iter()
+            .map(|address| fetch_lookup_table(client, address))
+            .collect::<Result<Vec<_>, _>>()?;
+        let recent_blockhash = match &self.nonce {
+            Some((nonce_account, _)) => fetch_nonce_account(client, nonce_account)?.0.blockhash(),
+            None => client
+                .rpc_client
+                .get_latest_blockhash()
+                .map_err(|err| format!("Failed to fetch blockhash: {:?}", err))?,
+        };
+        Ok((recent_blockhash, lookup_tables))
+    }
+
+    pub fn build_with_client(&self, client: &SolClient) -> Result<VersionedTransaction, String> {
+        let (recent_blockhash, lookup_tables) = self.fetch_recent_state(client)?;
+        self.build(recent_blockhash, &lookup_tables)
+    }
+
+    pub fn build_unsigned_with_client(
+        &self,
+        client: &SolClient,
+    ) -> Result<VersionedTransaction, String> {
+        let (recent_blockhash, lookup_tables) = self.fetch_recent_state(client)?;
+        self.build_unsigned(recent_blockhash, &lookup_tables)
+    }
+
+    // Lookup tables can only be resolved through a client
+    pub fn has_lookup_tables(&self) -> bool {
+        !self.lookup_tables.is_empty()
+    }
+}
+
+// Read `account_count` account metas from C
+pub fn account_metas_from_c(
+    accounts: *const SolAccountMeta,
+    account_count: usize,
+) -> Vec<AccountMeta> {
+    if accounts.is_null() || account_count == 0 {
+        return Vec::new();
+    }
+    unsafe { std::slice::from_raw_parts(accounts, account_count) }
+        .iter()
+        .map(SolAccountMeta::to_account_meta)
+        .collect()
+}
+
+// ==================== Builder Functions ==================== //
+
+#[no_mangle]
+pub extern "C" fn sol_transaction_builder_new() -> *mut SolTransactionBuilder {
+    Box::into_raw(Box::new(SolTransactionBuilder::default()))
+}
+
+// Append an instruction with complete instruction data for any program
+#[no_mangle]
+pub extern "C" fn sol_transaction_builder_add_instruction(
+    builder: *mut SolTransactionBuilder,
+    program_id: *const SolPublicKey,
+    accounts: *const SolAccountMeta,
+    account_count: usize,
+    data: *const u8,
+    data_len: usize,
+) -> bool {
+    let builder = unsafe {
+        assert!(!builder.is_null());
+        &mut *builder
+    };
+    let program_id = unsafe {
+        assert!(!program_id.is_null());
+        &*program_id
+    };
+    if data.is_null() && data_len > 0 {
+        eprintln!("Instruction data is NULL but data_len is {}", data_len);
+        return false;
+    }
+
+    builder.add_instruction(Instruction::new_with_bytes(
+        program_id.to_pubkey(),
+        &data_from_c(data, data_len),
+        account_metas_from_c(accounts, account_count),
+    ));
+    true
+}
+
+// Set the fee payer; it must also be added as a signer before sending
+#[no_mangle]
+pub extern "C" fn sol_transaction_builder_set_fee_payer(
+    builder: *mut SolTransactionBuilder,
+    fee_payer: *const SolPublicKey,
+) {
+    let builder = unsafe {
+        assert!(!builder.is_null());
+        &mut *builder
+    };
+    let fee_payer = unsafe {
+        assert!(!fee_payer.is_null());
+        &*fee_payer
+    };
+
+    builder.fee_payer = Some(fee_payer.to_pubkey());
+}
+
+// Add a wallet as signer; the builder keeps its own copy of the key
+#[no_mangle]
+pub extern "C" fn sol_transaction_builder_add_signer(
+    builder: *mut SolTransactionBuilder,
+    signer: *mut SolKeyPair,
+) {
+    let builder = unsafe {
+        assert!(!builder.is_null());
+        &mut *builder
+    };
+    let signer = unsafe {
+        assert!(!signer.is_null());
+        &*signer
+    };
+
+    builder
+        .signers
+        .push(BuilderSigner::Keypair(Box::new(signer.to_keypair())));
+}
+
+// Add a signer handle; it must outlive the builder
+#[no_mangle]
+pub extern "C" fn sol_transaction_builder_add_sol_signer(
+    builder: *mut SolTransactionBuilder,
+    signer: *const SolSigner,
+) {
+    let builder = unsafe {
+        assert!(!builder.is_null());
+        &mut *builder
+    };
+    assert!(!signer.is_null());
+
+    builder.signers.push(BuilderSigner::Handle(signer));
+}
+
+// Compress account keys found in this address lookup table; the table is
+// fetched each time the transaction is built
+#[no_mangle]
+pub extern "C" fn sol_transaction_builder_add_lookup_table(
+    builder: *mut SolTransactionBuilder,
+    lookup_table: *const SolPublicKey,
+) {
+    let builder = unsafe {
+        assert!(!builder.is_null());
+        &mut *builder
+    };
+    let lookup_table = unsafe {
+        assert!(!lookup_table.is_null());
+        &*lookup_table
+    };
+
+    builder.lookup_tables.push(lookup_table.to_pubkey());
+}
+
+// Use a durable nonce instead of a recent blockhash so the transaction does
+// not expire. `nonce_authority` must also be added as a signer.
+#[no_mangle]
+pub extern "C" fn sol_transaction_builder_set_nonce(
+    builder: *mut SolTransactionBuilder,
+    nonce_account: *const SolPublicKey,
+    nonce_authority: *const SolPublicKey,
+) {
+    let builder = unsafe {
+        assert!(!builder.is_null());
+        &mut *builder
+    };
+    let nonce_account = unsafe {
+        assert!(!nonce_account.is_null());
+        &*nonce_account
+    };
+    let nonce_authority = unsafe {
+        assert!(!nonce_authority.is_null());
+        &*nonce_authority
+    };
+
+    builder.nonce = Some((nonce_account.to_pubkey(), nonce_authority.to_pubkey()));
+}
+
+#[no_mangle]
+pub extern "C" fn sol_transaction_builder_send(
+    builder: *const SolTransactionBuilder,
+    client: *mut SolClient,
+) -> *mut SolTransactionResult {
+    let builder = unsafe {
+        assert!(!builder.is_null());
+        &*builder
+    };
+    let client = unsafe {
+        assert!(!client.is_null());
+        &*client
+    };
+
+    let transaction = match builder.build_with_client(client) {
+        Ok(transaction) => transaction,
+        Err(err) => return SolTransactionResult::failed(err).into_raw(),
+    };
+
+    match client.rpc_client.send_and_confirm_transaction(&transaction) {
+        Ok(signature) => SolTransactionResult::sent(&signature).into_raw(),
+        Err(err) => send_failed(client, &transaction, &err).into_raw(),
+    }
+}
+
+// Simulate without sending; the result carries program logs and compute units
+#[no_mangle]
+pub extern "C" fn sol_transaction_builder_simulate(
+    builder: *const SolTransactionBuilder,
+    client: *mut SolClient,
+) -> *mut SolTransactionResult {
+    let builder = unsafe {
+        assert!(!builder.is_null());
+        &*builder
+    };
+    let client = unsafe {
+        assert!(!client.is_null());
+        &*client
+    };
+
+    let transaction = match builder.build_with_client(client) {
+        Ok(transaction) => transaction,
+        Err(err) => return SolTransactionResult::failed(err).into_raw(),
+    };
+
+    let simulation = match client.rpc_client.simulate_transaction(&transaction) {
+        Ok(response) => response.value,
+        Err(err) => {
+            return SolTransactionResult::failed(format!("Simulation failed: {:?}", err)).into_raw()
+        }
+    };
+
+    let logs = simulation.logs.clone().unwrap_or_default();
+    let mut result = match &simulation.err {
+        Some(err) => match decode_program_error(client, &transaction.message, err, &logs) {
+            Some(program_error) => {
+                SolTransactionResult::program_failed("Simulation failed", &program_error)
+            }
+            None => SolTransactionResult::failed(format!("Simulation failed: {:?}", err)),
+        },
+        None => SolTransactionResult::sent(&transaction.signatures[0]),
+    };
+    result.logs = simulation
+        .logs
+        .map_or(std::ptr::null_mut(), |logs| to_c_string(logs.join("\n")));
+    result.units_consumed = simulation.units_consumed.unwrap_or(0);
+    result.into_raw()
+}
+
+// Sign with a fresh blockhash and return the wire format as base64
+#[no_mangle]
+pub extern "C" fn sol_transaction_builder_serialize(
+    builder: *const SolTransactionBuilder,
+    client: *mut SolClient,
+) -> *mut c_char {
+    let builder = unsafe {
+        assert!(!builder.is_null());
+        &*builder
+    };
+    let client = unsafe {
+        assert!(!client.is_null());
+        &*client
+    };
+
+    let transaction = match builder.build_with_client(client) {
+        Ok(transaction) => transaction,
+        Err(err) => {
+            eprintln!("{}", err);
+            return std::ptr::null_mut();
+        }
+    };
+
+    match bincode::serialize(&transaction) {
+        Ok(bytes) => to_c_string(BASE64.encode(bytes)),
+        Err(err) => {
+            eprintln!("Failed to serialize transaction: {}", err);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn free_sol_transaction_builder(builder: *mut SolTransactionBuilder) {
+    if builder.is_null() {
+        return;
+    }
+    unsafe {
+        drop(Box::from_raw(builder));
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn free_transaction_result(result: *mut SolTransactionResult) {
+    if result.is_null() {
+        return;
+    }
+    unsafe {
+        let result = Box::from_raw(result);
+        for value in [
+            result.signature,
+            result.error,
+            result.logs,
+            result.error_name,
+            result.error_message,
+        ] {
+            if !value.is_null() {
+                drop(CString::from_raw(value));
+            }
+        }
+    }
+}
@@ -1,10 +1,15 @@
-use serde_json;
 use solana_program::pubkey::Pubkey;
-use solana_sdk::signature::{Keypair, Signer};
-use std::ffi::CStr;
+use solana_sdk::signature::{keypair_from_seed, Keypair, Signature, Signer};
+use std::borrow::Cow;
+use std::ffi::{c_void, CStr};
 use std::fs::File;
 use std::io::{BufReader, BufWriter, Write};
 use std::os::raw::c_char;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::Duration;
+use zeroize::{Zeroize, Zeroizing};
 #[repr(C)]
 pub struct SolKeyPair {
     pub bytes: [u8; 64], // Store the keypair as raw bytes
@@ -47,6 +52,13 @@ impl SolKeyPair {
     }
 }
 
+// Wipe the secret key when the wallet is released through `free_keypair`/`free_payer`
+impl Drop for SolKeyPair {
+    fn drop(&mut self) {
+        self.bytes.zeroize();
+    }
+}
+
 #[repr(C)]
 pub struct SolPublicKey {
     pub data: [u8; 32],
@@ -64,11 +76,34 @@ impl SolPublicKey {
     }
 }
 
+#[repr(C)]
+pub struct SolSignature {
+    pub data: [u8; 64],
+}
+
+impl SolSignature {
+    pub fn new(signature: Signature) -> Self {
+        SolSignature {
+            data: signature.into(),
+        }
+    }
+
+    pub fn to_signature(&self) -> Signature {
+        Signature::from(self.data)
+    }
+}
+
 #[repr(C)]
 pub struct SolSecretKey {
     pub data: [u8; 64],
 }
 
+impl Drop for SolSecretKey {
+    fn drop(&mut self) {
+        self.data.zeroize();
+    }
+}
+
 #[no_mangle]
 pub extern "C" fn get_public_key(wallet: *mut SolKeyPair) -> *mut SolPublicKey {
     let wallet = unsafe {
@@ -76,14 +111,11 @@ pub extern "C" fn get_public_key(wallet: *mut SolKeyPair) -> *mut SolPublicKey {
         &mut *wallet
     };
 
-    let keypair = wallet.to_keypair();
-    let public_key = keypair.pubkey();
-    let public_key = SolPublicKey {
-        data: public_key.to_bytes(),
-    };
+    let public_key = SolPublicKey::new(wallet.get_pubkey());
     Box::into_raw(Box::new(public_key))
 }
 
+// Export a heap copy of the secret key; release it with `free_secret_key`
 #[no_mangle]
 pub extern "C" fn get_secret_key(wallet: *mut SolKeyPair) -> *mut SolSecretKey {
     let wallet = unsafe {
@@ -91,8 +123,7 @@ pub extern "C" fn get_secret_key(wallet: *mut SolKeyPair) -> *mut SolSecretKey {
         &mut *wallet
     };
 
-    let secret_key = wallet.to_keypair().to_bytes();
-    let secret_key = SolSecretKey { data: secret_key };
+    let secret_key = SolSecretKey { data: wallet.bytes };
     Box::into_raw(Box::new(secret_key))
 }
 
@@ -103,8 +134,7 @@ pub extern "C" fn get_wallet_address(wallet: *mut SolKeyPair) -> *mut c_char {
         &mut *wallet
     };
 
-    let public_key = wallet.to_keypair().pubkey();
-    let address = public_key.to_string();
+    let address = wallet.get_pubkey().to_string();
     let c_str = std::ffi::CString::new(address).unwrap();
     c_str.into_raw()
 }
@@ -141,13 +171,13 @@ pub extern "C" fn create_and_save_wallet(file_path: *const c_char) -> *mut SolKe
 }
 
 // Save the wallet's private key to a file in Solana CLI format
-fn save_wallet_to_file(keypair: &Keypair, file_path: &str) -> std::io::Result<()> {
+pub fn save_wallet_to_file(keypair: &Keypair, file_path: &str) -> std::io::Result<()> {
     let file = File::create(file_path)?;
     let mut writer = BufWriter::new(file);
 
     // Convert the keypair's secret key to a byte array and serialize to JSON
-    let secret_key_bytes = keypair.to_bytes();
-    let json_data = serde_json::to_string(&secret_key_bytes.to_vec())?;
+    let secret_key_bytes = Zeroizing::new(keypair.to_bytes().to_vec());
+    let json_data = Zeroizing::new(serde_json::to_string(&*secret_key_bytes)?);
 
     // Write the JSON data to the file
     writer.write_all(json_data.as_bytes())?;
@@ -157,8 +187,7 @@ fn save_wallet_to_file(keypair: &Keypair, file_path: &str) -> std::io::Result<()
 
 #[no_mangle]
 pub extern "C" fn new_keypair() -> *mut SolKeyPair {
-    let keypair = Keypair::new();
-    Box::into_raw(Box::new(SolKeyPair::from_existing(keypair)))
+    Box::into_raw(Box::new(SolKeyPair::new()))
 }
 
 // Load a Solana wallet from the file, returning the public key as a C string
@@ -182,12 +211,12 @@ pub extern "C" fn load_wallet_from_file(file_path: *const c_char) -> *mut SolKey
 }
 
 // Load the wallet's private key from the file in Solana CLI format
-fn load_wallet(file_path: &str) -> Result<Keypair, std::io::Error> {
+pub fn load_wallet(file_path: &str) -> Result<Keypair, std::io::Error> {
     let file = File::open(file_path)?;
     let reader = BufReader::new(file);
 
     // Deserialize the JSON back into a byte array
-    let secret_key_bytes: Vec<u8> = serde_json::from_reader(reader)?;
+    let secret_key_bytes: Zeroizing<Vec<u8>> = Zeroizing::new(serde_json::from_reader(reader)?);
 
     // Ensure the byte array is exactly 64 bytes long
     if secret_key_bytes.len() != 64 {
@@ -198,13 +227,27 @@ fn load_wallet(file_path: &str) -> Result<Keypair, std::io::Error> {
     }
 
     // Create a keypair from the secret key bytes
-    let keypair = Keypair::from_bytes(&secret_key_bytes).map_err(|_| {
-        std::io::Error::new(
-            std::io::ErrorKind::InvalidData,
-            "Failed to load keypair from bytes",
-        )
-    })?;
+    let keypair = keypair_from_secret_bytes(&secret_key_bytes)
+        .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err))?;
+
+    Ok(keypair)
+}
 
+// Build a keypair from 64 secret key bytes, checking that the public key half
+// really belongs to the seed half (`Keypair::from_bytes` does not)
+pub fn keypair_from_secret_bytes(bytes: &[u8]) -> Result<Keypair, String> {
+    if bytes.len() != 64 {
+        return Err(format!(
+            "Invalid secret key length: {} bytes (expected 64)",
+            bytes.len()
+        ));
+    }
+
+    let keypair = keypair_from_seed(&bytes[..32])
+        .map_err(|err| format!("Failed to load keypair from bytes: {}", err))?;
+    if keypair.pubkey().as_ref() != &bytes[32..] {
+        return Err("Public key does not match the secret key".to_string());
+    }
     Ok(keypair)
 }
 
@@ -248,3 +291,188 @@ pub extern "C" fn get_address_from_pubkey(pubkey: *const SolPublicKey) -> *mut c
 
     c_str.into_raw()
 }
+
+// ==================== Vanity Addresses ==================== //
+
+// Receives the number of keys tried so far; return false to cancel the search
+pub type SolGrindProgressCallback =
+    Option<extern "C" fn(user_data: *mut c_void, attempts: u64) -> bool>;
+
+const BASE58_ALPHABET: &str = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+fn is_base58(value: &str, case_insensitive: bool) -> bool {
+    value.chars().all(|c| {
+        BASE58_ALPHABET.contains(c)
+            || (case_insensitive
+                && (BASE58_ALPHABET.contains(c.to_ascii_lowercase())
+                    || BASE58_ALPHABET.contains(c.to_ascii_uppercase())))
+    })
+}
+
+fn matches_vanity(address: &str, prefix: &str, suffix: &str, case_insensitive: bool) -> bool {
+    let address = if case_insensitive {
+        Cow::Owned(address.to_lowercase())
+    } else {
+        Cow::Borrowed(address)
+    };
+    address.starts_with(prefix) && address.ends_with(suffix)
+}
+
+// Search for a keypair whose address starts with `prefix` and ends with `suffix`
+pub fn grind(
+    prefix: &str,
+    suffix: &str,
+    case_insensitive: bool,
+    threads: usize,
+    max_attempts: u64,
+    mut progress: impl FnMut(u64) -> bool,
+) -> Option<Keypair> {
+    let (prefix, suffix) = if case_insensitive {
+        (prefix.to_lowercase(), suffix.to_lowercase())
+    } else {
+        (prefix.to_string(), suffix.to_string())
+    };
+    let attempts = AtomicU64::new(0);
+    let stop = AtomicBool::new(false);
+    // A match is sent back as soon as it is found; the channel disconnects once
+    // every worker has given up
+    let (found_tx, found_rx) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..threads {
+            let found_tx = found_tx.clone();
+            let (attempts, stop, prefix, suffix) = (&attempts, &stop, &prefix, &suffix);
+            scope.spawn(move || {
+                while !stop.load(Ordering::Relaxed) {
+                    let tried = attempts.fetch_add(1, Ordering::Relaxed);
+                    if max_attempts != 0 && tried >= max_attempts {
+                        break;
+                    }
+
+                    let keypair = Keypair::new();
+                    let address = keypair.pubkey().to_string();
+                    if matches_vanity(&address, prefix, suffix, case_insensitive) {
+                        stop.store(true, Ordering::Relaxed);
+                        let _ = found_tx.send(keypair);
+                        break;
+                    }
+                }
+            });
+        }
+        drop(found_tx);
+
+        // Progress is reported from the calling thread so C callbacks need no locking
+        let found = loop {
+            match found_rx.recv_timeout(Duration::from_millis(100)) {
+                Ok(keypair) => break Some(keypair),
+                Err(RecvTimeoutError::Disconnected) => break None,
+                Err(RecvTimeoutError::Timeout) => {
+                    if !progress(attempts.load(Ordering::Relaxed)) {
+                        break None;
+                    }
+                }
+            }
+        };
+        stop.store(true, Ordering::Relaxed);
+        found
+    })
+}
+
+// Grind a vanity address in parallel. `threads` of 0 uses every core and
+// `max_attempts` of 0 searches until found or cancelled by `progress_callback`.
+// When `file_path` is not NULL the keypair is also saved in Solana CLI format.
+#[no_mangle]
+pub extern "C" fn grind_keypair(
+    prefix: *const c_char,
+    suffix: *const c_char,
+    case_insensitive: bool,
+    threads: u32,
+    max_attempts: u64,
+    progress_callback: SolGrindProgressCallback,
+    user_data: *mut c_void,
+    file_path: *const c_char,
+) -> *mut SolKeyPair {
+    let optional_str = |value: *const c_char| {
+        if value.is_null() {
+            Some("")
+        } else {
+            unsafe { CStr::from_ptr(value) }.to_str().ok()
+        }
+    };
+    let (prefix, suffix) = match (optional_str(prefix), optional_str(suffix)) {
+        (Some(prefix), Some(suffix)) => (prefix, suffix),
+        _ => return std::ptr::null_mut(),
+    };
+    let file_path = if file_path.is_null() {
+        None
+    } else {
+        match unsafe { CStr::from_ptr(file_path) }.to_str() {
+            Ok(str) => Some(str),
+            Err(_) => return std::ptr::null_mut(),
+        }
+    };
+
+    // Characters outside the base58 alphabet (0, O, I, l) can never match
+    if !is_base58(prefix, case_insensitive) || !is_base58(suffix, case_insensitive) {
+        eprintln!("Vanity pattern contains characters that are not valid base58");
+        return std::ptr::null_mut();
+    }
+    if prefix.len() + suffix.len() > 44 {
+        eprintln!("Vanity pattern is longer than an address");
+        return std::ptr::null_mut();
+    }
+
+    let threads = match threads {
+        0 => thread::available_parallelism().map_or(1, |n| n.get()),
+        n => n as usize,
+    };
+    let progress = |attempts| match progress_callback {
+        Some(callback) => callback(user_data, attempts),
+        None => true,
+    };
+
+    let keypair = match grind(
+        prefix,
+        suffix,
+        case_insensitive,
+        threads,
+        max_attempts,
+        progress,
+    ) {
+        Some(keypair) => keypair,
+        None => {
+            eprintln!("No matching address found");
+            return std::ptr::null_mut();
+        }
+    };
+
+    if let Some(file_path) = file_path {
+        if let Err(err) = save_wallet_to_file(&keypair, file_path) {
+            eprintln!("Failed to save wallet {}: {}", file_path, err);
+            return std::ptr::null_mut();
+        }
+    }
+    Box::into_raw(Box::new(SolKeyPair::from_existing(keypair)))
+}
+
+// Zeroize and free a wallet returned by the SDK
+#[no_mangle]
+pub extern "C" fn free_keypair(wallet: *mut SolKeyPair) {
+    if wallet.is_null() {
+        return;
+    }
+    unsafe {
+        drop(Box::from_raw(wallet));
+    }
+}
+
+// Zeroize and free a secret key returned by `get_secret_key`
+#[no_mangle]
+pub extern "C" fn free_secret_key(secret_key: *mut SolSecretKey) {
+    if secret_key.is_null() {
+        return;
+    }
+    unsafe {
+        drop(Box::from_raw(secret_key));
+    }
+}
@@ -1,5 +1,6 @@
 #include <stdio.h>
 #include <stdlib.h>
+#include <string.h>
 #include <sys/time.h>
 
 #include "header/anchor_counter_interface.c"
@@ -445,6 +446,1405 @@ void test_counter()
     free_payer(payer);
 }
 
+void test_record_and_replay()
+{
+    printf("=== Test: Record and Replay RPC ===\n");
+    const char *recording_path = "rpc_recording.jsonl";
+    remove(recording_path);
+
+    SolKeyPair *wallet = load_wallet_from_file(file_path);
+    if (wallet == NULL)
+    {
+        printf("Failed to load wallet.\n");
+        return;
+    }
+
+    // Record a balance query against devnet
+    SolClient *recording = new_sol_client_recording(devnet_url, recording_path);
+    if (recording == NULL)
+    {
+        printf("Failed to create recording client.\n");
+        return;
+    }
+    uint64_t recorded_balance = get_balance(recording, &wallet->pubkey);
+    free_client(recording);
+
+    // Replay the same query without touching the network
+    SolClient *replay = new_sol_client_replay(recording_path);
+    if (replay == NULL)
+    {
+        printf("Failed to create replay client.\n");
+        return;
+    }
+    uint64_t replayed_balance = get_balance(replay, &wallet->pubkey);
+    free_client(replay);
+
+    printf("Recorded Balance: %lu, Replayed Balance: %lu (%s)\n",
+           recorded_balance, replayed_balance,
+           recorded_balance == replayed_balance ? "match" : "MISMATCH");
+    printf("=== End Test: Record and Replay RPC ===\n");
+}
+
+void test_mnemonic_wallet()
+{
+    printf("=== Test: Mnemonic Wallet ===\n");
+    char phrase[256];
+    SolKeyPair *wallet = create_wallet_with_mnemonic(12, "", phrase, sizeof(phrase));
+    if (wallet == NULL)
+    {
+        printf("Failed to create mnemonic wallet.\n");
+        return;
+    }
+
+    printf("Mnemonic valid: %s\n", validate_mnemonic(phrase) == SolMnemonicStatus_Valid ? "true" : "false");
+
+    // Restoring with the Phantom scheme must give back the same address
+    SolKeyPair *restored = wallet_from_mnemonic_with_scheme(phrase, "", SolDerivationScheme_Phantom, 0);
+    if (restored != NULL)
+    {
+        printf("Created Address:  %s\n", get_wallet_address(wallet));
+        printf("Restored Address: %s\n", get_wallet_address(restored));
+    }
+    else
+    {
+        printf("Failed to restore mnemonic wallet.\n");
+    }
+
+    wipe_mnemonic_buffer(phrase, sizeof(phrase));
+    printf("=== End Test: Mnemonic Wallet ===\n");
+}
+
+void test_encrypted_keystore()
+{
+    printf("=== Test: Encrypted Keystore ===\n");
+    const char *keystore_path = "wallet_keystore.json";
+    SolKeystoreStatus status;
+
+    SolKeyPair *wallet = create_and_save_encrypted_wallet(keystore_path, "correct horse");
+    if (wallet == NULL)
+    {
+        printf("Failed to create encrypted wallet.\n");
+        return;
+    }
+    printf("Keystore Wallet Address: %s\n", get_wallet_address(wallet));
+
+    SolKeyPair *loaded = load_encrypted_wallet(keystore_path, "wrong password", &status);
+    printf("Load with wrong password: %s\n", status == SolKeystoreStatus_WrongPassword ? "rejected" : "UNEXPECTED");
+
+    status = change_keystore_password(keystore_path, "correct horse", "battery staple");
+    printf("Change password: %s\n", status == SolKeystoreStatus_Ok ? "ok" : "failed");
+
+    loaded = load_encrypted_wallet(keystore_path, "battery staple", &status);
+    if (loaded != NULL)
+    {
+        printf("Loaded Wallet Address: %s\n", get_wallet_address(loaded));
+    }
+    else
+    {
+        printf("Failed to load encrypted wallet (status %d).\n", status);
+    }
+    printf("=== End Test: Encrypted Keystore ===\n");
+}
+
+void test_signer_handle()
+{
+    printf("=== Test: Signer Handle ===\n");
+    SolSigner *signer = sol_signer_from_file(file_path_payer);
+    if (signer == NULL)
+    {
+        printf("Failed to load signer.\n");
+        return;
+    }
+
+    SolPublicKey pubkey = sol_signer_pubkey(signer);
+    printf("Signer Address: %s\n", get_address_from_pubkey(&pubkey));
+    printf("Signer memory locked: %s\n", sol_signer_is_memory_locked(signer) ? "true" : "false");
+
+    SolClient *client = new_sol_client(devnet_url);
+    SolKeyPair *recipient = load_wallet_from_file(file_path_recipient);
+    if (client != NULL && recipient != NULL)
+    {
+        bool success = transfer_sol_with_signer(client, signer, &recipient->pubkey, 1000000, NULL);
+        printf("Transfer with signer: %s\n", success ? "success" : "failed");
+    }
+
+    free_keypair(recipient);
+    free_sol_signer(signer);
+    printf("=== End Test: Signer Handle ===\n");
+}
+
+void test_message_signing()
+{
+    printf("=== Test: Message Signing ===\n");
+    SolKeyPair *wallet = load_wallet_from_file(file_path_payer);
+    if (wallet == NULL)
+    {
+        printf("Failed to load wallet.\n");
+        return;
+    }
+
+    const char *message = "Sign in to example.com";
+    size_t message_len = strlen(message);
+    SolSignature signature;
+
+    if (sign_message(wallet, (const uint8_t *)message, message_len, &signature))
+    {
+        char *signature_str = get_signature_string(&signature);
+        printf("Signature: %s\n", signature_str);
+        printf("Signature valid: %s\n",
+               verify_signature(&wallet->pubkey, (const uint8_t *)message, message_len, &signature) ? "true" : "false");
+        free(signature_str);
+    }
+
+    if (sign_offchain_message(wallet, 0, (const uint8_t *)message, message_len, &signature))
+    {
+        printf("Off-chain signature valid: %s\n",
+               verify_offchain_message(&wallet->pubkey, 0, (const uint8_t *)message, message_len, &signature) ? "true" : "false");
+    }
+
+    free_keypair(wallet);
+    printf("=== End Test: Message Signing ===\n");
+}
+
+void test_sign_in_with_solana()
+{
+    printf("=== Test: Sign-In With Solana ===\n");
+    SolKeyPair *wallet = load_wallet_from_file(file_path_payer);
+    if (wallet == NULL)
+    {
+        printf("Failed to load wallet.\n");
+        return;
+    }
+
+    char *message = siws_create_message("game.example", &wallet->pubkey, "Sign in to play",
+                                        "https://game.example/login", "devnet", "a1b2c3d4", 0, 0);
+    if (message == NULL)
+    {
+        printf("Failed to create sign-in message.\n");
+        free_keypair(wallet);
+        return;
+    }
+    printf("%s\n", message);
+
+    SolSignature signature;
+    if (siws_sign_message(wallet, message, &signature))
+    {
+        SolSiwsStatus status = siws_verify(message, &signature, "game.example", "a1b2c3d4", 0);
+        printf("Sign-in valid: %s\n", status == SolSiwsStatus_Ok ? "true" : "false");
+    }
+
+    SolSiwsMessage *parsed = siws_parse_message(message);
+    if (parsed != NULL)
+    {
+        printf("Parsed domain: %s, nonce: %s\n", parsed->domain, parsed->nonce);
+        free_siws_message(parsed);
+    }
+    free(message);
+
+    // Wallets may add Not Before, Request ID and Resources
+    char *address = get_wallet_address(wallet);
+    char wallet_message[1024];
+    snprintf(wallet_message, sizeof(wallet_message),
+             "game.example wants you to sign in with your Solana account:\n%s\n\n"
+             "URI: https://game.example/login\nVersion: 1\nChain ID: devnet\nNonce: a1b2c3d4\n"
+             "Issued At: 2026-01-01T00:00:00Z\nNot Before: 2026-01-02T00:00:00Z\nRequest ID: r-17\n"
+             "Resources:\n- https://game.example/terms\n- ipfs://bafybeiemxf5abjwjbikoz4mc3a3dla6ual3jsgpdr4cjr3oz3evfyavhwq",
+             address);
+    free(address);
+    parsed = siws_parse_message(wallet_message);
+    bool fields_ok = parsed != NULL && parsed->not_before == 1767312000 &&
+                     strcmp(parsed->request_id, "r-17") == 0 && parsed->resource_count == 2 &&
+                     strcmp(parsed->resources[1], "ipfs://bafybeiemxf5abjwjbikoz4mc3a3dla6ual3jsgpdr4cjr3oz3evfyavhwq") == 0;
+    free_siws_message(parsed);
+    bool window_ok = false;
+    if (siws_sign_message(wallet, wallet_message, &signature))
+    {
+        window_ok = siws_verify(wallet_message, &signature, "game.example", "a1b2c3d4", 1767225600) == SolSiwsStatus_NotYetValid &&
+                    siws_verify(wallet_message, &signature, "game.example", "a1b2c3d4", 1767312000) == SolSiwsStatus_Ok &&
+                    siws_verify(wallet_message, &signature, "game.example", "", 1767312000) == SolSiwsStatus_InvalidArgument;
+    }
+    printf("Wallet fields: %s, validity window: %s\n", fields_ok ? "ok" : "FAILED", window_ok ? "ok" : "FAILED");
+
+    free_keypair(wallet);
+    printf("=== End Test: Sign-In With Solana ===\n");
+}
+
+void test_program_address()
+{
+    printf("=== Test: Program Derived Address ===\n");
+    SolPublicKey *program_id = get_pubkey_from_address("3CkKwWzHTvwnAURu8TD4JijeuYZkaPkU14QRGeGLHbSw");
+    if (program_id == NULL)
+    {
+        printf("Invalid program id.\n");
+        return;
+    }
+
+    const char *seed = "counter";
+    const uint8_t *seeds[] = {(const uint8_t *)seed};
+    uintptr_t seed_lens[] = {strlen(seed)};
+    SolPublicKey pda;
+    uint8_t bump;
+
+    if (find_program_address_c(seeds, seed_lens, 1, program_id, &pda, &bump))
+    {
+        char *address = get_address_from_pubkey(&pda);
+        printf("PDA: %s (bump %d)\n", address, bump);
+        printf("PDA on curve: %s\n", is_on_curve(&pda) ? "true" : "false");
+        free(address);
+    }
+
+    free(program_id);
+    printf("=== End Test: Program Derived Address ===\n");
+}
+
+void test_key_import_export()
+{
+    printf("=== Test: Key Import and Export ===\n");
+    SolKeyPair *wallet = load_wallet_from_file(file_path_payer);
+    if (wallet == NULL)
+    {
+        printf("Failed to load wallet.\n");
+        return;
+    }
+
+    char *secret_base58 = export_wallet_base58(wallet);
+    SolKeyPair *imported = wallet_from_base58(secret_base58);
+    if (imported != NULL)
+    {
+        char *address = get_wallet_address(imported);
+        printf("Imported Address: %s\n", address);
+        printf("Round trip matches: %s\n", memcmp(imported->bytes, wallet->bytes, 64) == 0 ? "true" : "false");
+        free(address);
+    }
+
+    free_secret_string(secret_base58);
+    free_keypair(imported);
+    free_keypair(wallet);
+    printf("=== End Test: Key Import and Export ===\n");
+}
+
+void test_keyring_wallets()
+{
+    printf("=== Test: Keyring ===\n");
+    SolKeyring *keyring = keyring_open("keyring", NULL);
+    if (keyring == NULL)
+    {
+        printf("Failed to open keyring.\n");
+        return;
+    }
+
+    keyring_import_file(keyring, "main", file_path_payer);
+    keyring_import_file(keyring, "mint authority", file_path_mint);
+    keyring_set_default(keyring, "main");
+
+    SolKeyringList *list = keyring_list(keyring);
+    SolKeyringEntry *entries = keyring_list_get_data(list);
+    for (size_t i = 0; i < keyring_list_get_len(list); i++)
+    {
+        char *address = get_address_from_pubkey(&entries[i].pubkey);
+        printf("%s%s: %s\n", entries[i].label, entries[i].is_default ? " (default)" : "", address);
+        free(address);
+    }
+    free_keyring_list(list);
+
+    SolKeyPair *wallet = keyring_get_default_wallet(keyring);
+    if (wallet != NULL)
+    {
+        char *address = get_wallet_address(wallet);
+        printf("Default Address: %s\n", address);
+        free(address);
+    }
+
+    free_keypair(wallet);
+    free_keyring(keyring);
+    printf("=== End Test: Keyring ===\n");
+}
+
+bool sign_with_wallet(void *user_data, const uint8_t *message, uintptr_t message_len, SolSignature *signature_out)
+{
+    // Stand-in for a remote signing service
+    return sign_message((SolKeyPair *)user_data, message, message_len, signature_out);
+}
+
+void test_external_signer()
+{
+    printf("=== Test: External Signer ===\n");
+    SolKeyPair *wallet = load_wallet_from_file(file_path_payer);
+    SolKeyPair *recipient = load_wallet_from_file(file_path_recipient);
+    SolClient *client = new_sol_client(devnet_url);
+    if (wallet == NULL || recipient == NULL || client == NULL)
+    {
+        printf("Failed to load wallets or client.\n");
+        return;
+    }
+
+    SolSigner *signer = sol_signer_from_callback(&wallet->pubkey, sign_with_wallet, wallet);
+    bool success = transfer_sol_with_signer(client, signer, &recipient->pubkey, 1000000, NULL);
+    printf("Transfer with external signer: %s\n", success ? "success" : "failed");
+
+    free_sol_signer(signer);
+    free_keypair(recipient);
+    free_keypair(wallet);
+    printf("=== End Test: External Signer ===\n");
+}
+
+bool print_grind_progress(void *user_data, uint64_t attempts)
+{
+    printf("Tried %llu keys...\n", (unsigned long long)attempts);
+    return true;
+}
+
+void test_vanity_address()
+{
+    printf("=== Test: Vanity Address ===\n");
+    SolKeyPair *wallet = grind_keypair("So", NULL, false, 0, 0, print_grind_progress, NULL, NULL);
+    if (wallet != NULL)
+    {
+        char *address = get_wallet_address(wallet);
+        printf("Vanity Address: %s\n", address);
+        free(address);
+    }
+
+    free_keypair(wallet);
+    printf("=== End Test: Vanity Address ===\n");
+}
+
+void test_transaction_builder()
+{
+    printf("=== Test: Transaction Builder ===\n");
+    SolClient *client = new_sol_client(devnet_url);
+    SolKeyPair *payer = load_wallet_from_file(file_path_payer);
+    SolKeyPair *recipient = load_wallet_from_file(file_path_recipient);
+    if (client == NULL || payer == NULL || recipient == NULL)
+    {
+        printf("Failed to load wallets or client.\n");
+        return;
+    }
+
+    SolTransactionBuilder *builder = sol_transaction_builder_new();
+
+    // System program transfer: u32 instruction index 2 followed by u64 lamports
+    SolPublicKey system_program = get_system_program_id();
+    SolAccountMeta transfer_accounts[] = {
+        {payer->pubkey, true, true},
+        {recipient->pubkey, false, true},
+    };
+    uint8_t transfer_data[12] = {2, 0, 0, 0};
+    uint64_t lamports = 1000000;
+    memcpy(transfer_data + 4, &lamports, sizeof(lamports));
+    sol_transaction_builder_add_instruction(builder, &system_program, transfer_accounts, 2, transfer_data, sizeof(transfer_data));
+
+    // Memo in the same transaction
+    SolPublicKey *memo_program = get_pubkey_from_address("MemoSq4gqABAXKb96qnH1TysNcWxMyWCqXgDLGmfcHr");
+    SolAccountMeta memo_accounts[] = {{payer->pubkey, true, false}};
+    const char *memo = "Sent from the C SDK";
+    sol_transaction_builder_add_instruction(builder, memo_program, memo_accounts, 1, (const uint8_t *)memo, strlen(memo));
+
+    sol_transaction_builder_add_signer(builder, payer);
+
+    SolTransactionResult *simulation = sol_transaction_builder_simulate(builder, client);
+    printf("Simulation: %s (%llu compute units)\n", simulation->success ? "success" : simulation->error,
+           (unsigned long long)simulation->units_consumed);
+    free_transaction_result(simulation);
+
+    SolTransactionResult *result = sol_transaction_builder_send(builder, client);
+    if (result->success)
+    {
+        printf("Transaction Signature: %s\n", result->signature);
+    }
+    else
+    {
+        printf("Transaction failed: %s\n", result->error);
+    }
+
+    free_transaction_result(result);
+    free_sol_transaction_builder(builder);
+    free(memo_program);
+    free_keypair(recipient);
+    free_keypair(payer);
+    printf("=== End Test: Transaction Builder ===\n");
+}
+
+void test_generic_with_metas()
+{
+    printf("=== Test: Generic Transaction With Account Flags ===\n");
+    SolClient *client = new_sol_client(devnet_url);
+    SolKeyPair *payer = load_wallet_from_file(file_path_payer);
+    SolKeyPair *account = new_keypair();
+    if (client == NULL || payer == NULL || account == NULL)
+    {
+        printf("Failed to load wallets or client.\n");
+        return;
+    }
+    const char *program_id = "3CkKwWzHTvwnAURu8TD4JijeuYZkaPkU14QRGeGLHbSw";
+
+    // initialize: counter and user sign, the system program is read-only
+    SolAccountMeta initialize_accounts[] = {
+        {account->pubkey, true, true},
+        {payer->pubkey, true, true},
+        {get_system_program_id(), false, false},
+    };
+    SolKeyPair *initialize_signers[] = {payer, account};
+    char *result = send_generic_transaction_with_metas(client, program_id, "initialize", initialize_accounts, 3, initialize_signers, 2, NULL, 0);
+    printf("Initialize Result: %s\n", result != NULL ? result : "failed");
+    free(result);
+
+    // increment: the counter is writable, the user only signs
+    SolAccountMeta increment_accounts[] = {
+        {account->pubkey, false, true},
+        {payer->pubkey, true, false},
+    };
+    SolKeyPair *increment_signers[] = {payer};
+    result = send_generic_transaction_with_metas(client, program_id, "increment", increment_accounts, 2, increment_signers, 1, NULL, 0);
+    printf("Increment Result: %s\n", result != NULL ? result : "failed");
+    free(result);
+
+    free_keypair(account);
+    free_keypair(payer);
+    printf("=== End Test: Generic Transaction With Account Flags ===\n");
+}
+
+void test_raw_instruction()
+{
+    printf("=== Test: Raw Instruction ===\n");
+    SolClient *client = new_sol_client(devnet_url);
+    SolKeyPair *payer = load_wallet_from_file(file_path_payer);
+    if (client == NULL || payer == NULL)
+    {
+        printf("Failed to load wallet or client.\n");
+        return;
+    }
+
+    // The memo program takes the memo text as its whole instruction data
+    SolAccountMeta memo_accounts[] = {{payer->pubkey, true, false}};
+    SolKeyPair *signers[] = {payer};
+    const char *memo = "Raw instruction from the C SDK";
+    char *result = send_raw_instruction_c(client, "MemoSq4gqABAXKb96qnH1TysNcWxMyWCqXgDLGmfcHr", memo_accounts, 1, signers, 1,
+                                          (const uint8_t *)memo, strlen(memo), NULL);
+    printf("Memo Result: %s\n", result != NULL ? result : "failed");
+    free(result);
+
+    // The Anchor discriminator matches the one recorded in the IDL
+    uint8_t discriminator[8];
+    get_anchor_discriminator("global", "increment", discriminator);
+    printf("increment discriminator:");
+    for (int i = 0; i < 8; i++)
+    {
+        printf(" %d", discriminator[i]);
+    }
+    printf("\n");
+
+    free_keypair(payer);
+    printf("=== End Test: Raw Instruction ===\n");
+}
+
+void test_lookup_table()
+{
+    printf("=== Test: Address Lookup Table ===\n");
+    SolClient *client = new_sol_client(devnet_url);
+    SolKeyPair *payer = load_wallet_from_file(file_path_payer);
+    SolKeyPair *recipient = load_wallet_from_file(file_path_recipient);
+    if (client == NULL || payer == NULL || recipient == NULL)
+    {
+        printf("Failed to load wallets or client.\n");
+        return;
+    }
+
+    SolPublicKey table;
+    SolTransactionResult *result = create_lookup_table(client, &payer->pubkey, payer, &table);
+    printf("Create: %s\n", result->success ? result->signature : result->error);
+    bool created = result->success;
+    free_transaction_result(result);
+    if (!created)
+    {
+        free_keypair(recipient);
+        free_keypair(payer);
+        return;
+    }
+
+    SolPublicKey addresses[] = {recipient->pubkey, get_system_program_id()};
+    result = extend_lookup_table(client, &table, payer, payer, addresses, 2);
+    printf("Extend: %s\n", result->success ? result->signature : result->error);
+    free_transaction_result(result);
+
+    SolLookupTable *lookup_table = get_lookup_table(client, &table);
+    if (lookup_table != NULL)
+    {
+        printf("Lookup table holds %zu addresses\n", lookup_table->address_count);
+        free_lookup_table(lookup_table);
+    }
+
+    // Addresses become usable one slot after the extend; transfer through the table
+    SolTransactionBuilder *builder = sol_transaction_builder_new();
+    SolPublicKey system_program = get_system_program_id();
+    SolAccountMeta transfer_accounts[] = {
+        {payer->pubkey, true, true},
+        {recipient->pubkey, false, true},
+    };
+    uint8_t transfer_data[12] = {2, 0, 0, 0};
+    uint64_t lamports = 1000;
+    memcpy(transfer_data + 4, &lamports, sizeof(lamports));
+    sol_transaction_builder_add_instruction(builder, &system_program, transfer_accounts, 2, transfer_data, sizeof(transfer_data));
+    sol_transaction_builder_add_signer(builder, payer);
+    sol_transaction_builder_add_lookup_table(builder, &table);
+    result = sol_transaction_builder_send(builder, client);
+    printf("Transfer: %s\n", result->success ? result->signature : result->error);
+    free_transaction_result(result);
+    free_sol_transaction_builder(builder);
+
+    result = deactivate_lookup_table(client, &table, payer, NULL);
+    printf("Deactivate: %s\n", result->success ? result->signature : result->error);
+    free_transaction_result(result);
+
+    free_keypair(recipient);
+    free_keypair(payer);
+    printf("=== End Test: Address Lookup Table ===\n");
+}
+
+void test_durable_nonce()
+{
+    printf("=== Test: Durable Nonce ===\n");
+    SolClient *client = new_sol_client(devnet_url);
+    SolKeyPair *payer = load_wallet_from_file(file_path_payer);
+    SolKeyPair *recipient = load_wallet_from_file(file_path_recipient);
+    SolKeyPair *nonce_keypair = new_keypair();
+    if (client == NULL || payer == NULL || recipient == NULL)
+    {
+        printf("Failed to load wallets or client.\n");
+        return;
+    }
+
+    SolTransactionResult *result = create_nonce_account(client, payer, nonce_keypair, &payer->pubkey, 0);
+    printf("Create: %s\n", result->success ? result->signature : result->error);
+    free_transaction_result(result);
+
+    SolNonceAccount *nonce = get_nonce_account(client, &nonce_keypair->pubkey);
+    if (nonce != NULL)
+    {
+        printf("Stored nonce: %s (%llu lamports)\n", nonce->nonce, (unsigned long long)nonce->lamports);
+        free_nonce_account(nonce);
+    }
+
+    // Transfer signed against the stored nonce instead of a recent blockhash
+    SolTransactionBuilder *builder = sol_transaction_builder_new();
+    SolPublicKey system_program = get_system_program_id();
+    SolAccountMeta transfer_accounts[] = {
+        {payer->pubkey, true, true},
+        {recipient->pubkey, false, true},
+    };
+    uint8_t transfer_data[12] = {2, 0, 0, 0};
+    uint64_t lamports = 1000;
+    memcpy(transfer_data + 4, &lamports, sizeof(lamports));
+    sol_transaction_builder_add_instruction(builder, &system_program, transfer_accounts, 2, transfer_data, sizeof(transfer_data));
+    sol_transaction_builder_add_signer(builder, payer);
+    sol_transaction_builder_set_nonce(builder, &nonce_keypair->pubkey, &payer->pubkey);
+    result = sol_transaction_builder_send(builder, client);
+    printf("Transfer: %s\n", result->success ? result->signature : result->error);
+    free_transaction_result(result);
+    free_sol_transaction_builder(builder);
+
+    // Withdraw everything to close the nonce account
+    nonce = get_nonce_account(client, &nonce_keypair->pubkey);
+    if (nonce != NULL)
+    {
+        result = withdraw_nonce_account(client, &nonce_keypair->pubkey, payer, &payer->pubkey, nonce->lamports, NULL);
+        printf("Withdraw: %s\n", result->success ? result->signature : result->error);
+        free_transaction_result(result);
+        free_nonce_account(nonce);
+    }
+
+    free_keypair(nonce_keypair);
+    free_keypair(recipient);
+    free_keypair(payer);
+    printf("=== End Test: Durable Nonce ===\n");
+}
+
+void test_partial_signing()
+{
+    printf("=== Test: Partial Signing ===\n");
+    SolClient *client = new_sol_client(devnet_url);
+    SolKeyPair *server = load_wallet_from_file(file_path_payer);
+    SolKeyPair *player = load_wallet_from_file(file_path_recipient);
+    if (client == NULL || server == NULL || player == NULL)
+    {
+        printf("Failed to load wallets or client.\n");
+        return;
+    }
+
+    // The player sends lamports, the server pays the fees
+    SolTransactionBuilder *builder = sol_transaction_builder_new();
+    SolPublicKey system_program = get_system_program_id();
+    SolAccountMeta transfer_accounts[] = {
+        {player->pubkey, true, true},
+        {server->pubkey, false, true},
+    };
+    uint8_t transfer_data[12] = {2, 0, 0, 0};
+    uint64_t lamports = 1000;
+    memcpy(transfer_data + 4, &lamports, sizeof(lamports));
+    sol_transaction_builder_add_instruction(builder, &system_program, transfer_accounts, 2, transfer_data, sizeof(transfer_data));
+    sol_transaction_builder_set_fee_payer(builder, &server->pubkey);
+    SolTransaction *transaction = sol_transaction_builder_build_unsigned(builder, client);
+    free_sol_transaction_builder(builder);
+    if (transaction == NULL)
+    {
+        printf("Failed to build transaction.\n");
+        return;
+    }
+
+    // Player signs and hands the transaction over as base64
+    sol_transaction_partial_sign(transaction, player);
+    char *encoded = sol_transaction_to_base64(transaction);
+    free_sol_transaction(transaction);
+    printf("Player signed: %s\n", encoded);
+
+    // Server checks the signatures, co-signs and submits
+    SolTransaction *received = sol_transaction_from_base64(encoded);
+    free(encoded);
+    SolPublicKeyList *missing = sol_transaction_missing_signers(received);
+    printf("Missing signers: %zu, signatures valid: %d\n", public_key_list_get_len(missing),
+           sol_transaction_verify_signatures(received));
+    free_public_key_list(missing);
+
+    sol_transaction_partial_sign(received, server);
+    SolTransactionResult *result = sol_transaction_send(received, client);
+    printf("Send: %s\n", result->success ? result->signature : result->error);
+    free_transaction_result(result);
+    free_sol_transaction(received);
+
+    free_keypair(player);
+    free_keypair(server);
+    printf("=== End Test: Partial Signing ===\n");
+}
+
+void test_fee_sponsorship()
+{
+    printf("=== Test: Fee Sponsorship ===\n");
+    SolClient *client = new_sol_client(devnet_url);
+    SolKeyPair *sponsor = load_wallet_from_file(file_path_payer);
+    SolKeyPair *player = load_wallet_from_file(file_path_recipient);
+    if (client == NULL || sponsor == NULL || player == NULL)
+    {
+        printf("Failed to load wallets or client.\n");
+        return;
+    }
+
+    // The player sends SOL while the sponsor pays the fee
+    bool sent = transfer_sol_with_fee_payer(client, player, &sponsor->pubkey, 1000, sponsor);
+    printf("Sponsored transfer: %s\n", sent ? "sent" : "failed");
+
+    // Relayer mode: the player signs a memo, the sponsor checks and co-signs it
+    SolPublicKey *memo_program = get_pubkey_from_address("MemoSq4gqABAXKb96qnH1TysNcWxMyWCqXgDLGmfcHr");
+    SolTransactionBuilder *builder = sol_transaction_builder_new();
+    SolAccountMeta memo_accounts[] = {{player->pubkey, true, false}};
+    const char *memo = "Fees paid by the sponsor";
+    sol_transaction_builder_add_instruction(builder, memo_program, memo_accounts, 1, (const uint8_t *)memo, strlen(memo));
+    sol_transaction_builder_set_fee_payer(builder, &sponsor->pubkey);
+    SolTransaction *transaction = sol_transaction_builder_build_unsigned(builder, client);
+    free_sol_transaction_builder(builder);
+    if (transaction != NULL)
+    {
+        sol_transaction_partial_sign(transaction, player);
+        char *encoded = sol_transaction_to_base64(transaction);
+        free_sol_transaction(transaction);
+
+        SolRelayerPolicy policy = {memo_program, 1, 10000, 0};
+        SolTransactionResult *result = relayer_sign_and_send(client, sponsor, encoded, &policy);
+        printf("Relayed: %s\n", result->success ? result->signature : result->error);
+        free_transaction_result(result);
+        free(encoded);
+    }
+
+    free(memo_program);
+    free_keypair(player);
+    free_keypair(sponsor);
+    printf("=== End Test: Fee Sponsorship ===\n");
+}
+
+void test_transaction_decoding()
+{
+    printf("=== Test: Transaction Decoding ===\n");
+    SolKeyPair *payer = load_wallet_from_file(file_path_payer);
+    SolKeyPair *recipient = load_wallet_from_file(file_path_recipient);
+    if (payer == NULL || recipient == NULL)
+    {
+        printf("Failed to load wallets.\n");
+        return;
+    }
+
+    // Build a memo transaction offline, then decode its wire format
+    SolPublicKey *memo_program = get_pubkey_from_address("MemoSq4gqABAXKb96qnH1TysNcWxMyWCqXgDLGmfcHr");
+    SolTransactionBuilder *builder = sol_transaction_builder_new();
+    SolAccountMeta memo_accounts[] = {{payer->pubkey, true, false}};
+    const char *memo = "Decode me";
+    sol_transaction_builder_add_instruction(builder, memo_program, memo_accounts, 1, (const uint8_t *)memo, strlen(memo));
+    sol_transaction_builder_set_fee_payer(builder, &payer->pubkey);
+    SolTransaction *transaction = sol_transaction_builder_build_unsigned_with_blockhash(builder, "11111111111111111111111111111111");
+    free_sol_transaction_builder(builder);
+    if (transaction != NULL)
+    {
+        uintptr_t len = sol_transaction_serialize(transaction, NULL, 0);
+        uint8_t *bytes = malloc(len);
+        sol_transaction_serialize(transaction, bytes, len);
+
+        SolDecodedTransaction *decoded = decode_transaction_c(NULL, bytes, len);
+        if (decoded != NULL)
+        {
+            printf("%s transaction, blockhash %s\n", decoded->is_versioned ? "v0" : "Legacy", decoded->recent_blockhash);
+            for (uintptr_t i = 0; i < decoded->instruction_count; i++)
+            {
+                SolDecodedInstruction *instruction = &decoded->instructions[i];
+                printf("  %s: %s\n",
+                       instruction->program_name ? instruction->program_name : "Unknown program",
+                       instruction->description ? instruction->description : "Unknown instruction");
+            }
+            free_decoded_transaction(decoded);
+        }
+        free(bytes);
+        free_sol_transaction(transaction);
+    }
+
+    // With an IDL registered on the client, Anchor instructions are decoded by name.
+    // No lookup tables are involved, so the client makes no requests.
+    SolClient *client = new_sol_client(devnet_url);
+    SolIdl *idl = load_idl_from_file("anchor_counter.json");
+    if (client != NULL && idl != NULL && sol_client_register_idl(client, idl, NULL))
+    {
+        SolPublicKey program_id;
+        idl_get_program_id(idl, &program_id);
+        uint8_t data[8];
+        uintptr_t data_len = idl_encode_instruction_data(idl, "increment", NULL, data, sizeof(data));
+        SolAccountMeta accounts[] = {
+            {recipient->pubkey, false, true},
+            {payer->pubkey, true, false}};
+        builder = sol_transaction_builder_new();
+        sol_transaction_builder_add_instruction(builder, &program_id, accounts, 2, data, data_len);
+        sol_transaction_builder_set_fee_payer(builder, &payer->pubkey);
+        transaction = sol_transaction_builder_build_unsigned_with_blockhash(builder, "11111111111111111111111111111111");
+        free_sol_transaction_builder(builder);
+
+        SolDecodedTransaction *decoded = transaction ? decode_sol_transaction(client, transaction) : NULL;
+        bool ok = decoded != NULL && decoded->instruction_count == 1 &&
+                  decoded->instructions[0].instruction_name != NULL &&
+                  strcmp(decoded->instructions[0].instruction_name, "increment") == 0;
+        printf("Registered IDL: %s\n", ok ? "ok" : "FAILED");
+        if (decoded != NULL)
+        {
+            printf("  %s: %s\n",
+                   decoded->instructions[0].program_name ? decoded->instructions[0].program_name : "Unknown program",
+                   decoded->instructions[0].description ? decoded->instructions[0].description : "Unknown instruction");
+            free_decoded_transaction(decoded);
+        }
+        free_sol_transaction(transaction);
+    }
+    if (idl != NULL)
+        free_idl(idl);
+    if (client != NULL)
+        free_client(client);
+
+    free(memo_program);
+    free_keypair(recipient);
+    free_keypair(payer);
+    printf("=== End Test: Transaction Decoding ===\n");
+}
+
+void test_idl_loading()
+{
+    printf("=== Test: IDL Loading ===\n");
+    SolIdl *idl = load_idl_from_file("anchor_counter.json");
+    if (idl == NULL)
+    {
+        printf("Failed to load IDL.\n");
+        return;
+    }
+
+    char *name = idl_get_name(idl);
+    char *version = idl_get_version(idl);
+    printf("Program: %s %s\n", name, version);
+    free(name);
+    free(version);
+
+    SolPublicKey program_id;
+    if (idl_get_program_id(idl, &program_id))
+    {
+        char *address = get_address_from_pubkey(&program_id);
+        printf("Address: %s\n", address);
+        free(address);
+    }
+
+    for (uintptr_t i = 0; i < idl_get_instruction_count(idl); i++)
+    {
+        char *instruction = idl_get_instruction_name(idl, i);
+        uint8_t discriminator[8];
+        uintptr_t len = idl_get_instruction_discriminator(idl, instruction, discriminator, sizeof(discriminator));
+        printf("Instruction %s:", instruction);
+        for (uintptr_t j = 0; j < len; j++)
+            printf(" %d", discriminator[j]);
+        printf("\n");
+        free(instruction);
+    }
+
+    free_idl(idl);
+
+    // A legacy untyped numeric seed is one byte, so 300 cannot be a seed
+    const char *legacy_template =
+        "{\"version\": \"0.1.0\", \"name\": \"seeds\", \"instructions\": [{\"name\": \"init\", \"args\": [],"
+        " \"accounts\": [{\"name\": \"vault\", \"isMut\": true, \"isSigner\": false,"
+        " \"pda\": {\"seeds\": [{\"kind\": \"const\", \"value\": %d}]}}]}]}";
+    char legacy_json[512];
+    snprintf(legacy_json, sizeof(legacy_json), legacy_template, 255);
+    SolIdl *fits = load_idl_from_json(legacy_json);
+    snprintf(legacy_json, sizeof(legacy_json), legacy_template, 300);
+    SolIdl *too_large = load_idl_from_json(legacy_json);
+    printf("Legacy seed range: %s\n", fits != NULL && too_large == NULL ? "ok" : "FAILED");
+    free_idl(fits);
+    free_idl(too_large);
+    printf("=== End Test: IDL Loading ===\n");
+}
+
+void test_idl_instruction()
+{
+    printf("=== Test: IDL Instruction ===\n");
+    SolClient *client = new_sol_client(devnet_url);
+    SolKeyPair *payer = load_wallet_from_file(file_path_payer);
+    SolIdl *idl = load_idl_from_file("anchor_counter.json");
+    if (client == NULL || payer == NULL || idl == NULL)
+    {
+        printf("Failed to load client, wallet or IDL.\n");
+        return;
+    }
+
+    // Create a counter, then increment it by name
+    SolKeyPair *counter = new_keypair();
+    SolPublicKey system_program = get_system_program_id();
+    SolAccountMeta initialize_accounts[] = {
+        {counter->pubkey, true, true},
+        {payer->pubkey, true, true},
+        {system_program, false, false}};
+    SolKeyPair *initialize_signers[] = {payer, counter};
+    char *signature = send_idl_instruction(client, idl, NULL, "initialize", NULL, initialize_accounts, 3, initialize_signers, 2);
+    printf("Initialize: %s\n", signature ? signature : "failed");
+    free(signature);
+
+    SolAccountMeta increment_accounts[] = {
+        {counter->pubkey, false, true},
+        {payer->pubkey, true, false}};
+    SolKeyPair *increment_signers[] = {payer};
+    signature = send_idl_instruction(client, idl, NULL, "increment", "{}", increment_accounts, 2, increment_signers, 1);
+    printf("Increment: %s\n", signature ? signature : "failed");
+
+    // Read the counter back through the IDL layout
+    char *counter_json = fetch_account_decoded(client, idl, "Counter", &counter->pubkey);
+    printf("Counter: %s\n", counter_json ? counter_json : "failed");
+    free(counter_json);
+
+    // anchor_counter emits no events, so this prints an empty array
+    char *events = signature ? get_transaction_events(client, idl, NULL, signature) : NULL;
+    printf("Events: %s\n", events ? events : "failed");
+    free(events);
+    free(signature);
+
+    free_keypair(counter);
+    free_idl(idl);
+    free_keypair(payer);
+    printf("=== End Test: IDL Instruction ===\n");
+}
+
+void test_program_error()
+{
+    printf("=== Test: Program Error ===\n");
+    SolClient *client = new_sol_client(devnet_url);
+    SolKeyPair *payer = load_wallet_from_file(file_path_payer);
+    SolIdl *idl = load_idl_from_file("anchor_counter.json");
+    if (client == NULL || payer == NULL || idl == NULL)
+    {
+        printf("Failed to load client, wallet or IDL.\n");
+        return;
+    }
+    sol_client_register_idl(client, idl, NULL);
+
+    // Incrementing a counter that was never created fails with AccountNotInitialized
+    SolKeyPair *counter = new_keypair();
+    SolPublicKey program_id;
+    idl_get_program_id(idl, &program_id);
+    uint8_t data[8];
+    uintptr_t data_len = idl_encode_instruction_data(idl, "increment", NULL, data, sizeof(data));
+    SolAccountMeta accounts[] = {
+        {counter->pubkey, false, true},
+        {payer->pubkey, true, false}};
+
+    SolTransactionBuilder *builder = sol_transaction_builder_new();
+    sol_transaction_builder_add_instruction(builder, &program_id, accounts, 2, data, data_len);
+    sol_transaction_builder_add_signer(builder, payer);
+    SolTransactionResult *result = sol_transaction_builder_simulate(builder, client);
+    if (result->has_program_error)
+    {
+        printf("Instruction %u failed with %u %s: %s\n",
+               result->instruction_index,
+               result->error_code,
+               result->error_name ? result->error_name : "(unknown)",
+               result->error_message ? result->error_message : "");
+    }
+    else
+    {
+        printf("Unexpected result: %s\n", result->error ? result->error : "success");
+    }
+
+    free_transaction_result(result);
+    free_sol_transaction_builder(builder);
+    free_keypair(counter);
+    free_idl(idl);
+    free_keypair(payer);
+    free_client(client);
+}
+
+void test_idl_resolved()
+{
+    printf("=== Test: IDL Account Resolution ===\n");
+    SolClient *client = new_sol_client(devnet_url);
+    SolKeyPair *payer = load_wallet_from_file(file_path_payer);
+    SolIdl *idl = load_idl_from_file("anchor_counter.json");
+    if (client == NULL || payer == NULL || idl == NULL)
+    {
+        printf("Failed to load client, wallet or IDL.\n");
+        return;
+    }
+
+    // Only the accounts the IDL cannot supply are named; the system program
+    // comes from its fixed address
+    SolKeyPair *counter = new_keypair();
+    char *counter_address = get_wallet_address(counter);
+    char *payer_address = get_wallet_address(payer);
+    char accounts_json[256];
+    snprintf(accounts_json, sizeof(accounts_json), "{\"counter\": \"%s\", \"user\": \"%s\"}", counter_address, payer_address);
+
+    SolAccountMeta accounts[3];
+    uintptr_t account_count = idl_resolve_accounts(client, idl, NULL, "initialize", NULL, accounts_json, accounts, 3);
+    printf("Resolved %lu accounts for initialize\n", (unsigned long)account_count);
+
+    SolKeyPair *signers[] = {payer, counter};
+    char *signature = send_idl_instruction_resolved(client, idl, NULL, "initialize", NULL, accounts_json, signers, 2);
+    printf("Initialize: %s\n", signature ? signature : "failed");
+    free(signature);
+
+    signature = send_idl_instruction_resolved(client, idl, NULL, "increment", NULL, accounts_json, signers, 1);
+    printf("Increment: %s\n", signature ? signature : "failed");
+    free(signature);
+
+    free(counter_address);
+    free(payer_address);
+    free_idl(idl);
+}
+
+// Compare a JSON result from the SDK with the expected text and free it
+bool check_json(const char *label, char *actual, const char *expected)
+{
+    bool ok = actual != NULL && strcmp(actual, expected) == 0;
+    printf("%s: %s\n", label, ok ? "ok" : "FAILED");
+    if (!ok)
+        printf("  expected %s\n  got      %s\n", expected, actual ? actual : "NULL");
+    free(actual);
+    return ok;
+}
+
+void test_idl_codec()
+{
+    printf("=== Test: IDL Codec ===\n");
+    SolIdl *counter_idl = load_idl_from_file("anchor_counter.json");
+    if (counter_idl == NULL)
+    {
+        printf("Failed to load IDL.\n");
+        return;
+    }
+
+    // anchor_counter instructions take no arguments, so the data is the discriminator
+    for (uintptr_t i = 0; i < idl_get_instruction_count(counter_idl); i++)
+    {
+        char *instruction = idl_get_instruction_name(counter_idl, i);
+        uint8_t discriminator[8];
+        uint8_t data[8];
+        idl_get_instruction_discriminator(counter_idl, instruction, discriminator, sizeof(discriminator));
+        uintptr_t len = idl_encode_instruction_data(counter_idl, instruction, "{}", data, sizeof(data));
+        bool ok = len == sizeof(data) && memcmp(data, discriminator, sizeof(data)) == 0;
+        printf("Encode %s: %s\n", instruction, ok ? "ok" : "FAILED");
+        free(instruction);
+    }
+
+    // Counter account: discriminator followed by count as a little-endian u64
+    uint8_t counter_data[16] = {255, 176, 4, 245, 188, 253, 124, 25, 5, 1};
+    check_json("Decode Counter", idl_decode_account_data(counter_idl, "Counter", counter_data, sizeof(counter_data)),
+               "{\"count\":261}");
+    counter_data[0] = 0;
+    char *mismatch = idl_decode_account_data(counter_idl, "Counter", counter_data, sizeof(counter_data));
+    printf("Wrong discriminator rejected: %s\n", mismatch == NULL ? "ok" : "FAILED");
+    free(mismatch);
+    free_idl(counter_idl);
+
+    // An instruction and an account with the same fields, so encoded arguments
+    // decode back as account data once the discriminator is swapped
+    SolIdl *idl = load_idl_from_json(
+        "{\"address\": \"DsfPR2teuRS9ABmqGqq5NobD8Y9A9KvzMVNVzsjSP8Dy\","
+        " \"metadata\": {\"name\": \"codec\", \"version\": \"0.1.0\", \"spec\": \"0.1.0\"},"
+        " \"instructions\": [{\"name\": \"set\", \"discriminator\": [1, 1, 1, 1, 1, 1, 1, 1], \"accounts\": [],"
+        "   \"args\": [{\"name\": \"count\", \"type\": \"u64\"}, {\"name\": \"max\", \"type\": \"i256\"}, {\"name\": \"min\", \"type\": \"i256\"}]}],"
+        " \"accounts\": [{\"name\": \"Values\", \"discriminator\": [2, 2, 2, 2, 2, 2, 2, 2]}],"
+        " \"types\": [{\"name\": \"Values\", \"type\": {\"kind\": \"struct\", \"fields\": ["
+        "   {\"name\": \"count\", \"type\": \"u64\"}, {\"name\": \"max\", \"type\": \"i256\"}, {\"name\": \"min\", \"type\": \"i256\"}]}}]}");
+    if (idl == NULL)
+    {
+        printf("Failed to load inline IDL.\n");
+        return;
+    }
+
+    // i256 boundaries: 2^255 - 1 and -2^255
+    const char *values =
+        "{\"count\":261,"
+        "\"max\":\"57896044618658097711785492504343953926634992332820282019728792003956564819967\","
+        "\"min\":\"-57896044618658097711785492504343953926634992332820282019728792003956564819968\"}";
+    uint8_t data[8 + 8 + 32 + 32];
+    uintptr_t len = idl_encode_instruction_data(idl, "set", values, data, sizeof(data));
+    printf("Encode i256 boundaries: %s\n", len == sizeof(data) ? "ok" : "FAILED");
+    if (len == sizeof(data))
+    {
+        memset(data, 2, 8);
+        check_json("Round trip", idl_decode_account_data(idl, "Values", data, len), values);
+    }
+
+    // One past either end does not fit
+    const char *too_large =
+        "{\"count\":0,"
+        "\"max\":\"57896044618658097711785492504343953926634992332820282019728792003956564819968\","
+        "\"min\":\"0\"}";
+    const char *too_small =
+        "{\"count\":0,\"max\":\"0\","
+        "\"min\":\"-57896044618658097711785492504343953926634992332820282019728792003956564819969\"}";
+    printf("2^255 rejected: %s\n", idl_encode_instruction_data(idl, "set", too_large, NULL, 0) == 0 ? "ok" : "FAILED");
+    printf("-2^255 - 1 rejected: %s\n", idl_encode_instruction_data(idl, "set", too_small, NULL, 0) == 0 ? "ok" : "FAILED");
+
+    // A key that matches no argument is an error rather than ignored
+    const char *misspelled = "{\"count\":0,\"max\":\"0\",\"min\":\"0\",\"maximum\":\"1\"}";
+    printf("Unknown key rejected: %s\n", idl_encode_instruction_data(idl, "set", misspelled, NULL, 0) == 0 ? "ok" : "FAILED");
+
+    // A short buffer is left untouched and the needed length comes back
+    printf("Needed length: %s\n", idl_encode_instruction_data(idl, "set", values, data, 8) == sizeof(data) ? "ok" : "FAILED");
+
+    free_idl(idl);
+
+    // Elements of a struct without fields take no bytes
+    SolIdl *unit_idl = load_idl_from_json(
+        "{\"address\": \"DsfPR2teuRS9ABmqGqq5NobD8Y9A9KvzMVNVzsjSP8Dy\","
+        " \"metadata\": {\"name\": \"units\", \"version\": \"0.1.0\", \"spec\": \"0.1.0\"},"
+        " \"instructions\": [],"
+        " \"accounts\": [{\"name\": \"Marks\", \"discriminator\": [3, 3, 3, 3, 3, 3, 3, 3]}],"
+        " \"types\": [{\"name\": \"Mark\", \"type\": {\"kind\": \"struct\", \"fields\": []}},"
+        "   {\"name\": \"Marks\", \"type\": {\"kind\": \"struct\", \"fields\": ["
+        "     {\"name\": \"marks\", \"type\": {\"vec\": {\"defined\": {\"name\": \"Mark\"}}}}]}}]}");
+    if (unit_idl != NULL)
+    {
+        uint8_t marks_data[12] = {3, 3, 3, 3, 3, 3, 3, 3, 3};
+        check_json("Zero-sized elements", idl_decode_account_data(unit_idl, "Marks", marks_data, sizeof(marks_data)),
+                   "{\"marks\":[{},{},{}]}");
+        free_idl(unit_idl);
+    }
+    printf("=== End Test: IDL Codec ===\n");
+}
+
+// Write a replay recording for new_sol_client_replay
+bool write_fixture(const char *path, const char *contents)
+{
+    FILE *file = fopen(path, "w");
+    if (file == NULL)
+        return false;
+    fputs(contents, file);
+    fclose(file);
+    return true;
+}
+
+void test_anchor_events()
+{
+    printf("=== Test: Anchor Events ===\n");
+    // One event type; the discriminator is arbitrary since the IDL spells it out
+    SolIdl *idl = load_idl_from_json(
+        "{\"address\": \"DsfPR2teuRS9ABmqGqq5NobD8Y9A9KvzMVNVzsjSP8Dy\","
+        " \"metadata\": {\"name\": \"events\", \"version\": \"0.1.0\", \"spec\": \"0.1.0\"},"
+        " \"instructions\": [],"
+        " \"events\": [{\"name\": \"Transferred\", \"discriminator\": [1, 2, 3, 4, 5, 6, 7, 8]}],"
+        " \"types\": [{\"name\": \"Transferred\", \"type\": {\"kind\": \"struct\", \"fields\": ["
+        "   {\"name\": \"amount\", \"type\": \"u64\"}, {\"name\": \"memo\", \"type\": \"string\"}]}}]}");
+    if (idl == NULL)
+    {
+        printf("Failed to load inline IDL.\n");
+        return;
+    }
+
+    // Events of the IDL program, around a nested call into the Memo program and
+    // followed by another top-level program that logs the same event layout
+    const char *logs[] = {
+        "Program DsfPR2teuRS9ABmqGqq5NobD8Y9A9KvzMVNVzsjSP8Dy invoke [1]",
+        "Program log: Instruction: Transfer",
+        "Program data: AQIDBAUGBwgBAAAAAAAAAAUAAABmaXJzdA==",
+        "Program MemoSq4gqABAXKb96qnH1TysNcWxMyWCqXgDLGmfcHr invoke [2]",
+        "Program data: AQIDBAUGBwhjAAAAAAAAAAUAAABvdGhlcg==",
+        "Program MemoSq4gqABAXKb96qnH1TysNcWxMyWCqXgDLGmfcHr consumed 1000 of 190000 compute units",
+        "Program MemoSq4gqABAXKb96qnH1TysNcWxMyWCqXgDLGmfcHr success",
+        "Program data: AQIDBAUGBwgCAAAAAAAAAAYAAABzZWNvbmQ=",
+        "Program DsfPR2teuRS9ABmqGqq5NobD8Y9A9KvzMVNVzsjSP8Dy consumed 10000 of 200000 compute units",
+        "Program DsfPR2teuRS9ABmqGqq5NobD8Y9A9KvzMVNVzsjSP8Dy success",
+        "Program 11111111111111111111111111111111 invoke [1]",
+        "Program data: AQIDBAUGBwgDAAAAAAAAAAUAAAB0aGlyZA==",
+        "Program 11111111111111111111111111111111 success"};
+    uintptr_t log_count = sizeof(logs) / sizeof(logs[0]);
+    check_json("Events of the IDL program", parse_events_from_logs(idl, NULL, logs, log_count),
+               "[{\"data\":{\"amount\":1,\"memo\":\"first\"},\"name\":\"Transferred\"},"
+               "{\"data\":{\"amount\":2,\"memo\":\"second\"},\"name\":\"Transferred\"}]");
+    check_json("Events of the nested program",
+               parse_events_from_logs(idl, "MemoSq4gqABAXKb96qnH1TysNcWxMyWCqXgDLGmfcHr", logs, log_count),
+               "[{\"data\":{\"amount\":99,\"memo\":\"other\"},\"name\":\"Transferred\"}]");
+
+    // emit_cpi! events come from inner instructions of a fetched transaction.
+    // Account 1 is the program itself; the event sent to account 0 is ignored.
+    SolKeyPair *payer = new_keypair();
+    SolPublicKey program_id;
+    idl_get_program_id(idl, &program_id);
+    SolAccountMeta accounts[] = {{payer->pubkey, true, true}};
+    uint8_t data[] = {0};
+    SolTransactionBuilder *builder = sol_transaction_builder_new();
+    sol_transaction_builder_add_instruction(builder, &program_id, accounts, 1, data, sizeof(data));
+    sol_transaction_builder_set_fee_payer(builder, &payer->pubkey);
+    SolTransaction *transaction = sol_transaction_builder_build_unsigned_with_blockhash(builder, "11111111111111111111111111111111");
+    free_sol_transaction_builder(builder);
+    char *encoded = transaction ? sol_transaction_to_base64(transaction) : NULL;
+
+    const char *fixture_path = "events_replay.jsonl";
+    char fixture[2048];
+    snprintf(fixture, sizeof(fixture),
+             "{\"method\": \"getTransaction\", \"params\": [], \"result\": {\"slot\": 1, \"blockTime\": null,"
+             " \"transaction\": [\"%s\", \"base64\"],"
+             " \"meta\": {\"err\": null, \"status\": {\"Ok\": null}, \"fee\": 5000, \"preBalances\": [], \"postBalances\": [],"
+             " \"logMessages\": [], \"loadedAddresses\": {\"writable\": [], \"readonly\": []},"
+             " \"innerInstructions\": [{\"index\": 0, \"instructions\": ["
+             "{\"programIdIndex\": 1, \"accounts\": [], \"data\": \"4UtL1GFrHum68n6sKDo9Yj4tLNTa2oQovVUNGxP2pWL\", \"stackHeight\": 2},"
+             "{\"programIdIndex\": 0, \"accounts\": [], \"data\": \"2Ap35ipDGK3kKzBACrX3CLWQLULQ8TCfkqNVhPrkQtcHXX\", \"stackHeight\": 2}]}]}}}\n",
+             encoded ? encoded : "");
+    SolClient *replay = encoded && write_fixture(fixture_path, fixture) ? new_sol_client_replay(fixture_path) : NULL;
+    if (replay != NULL)
+    {
+        check_json("emit_cpi events",
+                   get_transaction_events(replay, idl, NULL, "1111111111111111111111111111111111111111111111111111111111111111"),
+                   "[{\"data\":{\"amount\":7,\"memo\":\"cpi\"},\"name\":\"Transferred\"}]");
+        free_client(replay);
+    }
+    else
+    {
+        printf("emit_cpi events: FAILED\n");
+    }
+    remove(fixture_path);
+
+    free(encoded);
+    free_sol_transaction(transaction);
+    free_keypair(payer);
+    free_idl(idl);
+    printf("=== End Test: Anchor Events ===\n");
+}
+
+void test_cpi_program_error()
+{
+    printf("=== Test: Program Error After CPI ===\n");
+    // The inner program's IDL, registered under its address
+    SolIdl *idl = load_idl_from_json(
+        "{\"address\": \"DsfPR2teuRS9ABmqGqq5NobD8Y9A9KvzMVNVzsjSP8Dy\","
+        " \"metadata\": {\"name\": \"inner\", \"version\": \"0.1.0\", \"spec\": \"0.1.0\"},"
+        " \"instructions\": [],"
+        " \"errors\": [{\"code\": 6000, \"name\": \"Overflow\", \"msg\": \"Count overflowed\"}]}");
+    if (idl == NULL)
+    {
+        printf("Failed to load inline IDL.\n");
+        return;
+    }
+
+    // The outer Memo instruction calls the inner program, which fails; both log the failure
+    const char *fixture_path = "cpi_error_replay.jsonl";
+    const char *fixture =
+        "{\"method\": \"getLatestBlockhash\", \"params\": [], \"result\": {\"context\": {\"slot\": 1},"
+        " \"value\": {\"blockhash\": \"EkSnNWid2cvwEVnVx9aBqawnmiCNiDgp3gUdkDPTKN1N\", \"lastValidBlockHeight\": 100}}}\n"
+        "{\"method\": \"simulateTransaction\", \"params\": [], \"result\": {\"context\": {\"slot\": 1}, \"value\": {"
+        " \"err\": {\"InstructionError\": [0, {\"Custom\": 6000}]}, \"accounts\": null, \"unitsConsumed\": 2000, \"returnData\": null,"
+        " \"logs\": ["
+        "\"Program MemoSq4gqABAXKb96qnH1TysNcWxMyWCqXgDLGmfcHr invoke [1]\","
+        "\"Program DsfPR2teuRS9ABmqGqq5NobD8Y9A9KvzMVNVzsjSP8Dy invoke [2]\","
+        "\"Program log: AnchorError occurred. Error Code: Overflow. Error Number: 6000. Error Message: Count overflowed.\","
+        "\"Program DsfPR2teuRS9ABmqGqq5NobD8Y9A9KvzMVNVzsjSP8Dy failed: custom program error: 0x1770\","
+        "\"Program MemoSq4gqABAXKb96qnH1TysNcWxMyWCqXgDLGmfcHr failed: custom program error: 0x1770\"]}}}\n";
+    SolClient *client = write_fixture(fixture_path, fixture) ? new_sol_client_replay(fixture_path) : NULL;
+    if (client == NULL)
+    {
+        printf("Failed to create replay client.\n");
+        free_idl(idl);
+        return;
+    }
+    sol_client_register_idl(client, idl, NULL);
+
+    SolKeyPair *payer = new_keypair();
+    SolPublicKey *memo_program = get_pubkey_from_address("MemoSq4gqABAXKb96qnH1TysNcWxMyWCqXgDLGmfcHr");
+    SolAccountMeta accounts[] = {{payer->pubkey, true, false}};
+    const char *memo = "CPI";
+    SolTransactionBuilder *builder = sol_transaction_builder_new();
+    sol_transaction_builder_add_instruction(builder, memo_program, accounts, 1, (const uint8_t *)memo, strlen(memo));
+    sol_transaction_builder_add_signer(builder, payer);
+    SolTransactionResult *result = sol_transaction_builder_simulate(builder, client);
+
+    SolPublicKey inner_program;
+    idl_get_program_id(idl, &inner_program);
+    bool ok = result != NULL && result->has_program_error && result->error_code == 6000 &&
+              memcmp(&result->program_id, &inner_program, sizeof(inner_program)) == 0 &&
+              result->error_name != NULL && strcmp(result->error_name, "Overflow") == 0;
+    printf("Innermost program reported: %s\n", ok ? "ok" : "FAILED");
+
+    free_transaction_result(result);
+    free_sol_transaction_builder(builder);
+    free(memo_program);
+    free_keypair(payer);
+    free_client(client);
+    remove(fixture_path);
+    free_idl(idl);
+    printf("=== End Test: Program Error After CPI ===\n");
+}
+
+void test_send_results()
+{
+    printf("=== Test: Send Results ===\n");
+    SolIdl *idl = load_idl_from_file("anchor_counter.json");
+    if (idl == NULL)
+    {
+        printf("Failed to load IDL.\n");
+        return;
+    }
+
+    // Each send fails preflight because the counter was never initialized
+    const char *exchange =
+        "{\"method\": \"getLatestBlockhash\", \"params\": [], \"result\": {\"context\": {\"slot\": 1},"
+        " \"value\": {\"blockhash\": \"EkSnNWid2cvwEVnVx9aBqawnmiCNiDgp3gUdkDPTKN1N\", \"lastValidBlockHeight\": 100}}}\n"
+        "{\"method\": \"sendTransaction\", \"params\": [], \"error\": {\"kind\": \"rpcResponse\", \"code\": -32002,"
+        " \"message\": \"Transaction simulation failed: Error processing Instruction 0: custom program error: 0xbc4\","
+        " \"data\": {\"sendTransactionPreflightFailure\": {\"err\": {\"InstructionError\": [0, {\"Custom\": 3012}]},"
+        " \"accounts\": null, \"unitsConsumed\": 1500, \"returnData\": null, \"logs\": ["
+        "\"Program DsfPR2teuRS9ABmqGqq5NobD8Y9A9KvzMVNVzsjSP8Dy invoke [1]\","
+        "\"Program DsfPR2teuRS9ABmqGqq5NobD8Y9A9KvzMVNVzsjSP8Dy failed: custom program error: 0xbc4\"]}}}}\n";
+    const char *fixture_path = "send_results_replay.jsonl";
+    char fixture[4096] = "";
+    for (int i = 0; i < 3; i++)
+        strcat(fixture, exchange);
+    SolClient *client = write_fixture(fixture_path, fixture) ? new_sol_client_replay(fixture_path) : NULL;
+    if (client == NULL)
+    {
+        printf("Failed to create replay client.\n");
+        free_idl(idl);
+        return;
+    }
+    sol_client_register_idl(client, idl, NULL);
+
+    SolKeyPair *payer = new_keypair();
+    SolKeyPair *counter = new_keypair();
+    SolAccountMeta accounts[] = {
+        {counter->pubkey, false, true},
+        {payer->pubkey, true, false}};
+    SolKeyPair *signers[] = {payer};
+    char *payer_address = get_wallet_address(payer);
+    char *counter_address = get_wallet_address(counter);
+    char accounts_json[256];
+    snprintf(accounts_json, sizeof(accounts_json), "{\"counter\": \"%s\", \"user\": \"%s\"}", counter_address, payer_address);
+
+    SolTransactionResult *results[] = {
+        send_generic_transaction_with_result(client, "DsfPR2teuRS9ABmqGqq5NobD8Y9A9KvzMVNVzsjSP8Dy", "increment",
+                                             SolDiscriminatorKind_AnchorGlobal, NULL, 0, accounts, 2, signers, 1, NULL, 0, NULL),
+        send_idl_instruction_with_result(client, idl, NULL, "increment", NULL, accounts, 2, signers, 1),
+        send_idl_instruction_resolved_with_result(client, idl, NULL, "increment", NULL, accounts_json, signers, 1)};
+    const char *names[] = {"Generic", "IDL", "IDL resolved"};
+    for (int i = 0; i < 3; i++)
+    {
+        SolTransactionResult *result = results[i];
+        bool ok = result != NULL && !result->success && result->has_program_error &&
+                  result->error_code == 3012 && result->error_name != NULL &&
+                  strcmp(result->error_name, "AccountNotInitialized") == 0;
+        printf("%s send result: %s\n", names[i], ok ? "ok" : "FAILED");
+        free_transaction_result(result);
+    }
+
+    free(counter_address);
+    free(payer_address);
+    free_keypair(counter);
+    free_keypair(payer);
+    free_client(client);
+    remove(fixture_path);
+    free_idl(idl);
+    printf("=== End Test: Send Results ===\n");
+}
+
+void test_relayer_refusal()
+{
+    printf("=== Test: Relayer Refusal ===\n");
+    const char *fixture_path = "relayer_replay.jsonl";
+    SolClient *client = write_fixture(fixture_path,
+                                      "{\"method\": \"getLatestBlockhash\", \"params\": [], \"result\": {\"context\": {\"slot\": 1},"
+                                      " \"value\": {\"blockhash\": \"EkSnNWid2cvwEVnVx9aBqawnmiCNiDgp3gUdkDPTKN1N\", \"lastValidBlockHeight\": 100}}}\n")
+                            ? new_sol_client_replay(fixture_path)
+                            : NULL;
+    if (client == NULL)
+    {
+        printf("Failed to create replay client.\n");
+        return;
+    }
+
+    // The player moves tokens with the sponsor as the token authority
+    SolKeyPair *sponsor = new_keypair();
+    SolKeyPair *player = new_keypair();
+    SolKeyPair *source = new_keypair();
+    SolPublicKey *token_program = get_pubkey_from_address("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
+    SolTransactionBuilder *builder = sol_transaction_builder_new();
+    SolAccountMeta transfer_accounts[] = {
+        {source->pubkey, false, true},
+        {player->pubkey, false, true},
+        {sponsor->pubkey, true, false},
+    };
+    uint8_t transfer_data[9] = {3};
+    uint64_t amount = 1000000;
+    memcpy(transfer_data + 1, &amount, sizeof(amount));
+    sol_transaction_builder_add_instruction(builder, token_program, transfer_accounts, 3, transfer_data, sizeof(transfer_data));
+    SolPublicKey *memo_program = get_pubkey_from_address("MemoSq4gqABAXKb96qnH1TysNcWxMyWCqXgDLGmfcHr");
+    SolAccountMeta memo_accounts[] = {{player->pubkey, true, false}};
+    const char *memo = "Fees paid by the sponsor";
+    sol_transaction_builder_add_instruction(builder, memo_program, memo_accounts, 1, (const uint8_t *)memo, strlen(memo));
+    sol_transaction_builder_set_fee_payer(builder, &sponsor->pubkey);
+    SolTransaction *transaction = sol_transaction_builder_build_unsigned(builder, client);
+    free_sol_transaction_builder(builder);
+
+    char *encoded = NULL;
+    if (transaction != NULL)
+    {
+        sol_transaction_partial_sign(transaction, player);
+        encoded = sol_transaction_to_base64(transaction);
+        free_sol_transaction(transaction);
+    }
+
+    // Even with the token program allowed, the sponsor must not sign for the transfer
+    SolPublicKey allowed[] = {*token_program, *memo_program};
+    SolRelayerPolicy policy = {allowed, 2, 10000, 0};
+    SolTransactionResult *result = encoded ? relayer_sign_and_send(client, sponsor, encoded, &policy) : NULL;
+    bool refused = result != NULL && !result->success &&
+                   strcmp(result->error, "Rejected: Instruction 0 uses the fee payer account") == 0;
+    printf("Relayer refusal: %s\n", refused ? "ok" : "FAILED");
+
+    free_transaction_result(result);
+    free(encoded);
+    free(memo_program);
+    free(token_program);
+    free_keypair(source);
+    free_keypair(player);
+    free_keypair(sponsor);
+    free_client(client);
+    remove(fixture_path);
+    printf("=== End Test: Relayer Refusal ===\n");
+}
+
 void measure_time(const char *test_name, void (*func)())
 {
     struct timeval start, end;
@@ -468,6 +1868,34 @@ void test_mint_token() { test_mint_spl_token(); }
 void test_transfer_spl() { test_transfer_spl_token(); }
 void test_transfer() { test_transfer_sol(); }
 void test_smart_contract() { test_counter(); }
+void test_rpc_replay() { test_record_and_replay(); }
+void test_mnemonic() { test_mnemonic_wallet(); }
+void test_keystore() { test_encrypted_keystore(); }
+void test_signer() { test_signer_handle(); }
+void test_signing() { test_message_signing(); }
+void test_siws() { test_sign_in_with_solana(); }
+void test_pda() { test_program_address(); }
+void test_key_formats() { test_key_import_export(); }
+void test_keyring() { test_keyring_wallets(); }
+void test_callback_signer() { test_external_signer(); }
+void test_vanity() { test_vanity_address(); }
+void test_builder() { test_transaction_builder(); }
+void test_metas() { test_generic_with_metas(); }
+void test_raw() { test_raw_instruction(); }
+void test_alt() { test_lookup_table(); }
+void test_nonce() { test_durable_nonce(); }
+void test_partial() { test_partial_signing(); }
+void test_sponsor() { test_fee_sponsorship(); }
+void test_decode() { test_transaction_decoding(); }
+void test_idl() { test_idl_loading(); }
+void test_idl_call() { test_idl_instruction(); }
+void test_program_error_call() { test_program_error(); }
+void test_idl_resolved_call() { test_idl_resolved(); }
+void test_codec() { test_idl_codec(); }
+void test_events() { test_anchor_events(); }
+void test_cpi_error() { test_cpi_program_error(); }
+void test_results() { test_send_results(); }
+void test_relayer() { test_relayer_refusal(); }
 void test()
 {
     printf("\n| **Function**                      | **Execution Time** |\n");
@@ -479,6 +1907,34 @@ void test()
     measure_time("Mint SPL Token", test_mint_token);
     measure_time("Transfer SPL Token", test_transfer_spl);
     // measure_time("Test Smart Contract", test_smart_contract);
+    // measure_time("Record and Replay RPC", test_rpc_replay);
+    // measure_time("Mnemonic Wallet", test_mnemonic);
+    // measure_time("Encrypted Keystore", test_keystore);
+    // measure_time("Signer Handle", test_signer);
+    // measure_time("Message Signing", test_signing);
+    // measure_time("Sign-In With Solana", test_siws);
+    // measure_time("Program Derived Address", test_pda);
+    // measure_time("Key Import and Export", test_key_formats);
+    // measure_time("Keyring", test_keyring);
+    // measure_time("External Signer", test_callback_signer);
+    // measure_time("Vanity Address", test_vanity);
+    // measure_time("Transaction Builder", test_builder);
+    // measure_time("Generic Transaction With Account Flags", test_metas);
+    // measure_time("Raw Instruction", test_raw);
+    // measure_time("Address Lookup Table", test_alt);
+    // measure_time("Durable Nonce", test_nonce);
+    // measure_time("Partial Signing", test_partial);
+    // measure_time("Fee Sponsorship", test_sponsor);
+    // measure_time("Transaction Decoding", test_decode);
+    // measure_time("IDL Loading", test_idl);
+    // measure_time("IDL Instruction", test_idl_call);
+    // measure_time("Program Error", test_program_error_call);
+    // measure_time("IDL Account Resolution", test_idl_resolved_call);
+    // measure_time("IDL Codec", test_codec);
+    // measure_time("Anchor Events", test_events);
+    // measure_time("Program Error After CPI", test_cpi_error);
+    // measure_time("Send Results", test_results);
+    // measure_time("Relayer Refusal", test_relayer);
 }
 
 int main()